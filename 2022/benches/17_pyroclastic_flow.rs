@@ -0,0 +1,19 @@
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use advent_of_code_2022::input_generator::jet_pattern;
+use advent_of_code_2022::pyroclastic_flow::pyroclastic_flow_from_str;
+
+fn pyroclastic_flow_generated_jets(c: &mut Criterion) {
+    let mut group = c.benchmark_group("task 17 - Pyroclastic Flow - generated jets");
+
+    for jets in [1_000, 10_000] {
+        let input = jet_pattern(&mut StdRng::seed_from_u64(17), jets);
+        group.bench_with_input(BenchmarkId::from_parameter(jets), &input, |b, input| b.iter(|| pyroclastic_flow_from_str(input, 2022)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, pyroclastic_flow_generated_jets);
+criterion_main!(benches);
@@ -1,26 +1,9 @@
-use std::fmt::{Debug, Formatter};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
+use std::io::BufRead;
 
 use itertools::Itertools;
 
-#[derive(Copy, Clone)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn distance(&self, other: &Self) -> usize {
-        ((self.x - other.x).abs() + (self.y - other.y).abs()) as usize
-    }
-}
-
-impl Debug for Point {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
-    }
-}
+use crate::manhattan::{row_coverage, uncovered_point, Ball, Point, Rectangle};
 
 type Sensors = Vec<Point>;
 type Beacons = Vec<Point>;
@@ -30,16 +13,15 @@ fn parse_point(s: &str) -> Point {
         .map(|ss| ss.trim())
         .map(|ss| ss.trim_start_matches("x="))
         .map(|ss| ss.trim_start_matches("y="))
-        .map(|ss| ss.parse::<i32>().unwrap())
+        .map(|ss| ss.parse::<i64>().unwrap())
         .collect_tuple()
         .unwrap();
 
     Point { x, y }
 }
 
-fn read_input(file_name: &str) -> (Sensors, Beacons) {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
+fn parse_input(input: &str) -> (Sensors, Beacons) {
+    let mut reader = input.as_bytes();
 
     let mut sensors = Vec::new();
     let mut beacons = Vec::new();
@@ -66,110 +48,87 @@ fn read_input(file_name: &str) -> (Sensors, Beacons) {
     (sensors, beacons)
 }
 
-fn find_edges_of_circles(circles: &Vec<(Point, usize)>) -> (i32, i32) {
-    let mut x_min = i32::MAX;
-    let mut x_max = i32::MIN;
-
-    for (center, radius) in circles {
-        let left_most = center.x - *radius as i32;
-        let right_most = center.x + *radius as i32;
+/// Ball around every sensor reaching its closest beacon, no other beacon can be inside.
+pub fn sensor_balls(input: &str) -> Vec<Ball> {
+    let (sensors, beacons) = parse_input(input);
 
-        if left_most < x_min {
-            x_min = left_most
-        }
-        if right_most > x_max {
-            x_max = right_most
-        }
-    }
+    sensors.into_iter()
+        .zip(beacons)
+        .map(|(sensor, beacon)| Ball { center: sensor, radius: sensor.distance(&beacon) })
+        .collect()
+}
 
-    (x_min, x_max)
+pub fn beacon_exclusion_zone_part_1(file_name: &str, row_number: i64) -> u64 {
+    beacon_exclusion_zone_part_1_from_str(&fs::read_to_string(file_name).unwrap(), row_number)
 }
 
-fn calculate_radiuses(centers: &[Point], edge_points: &[Point]) -> Vec<(Point, usize)> {
-    let mut results = Vec::new();
+pub fn beacon_exclusion_zone_part_1_from_str(input: &str, row_number: i64) -> u64 {
+    let (_, beacons) = parse_input(input);
+    let coverage = row_coverage(&sensor_balls(input), row_number);
 
-    for (index, center) in centers.iter().enumerate() {
-        let radius = center.distance(&edge_points[index]);
-        results.push((*center, radius))
-    }
+    // beacons are always covered by their sensor's ball
+    let beacons_in_row = beacons.iter()
+        .filter(|beacon| beacon.y == row_number)
+        .map(|beacon| beacon.x)
+        .unique()
+        .count();
 
-    results
+    coverage.len() - beacons_in_row as u64
 }
 
-pub fn beacon_exclusion_zone_part_1(file_name: &str, row_number: i32) -> i32 {
-    let (sensors, beacons) = read_input(file_name);
-
-    let circles = calculate_radiuses(&sensors, &beacons);
-    println!("{sensors:?}");
-    println!("{beacons:?}");
+pub fn beacon_exclusion_zone_part_2(file_name: &str, square_width: i64) -> u64 {
+    beacon_exclusion_zone_part_2_from_str(&fs::read_to_string(file_name).unwrap(), square_width)
+}
 
-    let (x_min, x_max) = find_edges_of_circles(&circles);
-    println!("x_min: {}\t\t x_max: {}", x_min, x_max);
+pub fn beacon_exclusion_zone_part_2_from_str(input: &str, square_width: i64) -> u64 {
+    let square = Rectangle { min: Point { x: 0, y: 0 }, max: Point { x: square_width, y: square_width } };
+    let beacon = uncovered_point(&sensor_balls(input), &square).expect("distress beacon should be in the square");
 
-    let mut sum = 0;
+    beacon.x as u64 * 4_000_000 + beacon.y as u64
+}
 
-    let y = row_number;
-    for x in x_min..=x_max {
-        let curr_point = Point { x, y };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manhattan::uncovered_area;
 
-        let mut is_accessible = true;
-        for (index, (center, radius)) in circles.iter().enumerate() {
-            if curr_point.distance(&sensors[index]) == 0 || curr_point.distance(&beacons[index]) == 0 {
-                break;
-            }
-            if curr_point.distance(center) <= *radius {
-                is_accessible = false;
-                break;
-            }
-        }
+    #[test]
+    fn part_1_input_example() {
+        let answer = beacon_exclusion_zone_part_1("inputs/15_input_example.txt", 10);
 
-        if !is_accessible {
-            sum += 1;
-            print!("#");
-        } else {
-            print!(".");
-        }
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 26);
     }
-    println!();
-
-    sum
-}
 
-pub fn beacon_exclusion_zone_part_2(file_name: &str, square_width: i32) -> u64 {
-    let (sensors, beacons) = read_input(file_name);
+    #[test]
+    fn part_1_input() {
+        let answer = beacon_exclusion_zone_part_1("inputs/15_input.txt", 2_000_000);
 
-    let circles = calculate_radiuses(&sensors, &beacons);
-
-    let mut tuning_frequency = 0_u64;
-    let mut curr_point = Point { x: 0, y: 0 };
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 5403290);
+    }
 
-    let total = square_width as u64 * square_width as u64;
+    #[test]
+    fn part_2_input_example() {
+        let answer = beacon_exclusion_zone_part_2("inputs/15_input_example.txt", 20);
 
-    'outer: for _ in 0..=total {
-        if curr_point.x > square_width {
-            curr_point.x = 0;
-            curr_point.y += 1;
-        } else {
-            curr_point.x += 1;
-        }
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 56000011);
+    }
 
-        for (center, radius) in circles.iter() {
-            if curr_point.distance(center) <= *radius {
-                // jump to edge of circle in the same row
-                curr_point.x = center.x + *radius as i32 - (curr_point.y - center.y).abs();
-                continue 'outer;
-            }
-        }
+    #[test]
+    fn part_2_input() {
+        let answer = beacon_exclusion_zone_part_2("inputs/15_input.txt", 4_000_000);
 
-        println!("not in circle: {:?}", curr_point);
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 10291582906626);
+    }
 
-        assert!(curr_point.x >= 0);
-        assert!(curr_point.y >= 0);
+    #[test]
+    fn distress_beacon_is_the_only_uncovered_point() {
+        let balls = sensor_balls(&fs::read_to_string("inputs/15_input.txt").unwrap());
+        let square = Rectangle { min: Point { x: 0, y: 0 }, max: Point { x: 4_000_000, y: 4_000_000 } };
 
-        tuning_frequency = curr_point.x as u64 * 4000000 + curr_point.y as u64;
-        break 'outer;
+        assert_eq!(uncovered_area(&balls, &square), 1);
     }
-    println!();
-
-    tuning_frequency
 }
@@ -0,0 +1,7 @@
+use advent_of_code_2022::cathode_ray_tube::cathode_ray_tube_part_2;
+
+fn main() {
+    let answer = cathode_ray_tube_part_2("inputs/10_input.txt");
+
+    println!("letters on the screen are: {}", answer);
+}
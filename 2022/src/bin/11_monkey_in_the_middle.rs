@@ -1,9 +1,9 @@
-use advent_of_code_2022::monkey_in_the_middle::{DecreaseStrategy, monkey_in_the_middle};
+use advent_of_code_2022::monkey_in_the_middle::{monkey_in_the_middle, ModuloLcm};
 
-const ROUND_COUNT: usize = 10_000;
+const ROUND_COUNT: u64 = 10_000;
 
 fn main() {
-    let answer = monkey_in_the_middle("inputs/11_input.txt", ROUND_COUNT, DecreaseStrategy::DivideByDivisorProduct);
+    let answer = monkey_in_the_middle("inputs/11_input.txt", ROUND_COUNT, ModuloLcm::default());
 
     println!("Monkey business after {ROUND_COUNT} rounds is: {}", answer);
-}
\ No newline at end of file
+}
@@ -0,0 +1,17 @@
+use std::env;
+use std::fs;
+
+use advent_of_code_2022::regolith_reservoir::{Bottom, Cave};
+
+/// Prints the cave after the sand came to rest, falling into the abyss and heaped on the floor.
+fn main() {
+    let file_name = env::args().nth(1).unwrap_or("inputs/14_input_example.txt".to_string());
+    let input = fs::read_to_string(file_name).unwrap();
+
+    for bottom in [Bottom::Abyss, Bottom::Floor(2)] {
+        let mut cave = Cave::new(&input, &[(500, 0)], bottom);
+        let resting = cave.fill();
+
+        println!("{bottom:?}, {resting} units of sand:\n{}", cave.render());
+    }
+}
@@ -1,7 +1,7 @@
 pub use advent_of_code_2022::proboscidea_volcanium::proboscidea_volcanium_part_1;
 
 fn main() {
-    let answer = proboscidea_volcanium_part_1("inputs/16_input_example.txt");
+    let answer = proboscidea_volcanium_part_1("inputs/16_input.txt");
 
     println!("Released pressure: {}", answer);
 }
\ No newline at end of file
@@ -0,0 +1,22 @@
+use std::env;
+use std::fs;
+
+use advent_of_code_2022::boiling_boulders::{Droplet, Surface};
+
+/// Exports the droplet as a mesh, e.g. `cargo run --bin 18_boiling_boulders_mesh -- inputs/18_input.txt droplet.stl`,
+/// add `total` to include the faces around the air pockets.
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    if args.len() < 3 {
+        eprintln!("usage: 18_boiling_boulders_mesh <input file> <output .obj | .stl> [total]");
+        return;
+    }
+
+    let droplet = fs::read_to_string(&args[1]).unwrap().parse::<Droplet>().unwrap();
+    let surface = if args.get(3).is_some_and(|arg| arg == "total") { Surface::Total } else { Surface::Exterior };
+
+    let mesh = if args[2].ends_with(".stl") { droplet.to_stl(surface) } else { droplet.to_obj(surface) };
+    fs::write(&args[2], mesh).unwrap();
+
+    println!("{} faces written to {}", droplet.surface(surface), args[2]);
+}
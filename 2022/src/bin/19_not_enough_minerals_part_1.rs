@@ -1,7 +1,14 @@
-pub use advent_of_code_2022::not_enough_minerals::not_enough_minerals_part_1;
+pub use advent_of_code_2022::not_enough_minerals::{max_geodes, read_input};
 
 fn main() {
-    let answer = not_enough_minerals_part_1("inputs/19_input_example.txt");
+    let answer: u32 = read_input("inputs/19_input.txt").iter()
+        .map(|blueprint| {
+            let optimum = max_geodes(blueprint, 24);
+            println!("blueprint {} opens {} geodes: {:?}", blueprint.id, optimum.geodes, optimum.timeline);
 
-    println!("answer {}", answer);
-}
\ No newline at end of file
+            blueprint.id * optimum.geodes
+        })
+        .sum();
+
+    println!("Sum of quality levels: {}", answer);
+}
@@ -0,0 +1,14 @@
+pub use advent_of_code_2022::not_enough_minerals::{max_geodes, read_input};
+
+fn main() {
+    let answer: u32 = read_input("inputs/19_input.txt").iter().take(3)
+        .map(|blueprint| {
+            let optimum = max_geodes(blueprint, 32);
+            println!("blueprint {} opens {} geodes: {:?}", blueprint.id, optimum.geodes, optimum.timeline);
+
+            optimum.geodes
+        })
+        .product();
+
+    println!("Product of max geodes of the first three blueprints: {}", answer);
+}
@@ -1,7 +1,7 @@
 pub use advent_of_code_2022::grove_positioning_system::grove_positioning_system_part_1;
 
 fn main() {
-    let answer = grove_positioning_system_part_1("inputs/20_input_example.txt");
+    let answer = grove_positioning_system_part_1("inputs/20_input.txt");
 
     println!("sum of coordinates is: {}", answer);
 }
\ No newline at end of file
@@ -1,7 +1,13 @@
-pub use advent_of_code_2022::monkey_math::monkey_math_part_2;
+use std::fs;
+
+pub use advent_of_code_2022::monkey_math::{monkey_equation, monkey_math_part_2};
 
 fn main() {
+    let equation = monkey_equation(&fs::read_to_string("inputs/21_input.txt").unwrap(), &["humn"]).simplify().unwrap();
+    println!("{equation}");
+    println!("{}", equation.isolate("humn").unwrap());
+
     let answer = monkey_math_part_2("inputs/21_input.txt");
 
     println!("I should yell number: {}", answer);
-}
\ No newline at end of file
+}
@@ -1,7 +1,7 @@
 pub use advent_of_code_2022::unstable_diffusion::unstable_diffusion_part_1;
 
 fn main() {
-    let answer = unstable_diffusion_part_1("inputs/23_input_example.txt");
+    let answer = unstable_diffusion_part_1("inputs/23_input.txt");
 
     println!("Number of free tiles: {}", answer);
 }
\ No newline at end of file
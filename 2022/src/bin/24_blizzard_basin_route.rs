@@ -0,0 +1,16 @@
+use std::env;
+use std::fs;
+
+use advent_of_code_2022::blizzard_basin::Valley;
+
+/// Prints the valley in every minute of the fastest trip there, back and there again.
+fn main() {
+    let file_name = env::args().nth(1).unwrap_or("inputs/24_input_example.txt".to_string());
+    let valley = fs::read_to_string(file_name).unwrap().parse::<Valley>().unwrap();
+    let (start, goal) = (valley.start(), valley.goal());
+
+    let route = valley.route(&[start, goal, start, goal]).expect("no route through the valley");
+    for (minute, &position) in route.iter().enumerate() {
+        println!("Minute {minute}:\n{}", valley.render(minute, position));
+    }
+}
@@ -0,0 +1,7 @@
+use advent_of_code_2022::full_of_hot_air::full_of_hot_air_part_1;
+
+fn main() {
+    let answer = full_of_hot_air_part_1("inputs/25_input_example.txt");
+
+    println!("SNAFU number to supply to Bob's console: {}", answer);
+}
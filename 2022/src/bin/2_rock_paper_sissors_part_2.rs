@@ -1,5 +1,7 @@
 use advent_of_code_2022::rock_paper_scissors::rock_paper_scissors_part_2;
 
 fn main() {
-    let _ = rock_paper_scissors_part_2("inputs/2_input.txt");
+    let answer = rock_paper_scissors_part_2("inputs/2_input.txt");
+
+    println!("total points: {}", answer);
 }
\ No newline at end of file
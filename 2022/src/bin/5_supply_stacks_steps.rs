@@ -0,0 +1,16 @@
+use std::env;
+use std::fs;
+
+use advent_of_code_2022::supply_stacks::{CrateMover9001, parse_input};
+
+/// Prints the drawing of the stacks after every move of the CrateMover 9001.
+fn main() {
+    let file_name = env::args().nth(1).unwrap_or("inputs/5_input_example.txt".to_string());
+    let (mut cargo, moves) = parse_input(&fs::read_to_string(file_name).unwrap());
+
+    println!("{}", cargo.render());
+    for step in moves.iter() {
+        cargo.apply(&CrateMover9001, step);
+        println!("move {} from {} to {}\n{}", step.quantity, cargo.label(step.from), cargo.label(step.to), cargo.render());
+    }
+}
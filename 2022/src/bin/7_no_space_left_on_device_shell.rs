@@ -0,0 +1,63 @@
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+
+use advent_of_code_2022::no_space_left_on_device::{canonical_transcript, replay, Shell};
+
+/// Replays a transcript, reports its inconsistencies and lets you look around the reconstructed filesystem.
+fn main() {
+    let file_name = env::args().nth(1).unwrap_or("inputs/7_input.txt".to_string());
+    let (file_system, inconsistencies) = replay(&fs::read_to_string(file_name).unwrap());
+
+    for inconsistency in inconsistencies.iter() {
+        println!("{inconsistency}");
+    }
+    println!("commands: cd <dir>, ls, pwd, du, tree, find <min size>, free <bytes>, transcript, exit");
+
+    let mut shell = Shell::new(file_system);
+    loop {
+        print!("{} $ ", shell.file_system().path(shell.cwd()));
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        if io::stdin().lock().read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+
+        let file_system = shell.file_system();
+        match line.split_whitespace().collect::<Vec<_>>()[..] {
+            ["cd", param] => {
+                if let Err(error) = shell.cd(param) {
+                    println!("{error}");
+                }
+            }
+            ["ls"] => print!("{}", shell.ls()),
+            ["pwd"] => println!("{}", file_system.path(shell.cwd())),
+            ["du"] => {
+                for (path, size) in file_system.du() {
+                    println!("{size}\t{path}");
+                }
+            }
+            ["tree"] => print!("{}", file_system.tree()),
+            ["find", min_size] => match min_size.parse::<u64>() {
+                Ok(min_size) => {
+                    for dir in file_system.find_dirs(min_size..) {
+                        println!("{}", file_system.path(dir));
+                    }
+                }
+                Err(error) => println!("{error}"),
+            },
+            ["free", bytes] => match bytes.parse::<u64>() {
+                Ok(bytes) => match file_system.smallest_dir_to_free(70_000_000, bytes) {
+                    Some(dir) => println!("{} ({})", file_system.path(dir), file_system.size(dir)),
+                    None => println!("no dir frees {bytes} bytes"),
+                },
+                Err(error) => println!("{error}"),
+            },
+            ["transcript"] => print!("{}", canonical_transcript(file_system)),
+            ["exit"] => break,
+            [] => {}
+            _ => println!("unknown command: {}", line.trim()),
+        }
+    }
+}
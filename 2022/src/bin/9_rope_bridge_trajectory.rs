@@ -0,0 +1,12 @@
+use advent_of_code_2022::rope_bridge::{read_input, FollowRule, Rope};
+
+/// Prints the trajectory of the 10 knot rope as CSV, e.g. for plotting.
+fn main() {
+    let mut rope = Rope::new(10, FollowRule::Standard).with_trajectory();
+
+    for (direction, distance) in read_input("inputs/9_input_example_2.txt") {
+        rope.apply(direction, distance);
+    }
+
+    print!("{}", rope.export_trajectory().unwrap());
+}
@@ -0,0 +1,38 @@
+use std::env;
+use std::fs;
+
+use advent_of_code_2022::input_generator::{anonymise_valve_network, blueprints, droplet, encrypted_file, jet_pattern, rock_paths, valve_network};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Prints a shareable puzzle input, e.g. `cargo run --bin generate_input -- 16 42 60`
+/// for a network of 60 valves generated from seed 42,
+/// or `cargo run --bin generate_input -- 16 42 inputs/16_input.txt` to anonymise a real one.
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    if args.len() != 4 {
+        eprintln!("usage: generate_input <day> <seed> <size | input file to anonymise>");
+        return;
+    }
+
+    let day = args[1].as_str();
+    let mut rng = StdRng::seed_from_u64(args[2].parse().expect("seed should be a number"));
+
+    let input = match args[3].parse::<usize>() {
+        Ok(size) => match day {
+            "14" => rock_paths(&mut rng, size, 4),
+            "16" => valve_network(&mut rng, size, size / 4),
+            "17" => jet_pattern(&mut rng, size),
+            "18" => droplet(&mut rng, 20, size),
+            "19" => blueprints(&mut rng, size),
+            "20" => encrypted_file(&mut rng, size),
+            _ => panic!("no input generator of day {day}"),
+        },
+        Err(_) => match day {
+            "16" => anonymise_valve_network(&mut rng, &fs::read_to_string(&args[3]).unwrap()),
+            _ => panic!("no input anonymiser of day {day}"),
+        },
+    };
+
+    print!("{input}");
+}
@@ -1,10 +1,11 @@
-use std::{fs, iter};
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::fmt::{Debug, Formatter};
+use std::convert::Infallible;
+use std::fs;
+use std::str::FromStr;
 
-type Map = Vec<Vec<Tile>>;
+use cellular_automaton::{Automaton, DenseGrid, Grid, Point};
+use num::integer::lcm;
 
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
 enum Direction {
     Up,
     Right,
@@ -12,367 +13,330 @@ enum Direction {
     Left,
 }
 
-enum Tile {
-    Ground,
-    Blizzard(Direction),
-}
-
-impl Debug for Tile {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+impl Direction {
+    fn offset(&self) -> Point {
         match self {
-            Tile::Ground => write!(f, "."),
-            Tile::Blizzard(dir) => match dir {
-                Direction::Up => write!(f, "^"),
-                Direction::Right => write!(f, ">"),
-                Direction::Down => write!(f, "v"),
-                Direction::Left => write!(f, "<"),
-            }
+            Direction::Up => Point::new(-1, 0),
+            Direction::Right => Point::new(0, 1),
+            Direction::Down => Point::new(1, 0),
+            Direction::Left => Point::new(0, -1),
         }
     }
-}
-
-#[derive(Eq, PartialEq, Hash, Copy, Clone, Ord, PartialOrd)]
-struct Point {
-    row: usize,
-    col: usize,
-}
 
-impl Point {
-    fn up(&self) -> Point {
-        Point {
-            row: self.row - 1,
-            col: self.col,
+    fn symbol(&self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
         }
     }
 
-    fn down(&self) -> Point {
-        Point {
-            row: self.row + 1,
-            col: self.col,
-        }
+    /// Bit of the direction in a tile holding blizzards.
+    fn bit(&self) -> u8 {
+        1 << *self as u8
     }
+}
 
-    fn right(&self) -> Point {
-        Point {
-            row: self.row,
-            col: self.col + 1,
-        }
+const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+/// Fixed size set of tile indices.
+#[derive(Clone)]
+struct Bitset {
+    words: Vec<u64>,
+}
+
+impl Bitset {
+    fn new(size: usize) -> Self {
+        Bitset { words: vec![0; size.div_ceil(64)] }
     }
 
-    fn left(&self) -> Point {
-        Point {
-            row: self.row,
-            col: self.col - 1,
-        }
+    fn contains(&self, index: usize) -> bool {
+        self.words[index / 64] & (1 << (index % 64)) != 0
     }
-}
 
-impl Debug for Point {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.row, self.col)
+    /// Returns whether the index was not in the set yet.
+    fn insert(&mut self, index: usize) -> bool {
+        let absent = !self.contains(index);
+        self.words[index / 64] |= 1 << (index % 64);
+        absent
     }
 }
 
-fn read_input(file_name: &str) -> Map {
-    fs::read_to_string(file_name)
-        .unwrap()
-        .split("\r\n")
-        .skip(1)
-        .take_while(|line| !line.starts_with("##"))
-        .map(|line| line.trim_matches('#')
-            .chars()
-            .map(|ch| if ch == '.' {
-                Tile::Ground
-            } else {
-                Tile::Blizzard(match ch {
-                    '^' => Direction::Up,
-                    '>' => Direction::Right,
-                    'v' => Direction::Down,
-                    '<' => Direction::Left,
-                    _ => panic!()
-                })
-            }).collect::<Vec<_>>()
-        ).collect::<Vec<_>>()
+/// Blizzards move one tile a minute in their direction, wrapping around at the walls.
+/// Tiles hold the bits of the directions of their blizzards.
+fn blizzard_winds(width: i32, height: i32) -> Automaton<'static, DenseGrid<u8>> {
+    Automaton::synchronous(move |blizzards: &DenseGrid<u8>, p, _| {
+        DIRECTIONS.iter()
+            .map(|dir| {
+                let offset = dir.offset();
+                let from = Point::new((p.row - offset.row).rem_euclid(height), (p.col - offset.col).rem_euclid(width));
+                blizzards.get(from) & dir.bit()
+            })
+            .fold(0, |tile, bit| tile | bit)
+    })
 }
 
-struct TaskData {
-    height: usize,
-    width: usize,
-    blizzards_horizontally: HashMap<Point, HashSet<usize>>,
-    blizzards_vertically: HashMap<Point, HashSet<usize>>,
+/// Valley without the surrounding walls, the start is above the top-left tile and the goal below the bottom-right one.
+/// Blizzards repeat their positions every `lcm(width, height)` minutes, so the blizzards of every minute of the period
+/// are precomputed by letting them blow.
+pub struct Valley {
+    width: i32,
+    height: i32,
+    /// blizzards in each minute of the period
+    minutes: Vec<DenseGrid<u8>>,
 }
 
-pub fn blizzard_basin_part_1(filename: &str) -> usize {
-    let input = read_input(filename);
-    let (blizzards_horizontally, blizzards_vertically) = calculate_blizzards_distances(&input);
-    let (width, height) = (input[0].len(), input.len());
-
-    let task_data = TaskData {
-        height,
-        width,
-        blizzards_horizontally,
-        blizzards_vertically,
-    };
-
-    let start = Point { row: 0, col: 1 };
-    let goal = Point { row: task_data.height + 1, col: task_data.width };
-    let start_minute = 0;
-
-    let (result, _came_from) = a_star(start, goal, start_minute, &task_data, manhattan_dist_plus_minute).unwrap();
-
-    // for minute in 0..=12 {
-    //     println!("== MINUTE {minute} ==");
-    //     for row in 1..=height {
-    //         for col in 1..=width {
-    //             // '☠'
-    //             let ch = if is_clear(&Point { row, col }, minute, &task_data) {
-    //                 '▮'
-    //             } else {
-    //                 '▯'
-    //             };
-    //             print!("{}", ch);
-    //         }
-    //         println!()
-    //     }
-    // }
-
-    result
-}
+impl Valley {
+    fn new(blizzards: DenseGrid<u8>) -> Self {
+        let (width, height) = (blizzards.width() as i32, blizzards.height() as i32);
+        let period = lcm(width, height) as usize;
+
+        let mut winds = blizzard_winds(width, height);
+        let mut minutes = vec![blizzards];
+        while minutes.len() < period {
+            let mut next = minutes.last().unwrap().clone();
+            winds.step(&mut next);
+            minutes.push(next);
+        }
 
-pub fn blizzard_basin_part_2(filename: &str) -> usize {
-    let input = read_input(filename);
-    let (blizzards_horizontally, blizzards_vertically) = calculate_blizzards_distances(&input);
-    let (width, height) = (input[0].len(), input.len());
-
-    let task_data = TaskData {
-        height,
-        width,
-        blizzards_horizontally,
-        blizzards_vertically,
-    };
-
-    let start = Point { row: 0, col: 1 };
-    let goal = Point { row: task_data.height + 1, col: task_data.width };
-
-    let algorithm_data = [
-        (start, goal),
-        (goal, start),
-        (start, goal),
-    ];
-
-    let mut sum = 0usize;
-    let mut start_minute = 0;
-    for (begin, end) in algorithm_data.into_iter() {
-        let (minutes, _) = a_star(begin, end, start_minute, &task_data, manhattan_dist_plus_minute).unwrap();
-        sum += minutes;
-        start_minute = sum;
+        Valley { width, height, minutes }
     }
 
-    sum
-}
+    pub fn start(&self) -> Point {
+        Point::new(-1, 0)
+    }
 
-fn manhattan_dist_plus_minute((p1, min): (&Point, usize), p2: &Point) -> u32 {
-    (p1.row.abs_diff(p2.row) + p1.col.abs_diff(p2.col)) as u32 + min as u32
-}
+    pub fn goal(&self) -> Point {
+        Point::new(self.height, self.width - 1)
+    }
+
+    pub fn period(&self) -> usize {
+        self.minutes.len()
+    }
 
-fn a_star(
-    start: Point,
-    goal: Point,
-    start_minute: usize,
-    task_data: &TaskData,
-    h: fn((&Point, usize), &Point) -> u32,
-) -> Option<(usize, HashMap<Point, Point>)> {
-    let mut open_set = HashSet::from([(start, start_minute)]);
-    let mut came_from = HashMap::new();
-    let mut g_score = HashMap::new();
-    let mut f_score = HashMap::new();
-
-    g_score.entry((start, start_minute)).or_insert(0);
-    f_score.entry((start, start_minute)).or_insert(h((&start, start_minute), &goal));
-
-    while !open_set.is_empty()
-    {
-        let (current_point, current_minute) = *open_set.iter()
-            .min_by(|&elem1, &elem2| f_score[elem1].cmp(&f_score[elem2]))
-            .unwrap();
-
-        open_set.remove(&(current_point, current_minute));
-
-        if current_point == goal {
-            return Some((current_minute - start_minute, came_from));
+    /// Whether the expedition can stand on the tile in given minute.
+    fn is_free(&self, point: Point, minute: usize) -> bool {
+        if point == self.start() || point == self.goal() {
+            return true;
         }
 
-        let available_neighbors = neighbors_of(current_point, task_data.height, task_data.width).into_iter()
-            .chain(iter::once(current_point))
-            .filter(|p| is_clear(p, current_minute + 1, task_data))
-            .collect::<Vec<_>>();
+        (0..self.height).contains(&point.row)
+            && (0..self.width).contains(&point.col)
+            && self.minutes[minute % self.period()].get(point) == 0
+    }
 
-        for neighbor in available_neighbors {
-            let score_of_current = *g_score.entry((current_point, current_minute)).or_insert(usize::MAX);
-            let score_of_neighbor = *g_score.entry((neighbor, current_minute + 1)).or_insert(usize::MAX);
+    /// Fastest route visiting the waypoints in order, starting at the first one in minute 0.
+    /// Returns the position of the expedition in every minute, or `None` if some waypoint cannot be reached.
+    ///
+    /// Searches breadth first over (waypoints visited, tile, minute in the period), since being in the same
+    /// state later again cannot lead to an earlier arrival.
+    pub fn route(&self, waypoints: &[Point]) -> Option<Vec<Point>> {
+        let (first, rest) = waypoints.split_first()?;
+        if !self.is_free(*first, 0) {
+            return None;
+        }
 
-            let tentative_g_score = score_of_current + 1;
-            if tentative_g_score <= score_of_neighbor {
-                came_from.entry(neighbor)
-                    .and_modify(|x| *x = current_point)
-                    .or_insert(current_point);
+        let tiles = ((self.width + 2) * (self.height + 2)) as usize;
+        let state_index = |leg: usize, point: Point, minute: usize| {
+            // tiles are shifted to fit the start and the goal outside of the valley
+            let tile = ((point.row + 1) * (self.width + 2) + point.col + 1) as usize;
+            (leg * self.period() + minute % self.period()) * tiles + tile
+        };
+
+        let mut visited = Bitset::new((rest.len() + 1) * self.period() * tiles);
+        visited.insert(state_index(0, *first, 0));
+
+        // every reached state with the index of the state it was reached from
+        let mut states: Vec<(usize, Point, Option<usize>)> = vec![(0, *first, None)];
+        let mut frontier = 0..1;
+        let mut minute = 0;
+
+        loop {
+            if let Some(end) = frontier.clone().find(|&index| states[index].0 == rest.len()) {
+                let mut route = vec![states[end].1];
+                let mut current = states[end].2;
+                while let Some(index) = current {
+                    route.push(states[index].1);
+                    current = states[index].2;
+                }
+                route.reverse();
+
+                return Some(route);
+            }
+            if frontier.is_empty() {
+                return None;
+            }
 
-                g_score.entry((neighbor, current_minute + 1))
-                    .and_modify(|x| *x = tentative_g_score);
+            minute += 1;
+            let next_start = states.len();
 
-                f_score.entry((neighbor, current_minute + 1))
-                    .and_modify(|x| *x = tentative_g_score as u32 + h((&neighbor, current_minute + 1), &goal))
-                    .or_insert(tentative_g_score as u32 + h((&neighbor, current_minute + 1), &goal));
+            for index in frontier {
+                let (leg, point, _) = states[index];
+                let moves = point.neighbours_4().into_iter().chain([point]);
 
-                if !open_set.contains(&(neighbor, current_minute + 1)) {
-                    open_set.insert((neighbor, current_minute + 1));
+                for next in moves.filter(|&next| self.is_free(next, minute)) {
+                    let leg = if next == rest[leg] { leg + 1 } else { leg };
+                    if visited.insert(state_index(leg, next, minute)) {
+                        states.push((leg, next, Some(index)));
+                    }
                 }
             }
+
+            frontier = next_start..states.len();
         }
     }
 
-    println!("Open set is empty but goal was never reached!");
-    None
+    /// Valley with its walls in given minute, with the expedition drawn as `E`.
+    pub fn render(&self, minute: usize, expedition: Point) -> String {
+        let entrance_row = |entrance: Point| (0..self.width + 2)
+            .map(|col| match col - 1 {
+                col if Point::new(entrance.row, col) == expedition => 'E',
+                col if col == entrance.col => '.',
+                _ => '#',
+            })
+            .collect::<String>();
+
+        let mut lines = vec![entrance_row(self.start())];
+        for (row, tiles) in self.minutes[minute % self.period()].rows().enumerate() {
+            let line = tiles.iter()
+                .enumerate()
+                .map(|(col, &tile)| match tile.count_ones() {
+                    _ if Point::new(row as i32, col as i32) == expedition => 'E',
+                    0 => '.',
+                    1 => DIRECTIONS.iter().find(|dir| dir.bit() == tile).unwrap().symbol(),
+                    n => char::from_digit(n, 10).unwrap(),
+                })
+                .collect::<String>();
+            lines.push(format!("#{line}#"));
+        }
+        lines.push(entrance_row(self.goal()));
+
+        lines.join("\n") + "\n"
+    }
+}
+
+fn parse_input(input: &str) -> Valley {
+    let rows = input
+        .lines()
+        .skip(1)
+        .take_while(|line| !line.starts_with("##"))
+        .map(|line| line.trim_matches('#')
+            .chars()
+            .map(|ch| match ch {
+                '.' => 0,
+                '^' => Direction::Up.bit(),
+                '>' => Direction::Right.bit(),
+                'v' => Direction::Down.bit(),
+                '<' => Direction::Left.bit(),
+                _ => panic!("invalid tile: {ch}")
+            })
+            .collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    Valley::new(DenseGrid::from_rows(rows))
 }
 
-fn neighbors_of(p: Point, height: usize, width: usize) -> Vec<Point> {
-    let mut neighbors = Vec::new();
+impl FromStr for Valley {
+    type Err = Infallible;
 
-    if p.col != width + 1 {
-        neighbors.push(p.right())
-    }
-    if p.row != height + 1 {
-        neighbors.push(p.down())
-    }
-    if p.row != 0 {
-        neighbors.push(p.up())
-    }
-    if p.col != 0 {
-        neighbors.push(p.left())
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_input(s))
     }
-    neighbors
 }
 
-fn is_clear(
-    point: &Point,
-    minute: usize,
-    task_data: &TaskData,
-) -> bool {
-    let (width, height) = (task_data.width, task_data.height);
-    let start = Point { row: 0, col: 1 };
-    let end = Point { row: height + 1, col: width };
-
-    if *point == start || *point == end {
-        return true;
-    }
+/// Minutes of the fastest trip through the waypoints.
+fn trip_minutes(valley: &Valley, waypoints: &[Point]) -> usize {
+    valley.route(waypoints).expect("waypoints should be reachable").len() - 1
+}
 
-    // points on edges
-    if point.row == 0 || point.row == height + 1 || point.col == 0 || point.col == width + 1 {
-        return false;
-    }
+pub fn blizzard_basin_part_1(filename: &str) -> usize {
+    blizzard_basin_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
 
-    for blizzard in task_data.blizzards_horizontally[point].iter() {
-        if (width * 1000 + *blizzard - minute) % width == 0 {
-            return false;
-        }
-    }
+pub fn blizzard_basin_part_1_from_str(input: &str) -> usize {
+    let valley = parse_input(input);
 
-    for blizzard in task_data.blizzards_vertically[point].iter() {
-        if (height * 1000 + *blizzard - minute) % height == 0 {
-            return false;
-        }
-    }
+    trip_minutes(&valley, &[valley.start(), valley.goal()])
+}
 
-    true
+pub fn blizzard_basin_part_2(filename: &str) -> usize {
+    blizzard_basin_part_2_from_str(&fs::read_to_string(filename).unwrap())
 }
 
-/// Creates maps, that represents how far are blizzards for each point at the map.
-/// Points of the map's rectangle have coordinates starting from 1 (not zero),
-/// to allow adding start point above with non-negative value.
-fn calculate_blizzards_distances(input: &Map) -> (HashMap<Point, HashSet<usize>>, HashMap<Point, HashSet<usize>>) {
-    let mut blizzards_horizontally = HashMap::new();
-    let mut blizzards_vertically = HashMap::new();
-    let (width, height) = (input[0].len(), input.len());
-
-    for row in 0..height {
-        for col in 0..width {
-            let mut horizontal_blizzards_distances = HashSet::new();
-            for i in 0..width {
-                if let Tile::Blizzard(dir) = &input[row][(col + i) % width] {
-                    match dir {
-                        Direction::Right => horizontal_blizzards_distances.insert(width - i),
-                        Direction::Left => horizontal_blizzards_distances.insert(i),
-                        _ => true,
-                    };
-                }
-            }
-            blizzards_horizontally.insert(Point { row: row + 1, col: col + 1 }, horizontal_blizzards_distances);
-
-            let mut vertical_blizzards_distances = HashSet::new();
-            for i in 0..height {
-                if let Tile::Blizzard(dir) = &input[(row + i) % height][col] {
-                    match dir {
-                        Direction::Down => vertical_blizzards_distances.insert(height - i),
-                        Direction::Up => vertical_blizzards_distances.insert(i),
-                        _ => true
-                    };
-                }
-            }
-            blizzards_vertically.insert(Point { row: row + 1, col: col + 1 }, vertical_blizzards_distances);
-        }
-    }
+pub fn blizzard_basin_part_2_from_str(input: &str) -> usize {
+    let valley = parse_input(input);
+    let (start, goal) = (valley.start(), valley.goal());
 
-    (blizzards_horizontally, blizzards_vertically)
+    trip_minutes(&valley, &[start, goal, start, goal])
 }
 
-fn print_input(input: &Map) {
-    for row in input {
-        for tile in row {
-            print!("{:?}", tile);
-        }
-        println!()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_input_example_1() {
+        let answer = blizzard_basin_part_1("inputs/24_input_example.txt");
+
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 18);
     }
-}
 
-#[allow(dead_code)]
-pub fn blizzard_basin_part_1_bfs(filename: &str) -> usize {
-    let input = read_input(filename);
-    print_input(&input);
+    #[test]
+    fn part_1_input() {
+        let answer = blizzard_basin_part_1("inputs/24_input.txt");
 
-    let (blizzards_horizontally, blizzards_vertically) = calculate_blizzards_distances(&input);
-    let (width, height) = (input[0].len(), input.len());
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 274);
+    }
 
-    let task_data = TaskData {
-        height,
-        width,
-        blizzards_horizontally,
-        blizzards_vertically,
-    };
+    #[test]
+    fn part_2_input_example_1() {
+        let answer = blizzard_basin_part_2("inputs/24_input_example.txt");
 
-    let mut queue = VecDeque::from([(Point { row: 0, col: 0 }, 1usize)]);
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 54);
+    }
 
-    while !queue.is_empty()
-    {
-        let (point, minute) = queue.pop_front().unwrap();
+    #[test]
+    fn part_2_input() {
+        let answer = blizzard_basin_part_2("inputs/24_input.txt");
 
-        if queue.len() % 100000 == 0 {
-            println!("point: {:?}, minute {}\t\t (queue size: {})", point, minute, queue.len());
-        }
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 839);
+    }
 
-        if point.row == height - 1 && point.col == width - 1 {
-            return minute;
+    #[test]
+    fn route_avoids_blizzards() {
+        let valley = fs::read_to_string("inputs/24_input_example.txt").unwrap().parse::<Valley>().unwrap();
+        let (start, goal) = (valley.start(), valley.goal());
+        let route = valley.route(&[start, goal, start, goal, start]).unwrap();
+
+        assert_eq!(route.first(), Some(&start));
+        assert_eq!(route.last(), Some(&start));
+        for (minute, pair) in route.windows(2).enumerate() {
+            assert!(pair[0] == pair[1] || pair[0].neighbours_4().contains(&pair[1]));
+            assert!(valley.is_free(pair[1], minute + 1));
         }
+    }
 
-        let available_neighbors = neighbors_of(point, height, width).into_iter()
-            .chain(iter::once(point))
-            .filter(|p| is_clear(p, minute, &task_data))
-            .collect::<Vec<_>>();
+    #[test]
+    fn inner_waypoint() {
+        let valley = fs::read_to_string("inputs/24_input_example.txt").unwrap().parse::<Valley>().unwrap();
+        let waypoint = Point::new(2, 3);
+        let route = valley.route(&[valley.start(), waypoint, valley.goal()]).unwrap();
 
-        for neighbor in available_neighbors {
-            queue.push_back((neighbor, minute + 1));
-        }
+        assert!(route.contains(&waypoint));
+        assert_eq!(route.len() - 1, 18);
     }
 
-    println!("queue is empty");
-    1
+    #[test]
+    fn render_input_example() {
+        let valley = fs::read_to_string("inputs/24_input_example.txt").unwrap().parse::<Valley>().unwrap();
+
+        assert_eq!(valley.period(), 12);
+        assert_eq!(valley.render(0, valley.start()), "#E######\n#>>.<^<#\n#.<..<<#\n#>v.><>#\n#<^v^^>#\n######.#\n");
+        assert_eq!(valley.render(1, Point::new(0, 0)), "#.######\n#E>3.<.#\n#<..<<.#\n#>2.22.#\n#>v..^<#\n######.#\n");
+    }
 }
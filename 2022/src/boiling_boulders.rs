@@ -1,183 +1,386 @@
-#![allow(dead_code)]
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::fmt::Write;
+use std::fs;
+use std::str::FromStr;
 
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use itertools::Itertools;
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
-struct Point {
-    x: u32,
-    y: u32,
-    z: u32,
-}
+type Voxel = [i32; 3];
 
-fn read_input(file_name: &str) -> Vec<Point> {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
-    let mut points = Vec::new();
-    let mut buf = String::new();
+const DIRECTIONS: [Voxel; 6] = [[1, 0, 0], [-1, 0, 0], [0, 1, 0], [0, -1, 0], [0, 0, 1], [0, 0, -1]];
 
-    while let Ok(n) = reader.read_line(&mut buf) {
-        if n == 0 {
-            break;
-        }
+fn neighbours(voxel: Voxel) -> impl Iterator<Item = Voxel> {
+    DIRECTIONS.iter().map(move |direction| [voxel[0] + direction[0], voxel[1] + direction[1], voxel[2] + direction[2]])
+}
 
-        let coords = buf.trim()
+fn parse_input(input: &str) -> Vec<Voxel> {
+    input.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.trim()
             .split(',')
-            .map(|s| s.parse::<u32>().unwrap())
-            .collect::<Vec<_>>();
+            .map(|s| s.parse::<i32>().unwrap())
+            .collect_tuple()
+            .map(|(x, y, z)| [x, y, z])
+            .unwrap_or_else(|| panic!("invalid cube: {line}")))
+        .collect()
+}
 
-        let point = Point {
-            x: coords[0],
-            y: coords[1],
-            z: coords[2],
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Cell {
+    Lava,
+    /// air reachable from outside of the droplet
+    Outside,
+    /// air enclosed by the lava
+    Pocket,
+}
+
+/// Face of a lava cube with its outward normal.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Face {
+    pub cube: Voxel,
+    pub normal: Voxel,
+}
+
+impl Face {
+    /// Corners counterclockwise as seen from outside.
+    fn corners(&self) -> [Voxel; 4] {
+        let axis = self.normal.iter().position(|&n| n != 0).unwrap();
+        let (b, c) = ((axis + 1) % 3, (axis + 2) % 3);
+
+        let mut base = self.cube;
+        if self.normal[axis] > 0 {
+            base[axis] += 1;
+        }
+        let corner = |db: i32, dc: i32| {
+            let mut corner = base;
+            corner[b] += db;
+            corner[c] += dc;
+            corner
         };
 
-        points.push(point);
-        buf = String::new();
+        let corners = [corner(0, 0), corner(1, 0), corner(1, 1), corner(0, 1)];
+        if self.normal[axis] > 0 {
+            corners
+        } else {
+            [corners[0], corners[3], corners[2], corners[1]]
+        }
     }
-
-    points
 }
 
-fn initialize_mappings(points: &[Point]) -> [HashMap<(u32, u32), Vec<u32>>; 3] {
-    let mut xy_to_z_map: HashMap<(u32, u32), Vec<u32>> = HashMap::new();
-    let mut xz_to_y_map: HashMap<(u32, u32), Vec<u32>> = HashMap::new();
-    let mut yz_to_x_map: HashMap<(u32, u32), Vec<u32>> = HashMap::new();
+#[derive(Debug, Eq, PartialEq)]
+pub struct AirPocket {
+    pub cells: Vec<Voxel>,
+}
 
-    for p in points.iter() {
-        xy_to_z_map.entry((p.x, p.y)).or_insert_with(Vec::new).push(p.z);
-        xz_to_y_map.entry((p.x, p.z)).or_insert_with(Vec::new).push(p.y);
-        yz_to_x_map.entry((p.y, p.z)).or_insert_with(Vec::new).push(p.x);
+impl AirPocket {
+    pub fn volume(&self) -> usize {
+        self.cells.len()
     }
-    [xy_to_z_map, xz_to_y_map, yz_to_x_map]
 }
 
-fn calculate_sides_and_gaps(mappings: &[HashMap<(u32, u32), Vec<u32>>; 3]) -> (usize, Vec<HashSet<Point>>) {
-    let mut visible_sides_total = 0_usize;
-    let mut gaps = Vec::new();
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Surface {
+    Total,
+    Exterior,
+}
 
-    for (plane_number, map) in mappings.iter().enumerate() {
-        let mut visible_sides_per_plane = 0_usize;
-        let mut gaps_per_plane = HashSet::new();
+/// Dense voxel grid of the droplet, padded by a layer of outside air,
+/// which is flood filled to tell the outside air from the air pockets.
+pub struct Droplet {
+    min: Voxel,
+    size: [usize; 3],
+    cells: Vec<Cell>,
+}
 
-        for (&(i, j), cubes_indexes) in map.iter() {
-            let mut indexes = cubes_indexes.clone();
-            indexes.sort();
-            let mut visible_sides_per_line = 2_usize;
-            // println!("{:?}: {:?}", coords, indexes);
+impl Droplet {
+    pub fn new(cubes: &[Voxel]) -> Self {
+        let bounds = |axis: usize| cubes.iter().map(|cube| cube[axis]).minmax().into_option().unwrap_or((0, 0));
+        let bounds = [bounds(0), bounds(1), bounds(2)];
 
-            let mut it = indexes.windows(2);
-            while let Some(&[left, right]) = it.next() {
-                if right - left == 1 {
-                    continue;
-                }
+        let min = bounds.map(|(min, _)| min - 1);
+        let size = bounds.map(|(min, max)| (max - min + 3) as usize);
+        let mut droplet = Droplet { min, size, cells: vec![Cell::Pocket; size.iter().product()] };
 
-                // there is a gap between lava points
-                visible_sides_per_line += 2;
+        for cube in cubes {
+            let index = droplet.index(cube).unwrap();
+            droplet.cells[index] = Cell::Lava;
+        }
 
-                for k in left + 1..right {
-                    gaps_per_plane.insert(match plane_number {
-                        0 => Point { x: i, y: j, z: k },
-                        1 => Point { x: i, y: k, z: j },
-                        2 => Point { x: k, y: i, z: j },
-                        _ => panic!()
-                    });
+        // the padding corner is always outside
+        let mut queue = VecDeque::from([min]);
+        droplet.cells[0] = Cell::Outside;
+        while let Some(voxel) = queue.pop_front() {
+            for neighbour in neighbours(voxel) {
+                if let Some(index) = droplet.index(&neighbour).filter(|&index| droplet.cells[index] == Cell::Pocket) {
+                    droplet.cells[index] = Cell::Outside;
+                    queue.push_back(neighbour);
                 }
             }
+        }
 
-            visible_sides_per_plane += visible_sides_per_line;
+        droplet
+    }
+
+    fn index(&self, voxel: &Voxel) -> Option<usize> {
+        let mut index = 0;
+        for axis in (0..3).rev() {
+            let coordinate = voxel[axis] - self.min[axis];
+            if coordinate < 0 || coordinate as usize >= self.size[axis] {
+                return None;
+            }
+            index = index * self.size[axis] + coordinate as usize;
         }
 
-        visible_sides_total += visible_sides_per_plane;
-        gaps.push(gaps_per_plane);
+        Some(index)
     }
 
-    (visible_sides_total, gaps)
-}
+    fn voxel(&self, index: usize) -> Voxel {
+        let (x, rest) = (index % self.size[0], index / self.size[0]);
+        let (y, z) = (rest % self.size[1], rest / self.size[1]);
+
+        [x as i32 + self.min[0], y as i32 + self.min[1], z as i32 + self.min[2]]
+    }
+
+    /// Cell of the voxel, everything outside of the grid is outside air.
+    fn cell(&self, voxel: &Voxel) -> Cell {
+        self.index(voxel).map_or(Cell::Outside, |index| self.cells[index])
+    }
+
+    pub fn faces(&self, surface: Surface) -> Vec<Face> {
+        (0..self.cells.len())
+            .filter(|&index| self.cells[index] == Cell::Lava)
+            .map(|index| self.voxel(index))
+            .flat_map(|cube| DIRECTIONS.iter().map(move |&normal| Face { cube, normal }))
+            .filter(|face| {
+                let neighbour = [0, 1, 2].map(|axis| face.cube[axis] + face.normal[axis]);
+                match (surface, self.cell(&neighbour)) {
+                    (_, Cell::Lava) => false,
+                    (Surface::Total, _) => true,
+                    (Surface::Exterior, cell) => cell == Cell::Outside,
+                }
+            })
+            .collect()
+    }
+
+    pub fn surface(&self, surface: Surface) -> usize {
+        self.faces(surface).len()
+    }
+
+    /// Enclosed air, each pocket 6-connected.
+    pub fn air_pockets(&self) -> Vec<AirPocket> {
+        let mut visited = vec![false; self.cells.len()];
+        let mut pockets = Vec::new();
 
-fn print_plane(plane: &HashMap<(u32, u32), Vec<u32>>) {
-    let layers_number = *plane.values().flatten().max().unwrap_or(&0);
+        for start in 0..self.cells.len() {
+            if self.cells[start] != Cell::Pocket || visited[start] {
+                continue;
+            }
 
-    let max_i = *plane.keys().map(|(i, _j)| i).max().unwrap_or(&1);
-    let max_j = *plane.keys().map(|(_i, j)| j).max().unwrap_or(&1);
+            visited[start] = true;
+            let mut queue = VecDeque::from([self.voxel(start)]);
+            let mut cells = Vec::new();
 
-    for layer_number in 1..=layers_number {
-        for i in 1..=max_i {
-            for j in 1..=max_j {
-                if plane.contains_key(&(i, j)) && plane.get(&(i, j)).unwrap().contains(&layer_number) {
-                    print!("#")
-                } else {
-                    print!(" ")
+            while let Some(voxel) = queue.pop_front() {
+                for neighbour in neighbours(voxel) {
+                    if let Some(index) = self.index(&neighbour).filter(|&index| self.cells[index] == Cell::Pocket && !visited[index]) {
+                        visited[index] = true;
+                        queue.push_back(neighbour);
+                    }
                 }
+                cells.push(voxel);
             }
-            println!()
+
+            pockets.push(AirPocket { cells });
         }
-        println!("{}", "-".repeat(max_j as usize))
-    }
-}
 
-/// Each gap point must be adjacent to lava point or to other gap point
-/// # Arguments
-/// * `lava_mapping`:
-/// * `gaps_mapping`:
-/// returns: ()
-fn remove_false_positive_gaps(lava_points: &[Point], gaps_points: &mut Vec<Point>) {
-    let mut points_to_remove = Vec::new();
+        pockets
+    }
 
-    for gap_point in gaps_points.iter() {
-        let (x, y, z) = (gap_point.x, gap_point.y, gap_point.z);
+    /// Wavefront OBJ mesh of the surface, one quad per face.
+    pub fn to_obj(&self, surface: Surface) -> String {
+        let mut vertices: HashMap<Voxel, usize> = HashMap::new();
+        let mut vertex_lines = String::new();
+        let mut face_lines = String::new();
+
+        for face in self.faces(surface) {
+            let indices = face.corners().map(|corner| {
+                let next = vertices.len() + 1;
+                *vertices.entry(corner).or_insert_with(|| {
+                    writeln!(vertex_lines, "v {} {} {}", corner[0], corner[1], corner[2]).unwrap();
+                    next
+                })
+            });
+            writeln!(face_lines, "f {} {} {} {}", indices[0], indices[1], indices[2], indices[3]).unwrap();
+        }
 
-        let up = Point { x: gap_point.x + 1, y, z };
-        let down = Point { x: gap_point.x - 1, y, z };
-        let left = Point { x, y: gap_point.y + 1, z };
-        let right = Point { x, y: gap_point.y - 1, z };
-        let forward = Point { x, y, z: gap_point.z + 1 };
-        let backward = Point { x, y, z: gap_point.z - 1 };
+        vertex_lines + &face_lines
+    }
 
-        for point in [up, down, left, right, forward, backward] {
-            if lava_points.contains(&point) || gaps_points.contains(&point) {
-                continue;
+    /// ASCII STL mesh of the surface, two triangles per face.
+    pub fn to_stl(&self, surface: Surface) -> String {
+        let mut stl = String::from("solid droplet\n");
+
+        for face in self.faces(surface) {
+            let [a, b, c, d] = face.corners();
+            for triangle in [[a, b, c], [a, c, d]] {
+                writeln!(stl, "facet normal {} {} {}", face.normal[0], face.normal[1], face.normal[2]).unwrap();
+                stl.push_str("  outer loop\n");
+                for vertex in triangle {
+                    writeln!(stl, "    vertex {} {} {}", vertex[0], vertex[1], vertex[2]).unwrap();
+                }
+                stl.push_str("  endloop\nendfacet\n");
             }
-            points_to_remove.push(gap_point.clone());
-            break;
         }
+
+        stl + "endsolid droplet\n"
     }
+}
+
+impl FromStr for Droplet {
+    type Err = Infallible;
 
-    for p in points_to_remove.iter() {
-        let index = gaps_points.iter().position(|x| x == p).unwrap();
-        gaps_points.swap_remove(index);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Droplet::new(&parse_input(s)))
     }
 }
 
 pub fn boiling_boulders_part_1(file_name: &str) -> usize {
-    let points = read_input(file_name);
-    let mappings = initialize_mappings(&points);
-
-    let (total_sides, _gaps) = calculate_sides_and_gaps(&mappings);
-    total_sides
+    boiling_boulders_part_1_from_str(&fs::read_to_string(file_name).unwrap())
 }
 
+pub fn boiling_boulders_part_1_from_str(input: &str) -> usize {
+    Droplet::new(&parse_input(input)).surface(Surface::Total)
+}
 
 pub fn boiling_boulders_part_2(file_name: &str) -> usize {
-    let points = read_input(file_name);
-    let mappings = initialize_mappings(&points);
-    let (total_sides, gaps) = calculate_sides_and_gaps(&mappings);
+    boiling_boulders_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn boiling_boulders_part_2_from_str(input: &str) -> usize {
+    let droplet = Droplet::new(&parse_input(input));
+
+    for pocket in droplet.air_pockets() {
+        println!("air pocket of volume {} at {:?}", pocket.volume(), pocket.cells[0]);
+    }
+
+    droplet.surface(Surface::Exterior)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::input_generator::droplet;
+
+    use super::*;
+
+    #[test]
+    fn part_1_input_example() {
+        let answer = boiling_boulders_part_1("inputs/18_input_example.txt");
+
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 64);
+    }
+
+    #[test]
+    fn part_1_input() {
+        let answer = boiling_boulders_part_1("inputs/18_input.txt");
+
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 4444);
+    }
+
+    #[test]
+    fn part_2_input_example() {
+        let answer = boiling_boulders_part_2("inputs/18_input_example.txt");
+
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 58);
+    }
 
-    println!("total sides: {total_sides:?}");
+    #[test]
+    fn part_2_input() {
+        let answer = boiling_boulders_part_2("inputs/18_input.txt");
 
-    let mut gaps_intersection = gaps.iter()
-        .skip(1)
-        .fold(HashSet::from_iter(gaps[0].clone()), |acc, set| &acc & set)
-        .into_iter()
-        .collect::<Vec<_>>();
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 2530);
+    }
+
+    #[test]
+    fn part_1_generated_input() {
+        let input = droplet(&mut StdRng::seed_from_u64(18), 12, 900);
+        let answer = boiling_boulders_part_1_from_str(&input);
+
+        println!("part 1 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 2822);
+    }
+
+    #[test]
+    fn part_2_generated_input() {
+        let input = droplet(&mut StdRng::seed_from_u64(18), 12, 900);
+        let answer = boiling_boulders_part_2_from_str(&input);
+
+        println!("part 2 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 2748);
+    }
+
+    #[test]
+    fn air_pockets_input_example() {
+        let droplet = fs::read_to_string("inputs/18_input_example.txt").unwrap().parse::<Droplet>().unwrap();
+
+        assert_eq!(droplet.air_pockets(), vec![AirPocket { cells: vec![[2, 2, 5]] }]);
+    }
+
+    #[test]
+    fn pockets_account_for_hidden_surface() {
+        for i in 1..=7 {
+            let droplet = fs::read_to_string(format!("inputs/18_input_example_my_{i}.txt")).unwrap().parse::<Droplet>().unwrap();
 
-    remove_false_positive_gaps(&points, &mut gaps_intersection);
+            // every face is either exterior or faces a pocket, and a pocket has as many faces as its own surface
+            let pockets_surface = droplet.air_pockets().iter()
+                .map(|pocket| Droplet::new(&pocket.cells).surface(Surface::Total))
+                .sum::<usize>();
+            assert_eq!(droplet.surface(Surface::Total), droplet.surface(Surface::Exterior) + pockets_surface, "example {i}");
+        }
+    }
+
+    #[test]
+    fn hollow_cube() {
+        let cubes = (0..5).cartesian_product(0..5).cartesian_product(0..5)
+            .map(|((x, y), z)| [x, y, z])
+            .filter(|cube| cube.iter().any(|&c| c == 0 || c == 4))
+            .collect::<Vec<_>>();
+        let droplet = Droplet::new(&cubes);
 
-    let gaps_mappings = initialize_mappings(&gaps_intersection);
-    let (gaps_sides, _) = calculate_sides_and_gaps(&gaps_mappings);
+        assert_eq!(droplet.surface(Surface::Exterior), 6 * 25);
+        assert_eq!(droplet.surface(Surface::Total), 6 * 25 + 6 * 9);
+        assert_eq!(droplet.air_pockets().iter().map(AirPocket::volume).collect::<Vec<_>>(), vec![27]);
+    }
 
-    println!("sides for gaps mappings: {:?}", gaps_sides);
+    #[test]
+    fn single_cube_mesh() {
+        let droplet = Droplet::new(&[[1, 2, 3]]);
+        let obj = droplet.to_obj(Surface::Total);
+        let stl = droplet.to_stl(Surface::Exterior);
 
-    total_sides - gaps_sides
+        assert_eq!(obj.lines().filter(|line| line.starts_with("v ")).count(), 8);
+        assert_eq!(obj.lines().filter(|line| line.starts_with("f ")).count(), 6);
+        assert_eq!(stl.lines().filter(|line| line.starts_with("facet")).count(), 12);
+        assert!(stl.starts_with("solid droplet\n") && stl.ends_with("endsolid droplet\n"));
+    }
+
+    #[test]
+    fn faces_point_outwards() {
+        let droplet = Droplet::new(&[[0, 0, 0]]);
+
+        for face in droplet.faces(Surface::Total) {
+            let [a, b, c, _] = face.corners();
+            let (u, v) = ([0, 1, 2].map(|i| b[i] - a[i]), [0, 1, 2].map(|i| c[i] - a[i]));
+            let cross = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+
+            assert_eq!(cross, face.normal);
+        }
+    }
 }
@@ -1,19 +1,17 @@
-use std::fs::File;
+use std::fs;
 use std::io;
-use std::io::{BufRead, BufReader};
 
 pub fn calculate_max_calories(file_name: &str) -> Result<(i32, i32), io::Error> {
-    let file = File::open(file_name)?;
-    let reader = BufReader::new(file);
+    Ok(calculate_max_calories_from_str(&fs::read_to_string(file_name)?))
+}
 
+pub fn calculate_max_calories_from_str(input: &str) -> (i32, i32) {
     let mut max_calories = 0;
     let mut max_elf_num = 1;
     let mut elf_counter = 1;
     let mut single_elf_calories = 0;
 
-    for result in reader.lines() {
-        let line = result.unwrap();
-
+    for line in input.lines() {
         if line == "" {
             if single_elf_calories > max_calories {
                 (max_elf_num, max_calories) = (elf_counter, single_elf_calories);
@@ -28,19 +26,18 @@ pub fn calculate_max_calories(file_name: &str) -> Result<(i32, i32), io::Error>
         single_elf_calories += calories;
     }
 
-    Ok((max_calories, max_elf_num))
+    (max_calories, max_elf_num)
 }
 
 pub fn calculate_top_n_max_calories(file_name: &str, n: usize) -> Result<Vec<i32>, io::Error> {
-    let file = File::open(file_name)?;
-    let reader = BufReader::new(file);
+    Ok(calculate_top_n_max_calories_from_str(&fs::read_to_string(file_name)?, n))
+}
 
+pub fn calculate_top_n_max_calories_from_str(input: &str, n: usize) -> Vec<i32> {
     let mut max_calories = Vec::new();
     let mut single_elf_calories = 0;
 
-    for result in reader.lines() {
-        let line = result.unwrap();
-
+    for line in input.lines() {
         if line == "" {
             if max_calories.len() == 0 {
                 max_calories.push(single_elf_calories);
@@ -63,5 +60,5 @@ pub fn calculate_top_n_max_calories(file_name: &str, n: usize) -> Result<Vec<i32
         single_elf_calories += calories;
     }
 
-    Ok(max_calories)
+    max_calories
 }
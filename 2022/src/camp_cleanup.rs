@@ -1,22 +1,29 @@
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
 
 use itertools::Itertools;
 
 pub fn read_input(file_name: &str) -> SectionsIterator {
     let file = File::open(file_name).unwrap();
-    let reader = BufReader::new(file);
+
+    SectionsIterator {
+        buf_reader: BufReader::new(file),
+    }
+}
+
+pub fn read_input_from_str(input: &str) -> SectionsIterator<&[u8]> {
+    let reader = input.as_bytes();
 
     SectionsIterator {
         buf_reader: reader,
     }
 }
 
-pub struct SectionsIterator {
-    buf_reader: BufReader<File>,
+pub struct SectionsIterator<R = BufReader<File>> {
+    buf_reader: R,
 }
 
-impl Iterator for SectionsIterator {
+impl<R: BufRead> Iterator for SectionsIterator<R> {
     type Item = ((u32, u32), (u32, u32));
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -41,8 +48,12 @@ impl Iterator for SectionsIterator {
 }
 
 pub fn camp_cleanup_part_1(file_name: &str) -> i32 {
+    camp_cleanup_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn camp_cleanup_part_1_from_str(input: &str) -> i32 {
     let mut counter = 0;
-    for (first, second) in read_input(file_name) {
+    for (first, second) in read_input_from_str(input) {
         if (first.0 <= second.0 && first.1 >= second.1) || (first.0 >= second.0 && first.1 <= second.1) {
             counter += 1;
         }
@@ -52,8 +63,12 @@ pub fn camp_cleanup_part_1(file_name: &str) -> i32 {
 }
 
 pub fn camp_cleanup_part_2(file_name: &str) -> i32 {
+    camp_cleanup_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn camp_cleanup_part_2_from_str(input: &str) -> i32 {
     let mut counter = 0;
-    for (first, second) in read_input(file_name) {
+    for (first, second) in read_input_from_str(input) {
         // completely overlapping
         if (first.0 <= second.0 && first.1 >= second.1) || (first.0 >= second.0 && first.1 <= second.1) {
             counter += 1;
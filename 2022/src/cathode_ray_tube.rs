@@ -1,4 +1,5 @@
-use std::fs::File;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
 
 #[derive(Clone, Copy)]
@@ -16,11 +17,11 @@ impl Instruction {
     }
 }
 
-pub struct ProgramIterator {
-    buf_reader: BufReader<File>,
+pub struct ProgramIterator<R = BufReader<File>> {
+    buf_reader: R,
 }
 
-impl Iterator for ProgramIterator {
+impl<R: BufRead> Iterator for ProgramIterator<R> {
     type Item = Instruction;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -43,75 +44,214 @@ impl Iterator for ProgramIterator {
 
 pub fn read_input(file_name: &str) -> ProgramIterator {
     let file = File::open(file_name).unwrap();
-    let reader = BufReader::new(file);
+
+    ProgramIterator {
+        buf_reader: BufReader::new(file),
+    }
+}
+
+pub fn read_input_from_str(input: &str) -> ProgramIterator<&[u8]> {
+    let reader = input.as_bytes();
 
     ProgramIterator {
         buf_reader: reader,
     }
 }
 
-pub fn cathode_ray_tube_part_1(file_name: &str) -> i32 {
-    let mut result = Vec::new();
-    let mut registry = 1_i32;
-    let mut clock = 1_usize;
-    let mut curr_instruction: Option<Instruction> = None;
-    let mut curr_instruction_progress = 0_usize;
-    let mut update_value = 0_i32;
-
-    let mut crt_pos = 0_usize;
-    let mut sprite_pos = 1_i32;
-
-    let mut input_iterator = read_input(file_name);
-
-    loop {
-        if update_value != 0 {
-            registry += update_value;
-            sprite_pos += update_value;
-        }
-        update_value = 0;
+const CRT_WIDTH: usize = 40;
+const CRT_HEIGHT: usize = 6;
+const LETTER_WIDTH: usize = 5;
+
+/// State of the CPU during a cycle, i.e. before the instruction finishing in it takes effect.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CycleEvent {
+    /// 1-based
+    pub cycle: usize,
+    pub x: i32,
+}
+
+impl CycleEvent {
+    pub fn signal_strength(&self) -> i32 {
+        self.cycle as i32 * self.x
+    }
+}
+
+/// Cycle accurate CPU, yielding one event per cycle until the program ends.
+pub struct Cpu<I: Iterator<Item = Instruction>> {
+    program: I,
+    x: i32,
+    cycle: usize,
+    current: Option<(Instruction, usize)>,
+}
+
+impl<I: Iterator<Item = Instruction>> Cpu<I> {
+    pub fn new(program: I) -> Self {
+        Cpu { program, x: 1, cycle: 0, current: None }
+    }
+}
+
+impl<I: Iterator<Item = Instruction>> Iterator for Cpu<I> {
+    type Item = CycleEvent;
 
-        match curr_instruction {
+    fn next(&mut self) -> Option<Self::Item> {
+        let (instruction, cycles_left) = match self.current.take() {
+            Some(current) => current,
             None => {
-                if let Some(ins) = input_iterator.next() {
-                    curr_instruction = Some(ins);
-                    curr_instruction_progress = ins.get_duration();
-                    continue;
-                } else {
-                    break;
-                }
+                let instruction = self.program.next()?;
+                (instruction, instruction.get_duration())
             }
-            Some(ins) => {
-                curr_instruction_progress -= 1;
+        };
 
-                if curr_instruction_progress == 0 {
-                    match ins {
-                        Instruction::Noop => update_value = 0,
-                        Instruction::Addx(value) => update_value = value,
-                    }
+        self.cycle += 1;
+        let event = CycleEvent { cycle: self.cycle, x: self.x };
 
-                    curr_instruction = None
-                }
+        if cycles_left == 1 {
+            if let Instruction::Addx(value) = instruction {
+                self.x += value;
             }
+        } else {
+            self.current = Some((instruction, cycles_left - 1));
         }
 
-        if (clock + 20) % 40 == 0 {
-            result.push(clock as i32 * registry);
-        }
+        Some(event)
+    }
+}
 
-        if (sprite_pos - 1..=sprite_pos + 1).contains(&(crt_pos as i32)) {
-            print!("#");
-        } else {
-            print!(".");
+/// CRT framebuffer, drawing one pixel per cycle where the 3 pixels wide sprite is.
+pub struct Crt {
+    pixels: [[bool; CRT_WIDTH]; CRT_HEIGHT],
+}
+
+impl Crt {
+    pub fn from_events(events: impl Iterator<Item = CycleEvent>) -> Crt {
+        let mut pixels = [[false; CRT_WIDTH]; CRT_HEIGHT];
+
+        for event in events.take(CRT_WIDTH * CRT_HEIGHT) {
+            let position = event.cycle - 1;
+            let col = position % CRT_WIDTH;
+
+            pixels[position / CRT_WIDTH][col] = (event.x - 1..=event.x + 1).contains(&(col as i32));
         }
 
-        if clock % 40 == 0 {
-            println!()
+        Crt { pixels }
+    }
+
+    /// Reads the capital letters drawn on the screen, `?` stands for an unknown one.
+    pub fn read_letters(&self) -> String {
+        (0..CRT_WIDTH / LETTER_WIDTH)
+            .map(|letter| {
+                let glyph = self.pixels.iter()
+                    .map(|row| {
+                        row[letter * LETTER_WIDTH..letter * LETTER_WIDTH + 4].iter()
+                            .map(|&lit| if lit { '#' } else { '.' })
+                            .collect::<String>()
+                    })
+                    .collect::<Vec<_>>();
+
+                FONT.iter()
+                    .find(|(_, font_glyph)| font_glyph.iter().eq(glyph.iter()))
+                    .map_or('?', |(letter, _)| *letter)
+            })
+            .collect()
+    }
+}
+
+impl Display for Crt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.pixels.iter() {
+            let row = row.iter().map(|&lit| if lit { '#' } else { '.' }).collect::<String>();
+            writeln!(f, "{row}")?;
         }
 
-        clock += 1;
-        crt_pos = (crt_pos + 1) % 40;
+        Ok(())
+    }
+}
+
+/// 4x6 font of the puzzles, letters are 1 pixel apart.
+const FONT: [(char, [&str; CRT_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+pub fn cathode_ray_tube_part_1(file_name: &str) -> i32 {
+    cathode_ray_tube_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn cathode_ray_tube_part_1_from_str(input: &str) -> i32 {
+    Cpu::new(read_input_from_str(input))
+        .filter(|event| (event.cycle + 20) % 40 == 0)
+        .take(6)
+        .map(|event| event.signal_strength())
+        .sum()
+}
+
+pub fn cathode_ray_tube_part_2(file_name: &str) -> String {
+    cathode_ray_tube_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn cathode_ray_tube_part_2_from_str(input: &str) -> String {
+    let crt = Crt::from_events(Cpu::new(read_input_from_str(input)));
+    print!("{crt}");
+
+    crt.read_letters()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpu_small_program() {
+        let program = [Instruction::Noop, Instruction::Addx(3), Instruction::Addx(-5)];
+        let xs = Cpu::new(program.into_iter()).map(|event| event.x).collect::<Vec<_>>();
+
+        assert_eq!(xs, vec![1, 1, 1, 4, 4]);
+    }
+
+    #[test]
+    fn part_1_input_example() {
+        let answer = cathode_ray_tube_part_1("inputs/10_input_example.txt");
+
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 13140);
     }
-    println!("{:?}", result);
 
-    result.iter().take(6).sum()
+    #[test]
+    fn part_1_input() {
+        let answer = cathode_ray_tube_part_1("inputs/10_input.txt");
+
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 11820);
+    }
+
+    #[test]
+    fn part_2_input_example() {
+        let crt = Crt::from_events(Cpu::new(read_input_from_str(&fs::read_to_string("inputs/10_input_example.txt").unwrap())));
+
+        assert!(crt.to_string().starts_with("##..##..##..##..##..##..##..##..##..##..\n###...###...###...###...###...###...###.\n"));
+        assert_eq!(crt.read_letters(), "????????");
+    }
+
+    #[test]
+    fn part_2_input() {
+        let answer = cathode_ray_tube_part_2("inputs/10_input.txt");
+
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, "EPJBRKAH");
+    }
 }
@@ -1,218 +1,216 @@
-#![allow(dead_code)]
-
 use std::borrow::Cow;
 use std::cmp::Ordering;
-use std::fmt::{Debug, Formatter};
-use std::fs::File;
+use std::fmt::{Display, Formatter};
+use std::fs;
 use std::io;
-use std::io::{BufRead, BufReader, Write};
+use std::io::Write;
+use std::str::FromStr;
 
 use itertools::{EitherOrBoth, Itertools};
 use ptree::{print_tree, Style, TreeItem};
-
-#[derive(Clone)]
-struct Node {
-    value: Option<u32>,
-    children: Vec<Node>,
+use serde_json::Value;
+
+/// Packet of the distress signal, compared in the order of the puzzle,
+/// so an integer equals the list containing only that integer.
+#[derive(Clone, Debug)]
+pub enum Packet {
+    Int(u64),
+    List(Vec<Packet>),
 }
 
-impl Debug for Node {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let text = match self.value {
-            None => "E".to_string(),
-            Some(val) => val.to_string()
-        };
+impl Packet {
+    fn divider(value: u64) -> Packet {
+        Packet::List(vec![Packet::List(vec![Packet::Int(value)])])
+    }
+}
 
-        if self.children.is_empty() {
-            write!(f, "{} ", text)
-        } else {
-            write!(f, "{} {:?}", text, self.children)
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(left), Packet::Int(right)) => left.cmp(right),
+            (Packet::List(left), Packet::List(right)) => {
+                left.iter()
+                    .zip_longest(right.iter())
+                    .map(|pair| match pair {
+                        EitherOrBoth::Both(left, right) => left.cmp(right),
+                        EitherOrBoth::Left(_) => Ordering::Greater, // right run out of items
+                        EitherOrBoth::Right(_) => Ordering::Less, // left run out of items
+                    })
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            }
+            (Packet::Int(left), Packet::List(right)) => [Packet::Int(*left)][..].cmp(&right[..]),
+            (Packet::List(left), Packet::Int(right)) => left[..].cmp(&[Packet::Int(*right)][..]),
         }
     }
 }
 
-impl PartialEq for Node {
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Packet {
     fn eq(&self, other: &Self) -> bool {
-        self.value == other.value && self.children == other.children
+        self.cmp(other).is_eq()
     }
 }
 
-impl TreeItem for Node {
+impl Eq for Packet {}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Packet::Int(value) => write!(f, "{value}"),
+            Packet::List(items) => write!(f, "[{}]", items.iter().join(",")),
+        }
+    }
+}
+
+impl TreeItem for Packet {
     type Child = Self;
 
     fn write_self<W: Write>(&self, f: &mut W, style: &Style) -> io::Result<()> {
-        let text = match self.value {
-            None => "E".to_string(),
-            Some(val) => val.to_string(),
+        let text = match self {
+            Packet::Int(value) => value.to_string(),
+            Packet::List(_) => "E".to_string(),
         };
         write!(f, "{}", style.paint(text))
     }
+
     fn children(&'_ self) -> Cow<'_, [Self::Child]> {
-        Cow::from(self.children.clone())
+        match self {
+            Packet::Int(_) => Cow::Borrowed(&[]),
+            Packet::List(items) => Cow::Borrowed(items),
+        }
     }
 }
 
-fn read_input(file_name: &str) -> Vec<(Node, Node)> {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
-
-    let mut pairs = Vec::new();
-
-    loop {
-        let mut left = String::new();
-        let mut right = String::new();
-
-        let _ = reader.read_line(&mut left).unwrap();
-        let _ = reader.read_line(&mut right).unwrap();
+#[derive(Debug, PartialEq)]
+pub struct ParsePacketError {
+    /// byte offset in the parsed text
+    pub position: usize,
+    pub message: String,
+}
 
-        let left: Node = parse_tree(left.trim());
-        let right: Node = parse_tree(right.trim());
+impl FromStr for Packet {
+    type Err = ParsePacketError;
 
-        pairs.push((left, right));
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { text: s.as_bytes(), position: 0 };
+        let packet = parser.packet()?;
 
-        if let Ok(n) = reader.read_line(&mut String::new()) {
-            if n == 0 {
-                break;
-            };
+        match parser.peek() {
+            None => Ok(packet),
+            Some(_) => Err(parser.error("expected end of packet")),
         }
     }
+}
 
-    pairs
+/// Recursive descent parser of `packet = int | "[" (packet ("," packet)*)? "]"`.
+struct Parser<'a> {
+    text: &'a [u8],
+    position: usize,
 }
 
-fn find_closing_bracket(line: &str) -> usize {
-    let mut level = 0;
-    let mut closing_bracket_index = 0;
-    for (index, char) in line.chars().skip(1).enumerate() {
-        if char == ']' {
-            if level == 0 {
-                closing_bracket_index = index;
-                break;
-            } else {
-                level -= 1;
-            }
-        }
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.position).copied()
+    }
 
-        if char == '[' {
-            level += 1
+    fn error(&self, message: &str) -> ParsePacketError {
+        ParsePacketError { position: self.position, message: message.to_string() }
+    }
+
+    fn packet(&mut self) -> Result<Packet, ParsePacketError> {
+        match self.peek() {
+            Some(b'[') => self.list(),
+            Some(ch) if ch.is_ascii_digit() => self.int(),
+            _ => Err(self.error("expected '[' or digit")),
         }
     }
-    assert_ne!(closing_bracket_index, 0);
 
-    closing_bracket_index + 1
-}
+    fn list(&mut self) -> Result<Packet, ParsePacketError> {
+        self.position += 1;
+        let mut items = Vec::new();
 
-fn my_split(line: &str) -> Vec<&str> {
-    let mut level = 0;
-    let mut parts = Vec::new();
-    let mut begin = 0;
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(Packet::List(items));
+        }
 
-    for (i, char) in line.chars().enumerate() {
-        if char == ',' && level == 0 {
-            parts.push(&line[begin..i]);
-            begin = i + 1;
-        };
+        loop {
+            items.push(self.packet()?);
 
-        if char == '[' {
-            level += 1;
-            continue;
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(Packet::List(items));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
         }
+    }
 
-        if char == ']' {
-            level -= 1;
-            continue;
+    fn int(&mut self) -> Result<Packet, ParsePacketError> {
+        let start = self.position;
+        while self.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+            self.position += 1;
         }
-    }
-    parts.push(&line[begin..]);
 
-    parts
+        std::str::from_utf8(&self.text[start..self.position]).unwrap()
+            .parse::<u64>()
+            .map(Packet::Int)
+            .map_err(|_| ParsePacketError { position: start, message: "integer out of range".to_string() })
+    }
 }
 
-fn parse_tree(line: &str) -> Node {
-    let mut parent = Node {
-        value: None,
-        children: vec![],
-    };
-    let slice = &line[1..line.len() - 1];
-
-    for part in my_split(slice).into_iter() {
-        if part.starts_with('[') {
-            let node = parse_tree(part);
-
-            parent.children.push(node);
-        } else if !part.is_empty() {
-            let num = part.parse::<u32>().unwrap();
-            let node = Node { value: Option::from(num), children: vec![] };
-
-            parent.children.push(node);
+impl From<&Packet> for Value {
+    fn from(packet: &Packet) -> Self {
+        match packet {
+            Packet::Int(value) => Value::from(*value),
+            Packet::List(items) => Value::Array(items.iter().map(Value::from).collect()),
         }
     }
-
-    parent
 }
 
-fn compare(curr_l: &Node, curr_r: &Node) -> Ordering {
-    match (curr_l.value, curr_r.value) {
-        (Some(val_l), Some(val_r)) => {
-            // if both have value, then both have no children
-            val_l.cmp(&val_r)
-        }
-        (None, None) => {
-            for pair in curr_l.children.iter().zip_longest(curr_r.children.iter()) {
-                let result = match pair {
-                    EitherOrBoth::Both(child_l, child_r) => compare(child_l, child_r),
-                    EitherOrBoth::Left(_) => Ordering::Greater, // right run out of items
-                    EitherOrBoth::Right(_) => Ordering::Less, // left run out of items
-                };
-
-                if result == Ordering::Equal {
-                    continue;
-                } else {
-                    return result;
-                }
-            }
-            // empty leaves
-            Ordering::Equal
-        }
-        (None, Some(val_r)) => {
-            let node_copy = Node {
-                value: Some(val_r),
-                children: vec![],
-            };
-
-            let extended_child = Node {
-                value: None,
-                children: vec![node_copy],
-            };
-
-            compare(curr_l, &extended_child)
-        }
-        (Some(val_l), None) => {
-            let node_copy = Node {
-                value: Some(val_l),
-                children: vec![],
-            };
-
-            let extended_child = Node {
-                value: None,
-                children: vec![node_copy],
-            };
-            compare(&extended_child, curr_r)
+impl TryFrom<&Value> for Packet {
+    type Error = String;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Number(number) => number.as_u64()
+                .map(Packet::Int)
+                .ok_or(format!("{number} is not a non-negative integer")),
+            Value::Array(items) => items.iter().map(Packet::try_from).collect::<Result<_, _>>().map(Packet::List),
+            _ => Err(format!("{value} is neither a number nor an array")),
         }
     }
 }
 
+fn parse_input(input: &str) -> Vec<(Packet, Packet)> {
+    input.lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.trim().parse::<Packet>().unwrap_or_else(|error| panic!("invalid packet {line}: {error:?}")))
+        .tuples()
+        .collect()
+}
+
 pub fn distress_signal_part_1(file_name: &str) -> usize {
-    let input = read_input(file_name);
-    let mut indices_sum = 0;
+    distress_signal_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-    for (index, (node_l, node_r)) in input.into_iter().enumerate() {
-        let _ = print_tree(&node_l);
-        let _ = print_tree(&node_r);
+pub fn distress_signal_part_1_from_str(input: &str) -> usize {
+    let input = parse_input(input);
+    let mut indices_sum = 0;
 
-        let is_in_order = compare(&node_l, &node_r);
+    for (index, (packet_l, packet_r)) in input.into_iter().enumerate() {
+        let _ = print_tree(&packet_l);
+        let _ = print_tree(&packet_r);
 
-        if is_in_order == Ordering::Equal || is_in_order == Ordering::Less {
+        if packet_l <= packet_r {
             indices_sum += index + 1
         }
     }
@@ -221,57 +219,95 @@ pub fn distress_signal_part_1(file_name: &str) -> usize {
 }
 
 pub fn distress_signal_part_2(file_name: &str) -> usize {
-    let mut input = read_input(file_name);
-    let mut indices_product = 1;
-
-    let (node_1, node_2) = create_extra_nodes();
-    input.push((node_1.clone(), node_2.clone()));
+    distress_signal_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-    let sorted = input.into_iter()
-        .flat_map(|(node_l, node_r)| [node_l, node_r])
-        .sorted_by(compare)
+pub fn distress_signal_part_2_from_str(input: &str) -> usize {
+    let packets = parse_input(input).into_iter()
+        .flat_map(|(packet_l, packet_r)| [packet_l, packet_r])
         .collect::<Vec<_>>();
 
-    for (index, node) in sorted.iter().enumerate() {
-        if *node == node_1 {
-            indices_product *= index + 1;
-        }
-        if *node == node_2 {
-            indices_product *= index + 1;
-        }
-        println!("{:?}", node);
-    }
+    // position of a divider in the sorted packets is the count of packets before it
+    let (divider_1, divider_2) = (Packet::divider(2), Packet::divider(6));
+    let index_1 = packets.iter().filter(|&packet| *packet < divider_1).count() + 1;
+    let index_2 = packets.iter().filter(|&packet| *packet < divider_2).count() + 2;
 
-    indices_product
+    index_1 * index_2
 }
 
-fn create_extra_nodes() -> (Node, Node) {
-    (Node {
-        value: None,
-        children: vec![
-            Node {
-                value: None,
-                children: vec![
-                    Node {
-                        value: Some(2),
-                        children: vec![],
-                    }
-                ],
-            }
-        ],
-    },
-     Node {
-         value: None,
-         children: vec![
-             Node {
-                 value: None,
-                 children: vec![
-                     Node {
-                         value: Some(6),
-                         children: vec![],
-                     }
-                 ],
-             }
-         ],
-     })
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_input_example() {
+        let answer = distress_signal_part_1("inputs/13_input_example.txt");
+
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 13);
+    }
+
+    #[test]
+    fn part_1_input() {
+        let answer = distress_signal_part_1("inputs/13_input.txt");
+
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 5390);
+    }
+
+    #[test]
+    fn part_2_input_example() {
+        let answer = distress_signal_part_2("inputs/13_input_example.txt");
+
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 140);
+    }
+
+    #[test]
+    fn part_2_input() {
+        let answer = distress_signal_part_2("inputs/13_input.txt");
+
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 19261);
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let text = "[1,[2,[3,[4,[5,6,7]]]],8,9,[],12345678901234]";
+
+        assert_eq!(text.parse::<Packet>().unwrap().to_string(), text);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |text: &str| text.parse::<Packet>().unwrap_err();
+
+        assert_eq!(error("[1,2"), ParsePacketError { position: 4, message: "expected ',' or ']'".to_string() });
+        assert_eq!(error("[1,,2]").position, 3);
+        assert_eq!(error("[1]]").position, 3);
+        assert_eq!(error("[99999999999999999999]").message, "integer out of range");
+        assert_eq!(error("").position, 0);
+    }
+
+    #[test]
+    fn ordering() {
+        let packet = |text: &str| text.parse::<Packet>().unwrap();
+
+        assert!(packet("[1,1,3,1,1]") < packet("[1,1,5,1,1]"));
+        assert!(packet("[[1],[2,3,4]]") < packet("[[1],4]"));
+        assert!(packet("[9]") > packet("[[8,7,6]]"));
+        assert!(packet("[[[]]]") > packet("[[]]"));
+        assert_eq!(packet("[[2]]"), packet("2"));
+    }
+
+    #[test]
+    fn serde_json_round_trip() {
+        let packet = "[[1],[2,[]],3]".parse::<Packet>().unwrap();
+        let value = Value::from(&packet);
+
+        assert_eq!(value, serde_json::json!([[1], [2, []], 3]));
+        assert_eq!(Packet::try_from(&value).unwrap().to_string(), packet.to_string());
+        assert!(Packet::try_from(&serde_json::json!([1, -2])).is_err());
+        assert!(Packet::try_from(&serde_json::json!(["a"])).is_err());
+    }
 }
@@ -0,0 +1,240 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::iter::Sum;
+use std::ops::Add;
+use std::str::FromStr;
+
+/// Number in balanced base 5, digits `=`, `-`, `0`, `1` and `2` stand for -2 to 2.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Snafu {
+    /// least significant digit first, without leading zeros, so zero has no digits
+    digits: Vec<i8>,
+}
+
+impl Snafu {
+    fn trimmed(mut digits: Vec<i8>) -> Self {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        Snafu { digits }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseSnafuError {
+    /// byte offset in the parsed text
+    pub position: usize,
+    pub message: String,
+}
+
+impl FromStr for Snafu {
+    type Err = ParseSnafuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseSnafuError { position: 0, message: "empty number".to_string() });
+        }
+
+        let digits = s.char_indices()
+            .rev()
+            .map(|(position, ch)| match ch {
+                '=' => Ok(-2),
+                '-' => Ok(-1),
+                '0' => Ok(0),
+                '1' => Ok(1),
+                '2' => Ok(2),
+                _ => Err(ParseSnafuError { position, message: format!("invalid digit {ch}") }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Snafu::trimmed(digits))
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.digits.is_empty() {
+            return write!(f, "0");
+        }
+
+        let digits = self.digits.iter()
+            .rev()
+            .map(|digit| match digit {
+                -2 => '=',
+                -1 => '-',
+                0 => '0',
+                1 => '1',
+                _ => '2',
+            })
+            .collect::<String>();
+
+        write!(f, "{digits}")
+    }
+}
+
+impl From<i128> for Snafu {
+    fn from(mut value: i128) -> Self {
+        let mut digits = Vec::new();
+
+        while value != 0 {
+            let digit = match value.rem_euclid(5) {
+                digit @ 0..=2 => digit,
+                digit => digit - 5,
+            };
+            digits.push(digit as i8);
+            // value - digit might not fit when the digit is negative
+            value = value.div_euclid(5) + (digit < 0) as i128;
+        }
+
+        Snafu { digits }
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(value: i64) -> Self {
+        Snafu::from(value as i128)
+    }
+}
+
+/// The number does not fit the integer type.
+#[derive(Debug, PartialEq)]
+pub struct SnafuOverflowError;
+
+impl TryFrom<&Snafu> for i128 {
+    type Error = SnafuOverflowError;
+
+    fn try_from(value: &Snafu) -> Result<Self, Self::Error> {
+        value.digits.iter()
+            .rev()
+            // number * 5 alone might not fit when the digit brings it back, e.g. for i128::MIN
+            .try_fold(0_i128, |number, &digit| number.checked_mul(4)?.checked_add(digit as i128)?.checked_add(number))
+            .ok_or(SnafuOverflowError)
+    }
+}
+
+impl TryFrom<&Snafu> for i64 {
+    type Error = SnafuOverflowError;
+
+    fn try_from(value: &Snafu) -> Result<Self, Self::Error> {
+        i128::try_from(value)?.try_into().map_err(|_| SnafuOverflowError)
+    }
+}
+
+/// Adds digit by digit, carrying one up or down whenever a digit sum leaves -2 to 2.
+impl Add for &Snafu {
+    type Output = Snafu;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let length = self.digits.len().max(rhs.digits.len());
+        let mut digits = Vec::with_capacity(length + 1);
+        let mut carry = 0;
+
+        for i in 0..length {
+            let sum = self.digits.get(i).unwrap_or(&0) + rhs.digits.get(i).unwrap_or(&0) + carry;
+            let (next_carry, digit) = match sum {
+                3.. => (1, sum - 5),
+                ..=-3 => (-1, sum + 5),
+                _ => (0, sum),
+            };
+            digits.push(digit);
+            carry = next_carry;
+        }
+        digits.push(carry);
+
+        Snafu::trimmed(digits)
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Snafu>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), |sum, number| &sum + number)
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Snafu>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), |sum, number| sum + number)
+    }
+}
+
+fn parse_input(input: &str) -> Vec<Snafu> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.trim().parse::<Snafu>().unwrap_or_else(|error| panic!("{line}: {error:?}")))
+        .collect()
+}
+
+pub fn full_of_hot_air_part_1(file_name: &str) -> String {
+    full_of_hot_air_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+/// Fuel requirements summed up without leaving SNAFU.
+pub fn full_of_hot_air_part_1_from_str(input: &str) -> String {
+    parse_input(input).iter().sum::<Snafu>().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn part_1_input_example() {
+        let answer = full_of_hot_air_part_1("inputs/25_input_example.txt");
+
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, "2=-1=0");
+    }
+
+    #[test]
+    fn conversions() {
+        let pairs = [
+            (0, "0"), (1, "1"), (2, "2"), (3, "1="), (4, "1-"), (5, "10"), (6, "11"), (7, "12"), (8, "2="), (9, "2-"),
+            (10, "20"), (15, "1=0"), (20, "1-0"), (2022, "1=11-2"), (12345, "1-0---0"), (314159265, "1121-1110-1=0"),
+            (-1, "-"), (-3, "-2"), (-4890, "=21-20"),
+        ];
+
+        for (number, snafu) in pairs {
+            assert_eq!(Snafu::from(number).to_string(), snafu);
+            assert_eq!(i64::try_from(&snafu.parse::<Snafu>().unwrap()), Ok(number));
+        }
+
+        assert_eq!("0012".parse::<Snafu>().unwrap().to_string(), "12");
+        assert_eq!(i128::try_from(&Snafu::from(i128::MIN)), Ok(i128::MIN));
+        assert_eq!(i128::try_from(&Snafu::from(i128::MAX)), Ok(i128::MAX));
+        assert_eq!(i64::try_from(&Snafu::from(i64::MAX as i128 + 1)), Err(SnafuOverflowError));
+        assert_eq!(i128::try_from(&format!("1{}", "0".repeat(60)).parse::<Snafu>().unwrap()), Err(SnafuOverflowError));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("".parse::<Snafu>().unwrap_err().position, 0);
+        assert_eq!("1=3-".parse::<Snafu>(), Err(ParseSnafuError { position: 2, message: "invalid digit 3".to_string() }));
+    }
+
+    #[test]
+    fn addition_matches_integers() {
+        let mut rng = StdRng::seed_from_u64(25);
+
+        for _ in 0..1000 {
+            let (a, b) = (rng.gen_range(-1_000_000_000_i64..1_000_000_000), rng.gen_range(-1_000_000_000_i64..1_000_000_000));
+            assert_eq!(Snafu::from(a) + Snafu::from(b), Snafu::from(a + b));
+        }
+
+        let numbers = (1..=100_i64).map(Snafu::from).collect::<Vec<_>>();
+        assert_eq!(i64::try_from(&numbers.iter().sum::<Snafu>()), Ok(5050));
+        assert_eq!(numbers.into_iter().sum::<Snafu>(), Snafu::from(5050_i64));
+    }
+}
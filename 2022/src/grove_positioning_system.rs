@@ -1,110 +1,233 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 
-fn read_input(file_name: &str) -> Vec<i64> {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
-    let mut buf = String::new();
+fn parse_input(input: &str) -> Vec<i64> {
+    input.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.trim().parse::<i64>().unwrap())
+        .collect()
+}
+
+/// Circular list of the numbers, mixed by moving each number by its value in the order of the file.
+///
+/// Numbers are referred to by their index in the file, which keeps duplicates apart. The list is kept
+/// in blocks of about `√n` indices, so finding, removing and inserting a number takes `O(√n)`.
+pub struct Mixer {
+    numbers: Vec<i64>,
+    blocks: Vec<Vec<usize>>,
+    /// block holding each index
+    block_of: Vec<usize>,
+}
 
-    let mut numbers: Vec<i64> = Vec::new();
+impl Mixer {
+    /// Numbers multiplied by the decryption key, in their initial arrangement.
+    pub fn new(numbers: &[i64], decryption_key: i64) -> Self {
+        let mut mixer = Mixer {
+            numbers: numbers.iter().map(|&number| number * decryption_key).collect(),
+            blocks: vec![(0..numbers.len()).collect()],
+            block_of: vec![0; numbers.len()],
+        };
+        mixer.rebalance();
+
+        mixer
+    }
+
+    /// Splits the list into blocks of equal size again, they drift apart with the moves.
+    fn rebalance(&mut self) {
+        let order = self.blocks.concat();
+        let block_size = (order.len() as f64).sqrt().ceil().max(1.0) as usize;
 
-    while let Ok(n) = reader.read_line(&mut buf) {
-        if n == 0 {
-            break;
+        self.blocks = order.chunks(block_size).map(|chunk| chunk.to_vec()).collect();
+        for (block, indices) in self.blocks.iter().enumerate() {
+            for &index in indices {
+                self.block_of[index] = block;
+            }
         }
+    }
 
-        let i = buf.trim().parse::<i64>().unwrap();
+    fn remove(&mut self, index: usize) -> usize {
+        let block = self.block_of[index];
+        let offset = self.blocks[block].iter().position(|&other| other == index).unwrap();
+        self.blocks[block].remove(offset);
 
-        numbers.push(i);
-        buf = String::new();
-    };
+        self.blocks[..block].iter().map(Vec::len).sum::<usize>() + offset
+    }
 
-    numbers
-}
+    fn insert(&mut self, index: usize, mut position: usize) {
+        let last = self.blocks.len() - 1;
+        for (block, indices) in self.blocks.iter_mut().enumerate() {
+            if position <= indices.len() || block == last {
+                indices.insert(position, index);
+                self.block_of[index] = block;
+                return;
+            }
+            position -= indices.len();
+        }
+    }
 
-fn mix_numbers(numbers: &Vec<i64>, mix_times: usize) -> Vec<i64> {
-    // map numbers to avoid issues with duplicates
-    let mut mapped_numbers = (0..numbers.len()).collect::<Vec<usize>>();
-    let original_sequence = mapped_numbers.clone();
+    /// Moves the number by given amount of places, the list without the number has `n - 1` gaps to land in.
+    fn shift(&mut self, index: usize, amount: i64) {
+        let gaps = self.numbers.len() as i64 - 1;
+        if gaps == 0 {
+            return;
+        }
 
-    println!("Initial arrangement:");
-    println!("{mapped_numbers:?}  ({:?})", mapped_numbers.iter().map(|&num| numbers[num]).collect::<Vec<_>>());
+        let position = self.remove(index) as i64;
+        self.insert(index, (position + amount.rem_euclid(gaps)).rem_euclid(gaps) as usize);
+    }
 
-    for mix_i in 1..=mix_times {
-        for number in original_sequence.iter() {
-            let move_amount = numbers[*number];
+    /// One round of mixing.
+    pub fn mix(&mut self) {
+        for index in 0..self.numbers.len() {
+            self.shift(index, self.numbers[index]);
+        }
+        self.rebalance();
+    }
 
-            if move_amount == 0 {
-                continue;
-            }
+    /// Reverts one round of mixing, moving the numbers back in the reverse order.
+    pub fn unmix(&mut self) {
+        for index in (0..self.numbers.len()).rev() {
+            self.shift(index, -self.numbers[index]);
+        }
+        self.rebalance();
+    }
 
-            let initial_pos = mapped_numbers.iter().position(|num| *num == *number).unwrap();
-            let mut new_pos = (initial_pos as i64 + move_amount).rem_euclid(numbers.len() as i64 - 1) as usize;
+    /// Numbers around the circle, starting at the first number of the file.
+    pub fn arrangement(&self) -> Vec<i64> {
+        let order = self.blocks.concat();
+        let first = order.iter().position(|&index| index == 0).unwrap_or(0);
 
-            // when moving backwards, we jump to the end on a sequence
-            if new_pos == 0 && move_amount < 0 {
-                new_pos = numbers.len() - 1;
-            }
+        order[first..].iter().chain(&order[..first]).map(|&index| self.numbers[index]).collect()
+    }
 
-            // move elements between initial position and new position
-            if initial_pos < new_pos {
-                for i in initial_pos..new_pos {
-                    mapped_numbers[i] = mapped_numbers[i + 1];
-                }
-                mapped_numbers[new_pos] = *number;
-            } else {
-                for i in (new_pos..initial_pos).rev() {
-                    mapped_numbers[i + 1] = mapped_numbers[i];
-                }
-                mapped_numbers[new_pos] = *number;
-            };
-
-            // println!("moving number: {} to position: {new_pos}", numbers[*number]);
-            // println!("{mapped_numbers:?}  ({:?})", mapped_numbers.iter().map(|&num| numbers[num]).collect::<Vec<_>>());
-        }
+    /// Sum of the 1000th, 2000th and 3000th number after the value 0.
+    pub fn grove_coordinates(&self) -> i64 {
+        let arrangement = self.arrangement();
+        let zero = arrangement.iter().position(|&number| number == 0).expect("no number 0");
 
-        println!("After {mix_i} round of mixing:");
-        println!("{mapped_numbers:?}  ({:?})\n", mapped_numbers.iter().map(|&num| numbers[num]).collect::<Vec<_>>());
+        [1000, 2000, 3000].iter().map(|offset| arrangement[(zero + offset) % arrangement.len()]).sum()
     }
+}
 
-    mapped_numbers.iter().map(|&num| numbers[num]).collect::<Vec<_>>()
+pub fn grove_positioning_system(file_name: &str, decryption_key: i64, rounds: usize) -> i64 {
+    grove_positioning_system_from_str(&fs::read_to_string(file_name).unwrap(), decryption_key, rounds)
 }
 
-/// mapped numbers example:
-/// [1,2,1,3,4]
-/// [0,1,2,3,4]
-///
-/// 0->1
-/// 1->2
-/// 2->1
-/// 3->3
-/// 4->4
-pub fn grove_positioning_system_part_1(file_name: &str) -> i64 {
-    let numbers = read_input(file_name);
-    let mixed_numbers = mix_numbers(&numbers, 1);
+pub fn grove_positioning_system_from_str(input: &str, decryption_key: i64, rounds: usize) -> i64 {
+    let mut mixer = Mixer::new(&parse_input(input), decryption_key);
+    for _ in 0..rounds {
+        mixer.mix();
+    }
 
-    println!("mixed numbers: {:?}", mixed_numbers);
+    mixer.grove_coordinates()
+}
 
-    let zero_index = mixed_numbers.iter().position(|&num| num == 0).unwrap();
+pub fn grove_positioning_system_part_1(file_name: &str) -> i64 {
+    grove_positioning_system(file_name, 1, 1)
+}
 
-    mixed_numbers[(zero_index + 1000) % numbers.len() as i64 as usize]
-        + mixed_numbers[(zero_index + 2000) % numbers.len() as i64 as usize]
-        + mixed_numbers[(zero_index + 3000) % numbers.len() as i64 as usize]
+pub fn grove_positioning_system_part_1_from_str(input: &str) -> i64 {
+    grove_positioning_system_from_str(input, 1, 1)
 }
 
 pub fn grove_positioning_system_part_2(file_name: &str) -> i64 {
-    const MIX_TIMES: usize = 10;
-    const DECRYPTION_KEY: i64 = 811589153;
+    grove_positioning_system(file_name, 811589153, 10)
+}
 
-    let input_numbers = read_input(file_name);
-    let numbers = input_numbers.iter().map(|num| *num * DECRYPTION_KEY).collect::<Vec<_>>();
-    let mixed_numbers = mix_numbers(&numbers, MIX_TIMES);
+pub fn grove_positioning_system_part_2_from_str(input: &str) -> i64 {
+    grove_positioning_system_from_str(input, 811589153, 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::input_generator::encrypted_file;
+
+    use super::*;
+
+    #[test]
+    fn part_1_input_example() {
+        let answer = grove_positioning_system_part_1("inputs/20_input_example.txt");
+
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 3);
+    }
+
+    #[test]
+    fn part_1_input() {
+        let answer = grove_positioning_system_part_1("inputs/20_input.txt");
 
-    println!("mixed numbers: {:?}", mixed_numbers);
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 7225);
+    }
+
+    #[test]
+    fn part_2_input_example() {
+        let answer = grove_positioning_system_part_2("inputs/20_input_example.txt");
+
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 1623178306);
+    }
 
-    let zero_index = mixed_numbers.iter().position(|&num| num == 0).unwrap();
+    #[test]
+    fn part_2_input() {
+        let answer = grove_positioning_system_part_2("inputs/20_input.txt");
 
-    mixed_numbers[(zero_index + 1000) % numbers.len() as i64 as usize]
-        + mixed_numbers[(zero_index + 2000) % numbers.len() as i64 as usize]
-        + mixed_numbers[(zero_index + 3000) % numbers.len() as i64 as usize]
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 548634267428);
+    }
+
+    #[test]
+    fn part_1_generated_input() {
+        let input = encrypted_file(&mut StdRng::seed_from_u64(20), 5000);
+        let answer = grove_positioning_system_part_1_from_str(&input);
+
+        println!("part 1 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 9159);
+    }
+
+    #[test]
+    fn part_2_generated_input() {
+        let input = encrypted_file(&mut StdRng::seed_from_u64(20), 5000);
+        let answer = grove_positioning_system_part_2_from_str(&input);
+
+        println!("part 2 - generated - answer: {:?}", answer);
+        assert_eq!(answer, -590836903384);
+    }
+
+    #[test]
+    fn mix_input_example() {
+        let mut mixer = Mixer::new(&[1, 2, -3, 3, -2, 0, 4], 1);
+        mixer.mix();
+
+        assert_eq!(mixer.arrangement(), vec![1, 2, -3, 4, 0, 3, -2]);
+    }
+
+    #[test]
+    fn unmix_restores_arrangement() {
+        let numbers = parse_input(&fs::read_to_string("inputs/20_input.txt").unwrap());
+        let mut mixer = Mixer::new(&numbers, 811589153);
+        let initial = mixer.arrangement();
+
+        for _ in 0..3 {
+            mixer.mix();
+        }
+        assert_ne!(mixer.arrangement(), initial);
+
+        for _ in 0..3 {
+            mixer.unmix();
+        }
+        assert_eq!(mixer.arrangement(), initial);
+    }
+
+    #[test]
+    fn unmix_with_duplicates() {
+        let numbers = parse_input(&fs::read_to_string("inputs/20_input_example_3.txt").unwrap());
+        let mut mixer = Mixer::new(&numbers, 1);
+        mixer.mix();
+        mixer.unmix();
+
+        assert_eq!(mixer.arrangement(), numbers);
+    }
 }
@@ -1,43 +1,12 @@
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::fmt::{Debug, Formatter};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
-
-fn read_input(file_name: &str) -> Vec<Vec<u8>> {
-    let file = File::open(file_name).unwrap();
-    let reader = BufReader::new(file);
-
-    reader.lines()
-        .map(|result| result.unwrap())
-        .map(|line| line.chars()
-            .map(|char| if char == 'S' { 0 } else if char == 'E' { 27 } else { char as u8 - 96 })
-            .collect::<Vec<_>>())
-        .collect::<Vec<_>>()
-}
-
-fn find_start_and_end(map: &[Vec<u8>]) -> (Position, Position) {
-    let mut start_pos = Position { row: 0, col: 0 };
-    let mut end_pos = Position { row: 0, col: 0 };
-    for (i, row) in map.iter().enumerate() {
-        for (j, elem) in row.iter().enumerate() {
-            if *elem == 0 {
-                start_pos = Position { row: i, col: j }
-            } else if *elem == 27 {
-                end_pos = Position { row: i, col: j }
-            }
-        }
-    }
-
-    (start_pos, end_pos)
-}
+use std::fs;
 
 #[derive(Copy, Clone, Eq, Hash, PartialEq, PartialOrd, Ord)]
-struct Position {
-    row: usize,
-    col: usize,
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
 }
 
 impl Debug for Position {
@@ -46,278 +15,250 @@ impl Debug for Position {
     }
 }
 
-impl Position {
-    fn up(&self) -> Self {
-        Self {
-            row: self.row - 1,
-            col: self.col,
-        }
-    }
-
-    fn down(&self) -> Self {
-        Self {
-            row: self.row + 1,
-            col: self.col,
-        }
-    }
-
-    fn left(&self) -> Self {
-        Self {
-            row: self.row,
-            col: self.col - 1,
-        }
-    }
+/// How much the elevation may change in one step.
+#[derive(Copy, Clone, Debug)]
+pub struct ClimbRules {
+    pub max_ascent: u8,
+    pub max_descent: u8,
+}
 
-    fn right(&self) -> Self {
-        Self {
-            row: self.row,
-            col: self.col + 1,
-        }
+impl Default for ClimbRules {
+    /// Rules of the puzzle, at most one up and any amount down.
+    fn default() -> Self {
+        ClimbRules { max_ascent: 1, max_descent: u8::MAX }
     }
 }
 
-#[derive(Clone, Debug)]
-struct MapState<'a> {
-    map: &'a Vec<Vec<u8>>,
-    pos: Position,
-    path: Vec<Position>,
+impl ClimbRules {
+    fn can_step(&self, from: u8, to: u8) -> bool {
+        to <= from.saturating_add(self.max_ascent) && from <= to.saturating_add(self.max_descent)
+    }
 }
 
-#[derive(Debug, EnumIter, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+/// Elevations `a` to `z` as 0 to 25, the start `S` is at elevation `a` and the end `E` at `z`.
+pub struct HeightMap {
+    heights: Vec<Vec<u8>>,
+    pub start: Position,
+    pub end: Position,
 }
 
-impl MapState<'_> {
-    fn can_go(&self, direction: Direction) -> bool {
-        let new_pos = match direction {
-            Direction::Up => {
-                if self.pos.row == 0 {
-                    return false;
-                }
-                self.pos.up()
-            }
-            Direction::Down => {
-                if self.pos.row == self.map.len() - 1 {
-                    return false;
+fn parse_input(input: &str) -> HeightMap {
+    let mut start = None;
+    let mut end = None;
+
+    let heights = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(row, line)| line.trim()
+            .chars()
+            .enumerate()
+            .map(|(col, ch)| match ch {
+                'S' => {
+                    start = Some(Position { row, col });
+                    0
                 }
-                self.pos.down()
-            }
-            Direction::Left => {
-                if self.pos.col == 0 {
-                    return false;
-                }
-                self.pos.left()
-            }
-            Direction::Right => {
-                if self.pos.col == self.map[0].len() - 1 {
-                    return false;
+                'E' => {
+                    end = Some(Position { row, col });
+                    25
                 }
-                self.pos.right()
-            }
-        };
-
-        let height_diff = self.map[self.pos.row][self.pos.col] as i32 - self.map[new_pos.row][new_pos.col] as i32;
-
-        height_diff >= -1 && !self.path.contains(&new_pos)
-    }
+                'a'..='z' => ch as u8 - b'a',
+                _ => panic!("invalid elevation: {ch}"),
+            })
+            .collect::<Vec<_>>())
+        .collect::<Vec<_>>();
 
-    fn go(&self, direction: Direction) -> Self {
-        let new_pos = match direction {
-            Direction::Up => Position { row: self.pos.row - 1, col: self.pos.col },
-            Direction::Down => Position { row: self.pos.row + 1, col: self.pos.col },
-            Direction::Left => Position { row: self.pos.row, col: self.pos.col - 1 },
-            Direction::Right => Position { row: self.pos.row, col: self.pos.col + 1 },
-        };
-        let mut new_path = self.path.clone();
+    HeightMap { heights, start: start.expect("no start"), end: end.expect("no end") }
+}
 
-        new_path.push(new_pos);
+/// Steps from every position to the end, with the next position on a shortest way there.
+pub struct Distances {
+    steps: Vec<Vec<Option<usize>>>,
+    next: Vec<Vec<Option<Position>>>,
+}
 
-        Self {
-            map: self.map,
-            pos: new_pos,
-            path: new_path,
-        }
+impl Distances {
+    pub fn get(&self, position: Position) -> Option<usize> {
+        self.steps[position.row][position.col]
     }
 }
 
-fn find_paths<'a>(
-    map_info: MapState<'a>,
-    reached_fields: &mut HashMap<Position, usize>,
-    end: Position,
-) -> Option<MapState<'a>> {
-    let mut result = Option::default();
-    if map_info.pos == end {
-        result = Some(map_info.clone());
-        // print_map_with_path(map_info.map, &map_info.path);
+impl HeightMap {
+    fn height(&self, position: Position) -> u8 {
+        self.heights[position.row][position.col]
     }
 
-    let current_dist = map_info.path.len();
+    fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        let Position { row, col } = position;
 
-    match reached_fields.get(&map_info.pos) {
-        None => {
-            reached_fields.insert(map_info.pos, current_dist);
-        }
-        Some(prev_distance) => {
-            if *prev_distance > current_dist {
-                reached_fields.insert(map_info.pos, current_dist);
-            } else {
-                // this field was reached with shorter distance
-                return None;
-            }
-        }
+        [(row.wrapping_sub(1), col), (row + 1, col), (row, col.wrapping_sub(1)), (row, col + 1)]
+            .into_iter()
+            .filter(|&(row, _)| row < self.heights.len())
+            .filter(move |&(row, col)| col < self.heights[row].len())
+            .map(|(row, col)| Position { row, col })
     }
 
-    for direction in Direction::iter() {
-        if !map_info.can_go(direction) {
-            continue;
-        }
+    /// Positions at given elevation, `a` includes the start.
+    pub fn positions_at(&self, elevation: char) -> Vec<Position> {
+        let height = elevation as u8 - b'a';
+
+        self.heights.iter()
+            .enumerate()
+            .flat_map(|(row, heights)| heights.iter()
+                .enumerate()
+                .filter(move |&(_col, &other)| other == height)
+                .map(move |(col, _)| Position { row, col }))
+            .collect()
+    }
 
-        let new_map_info = map_info.go(direction);
+    /// Breadth first search from the end, walking the steps backwards, which finds the distances from all positions at once.
+    pub fn distances_to_end(&self, rules: ClimbRules) -> Distances {
+        let mut distances = Distances {
+            steps: self.heights.iter().map(|row| vec![None; row.len()]).collect(),
+            next: self.heights.iter().map(|row| vec![None; row.len()]).collect(),
+        };
+        distances.steps[self.end.row][self.end.col] = Some(0);
 
-        let inner_state = find_paths(new_map_info, reached_fields, end);
+        let mut queue = VecDeque::from([self.end]);
+        while let Some(position) = queue.pop_front() {
+            let steps = distances.get(position).unwrap();
 
-        let res_2 = result.clone();
-        if let Some(state) = inner_state {
-            if let Some(ref current) = res_2 { // why without ref i got stackoverflow?
-                if state.path.len() < current.path.len() {
-                    result = Some(state);
+            for previous in self.neighbours(position) {
+                if distances.get(previous).is_none() && rules.can_step(self.height(previous), self.height(position)) {
+                    distances.steps[previous.row][previous.col] = Some(steps + 1);
+                    distances.next[previous.row][previous.col] = Some(position);
+                    queue.push_back(previous);
                 }
-            } else {
-                result = Some(state.clone())
             }
         }
-    }
 
-    result
-}
+        distances
+    }
 
-pub fn hill_climbing_algorithm_part_1(file_name: &str) -> usize {
-    let map = read_input(file_name);
+    /// Shortest path to the end from the nearest of the starts, including both ends.
+    pub fn shortest_path(&self, starts: &[Position], rules: ClimbRules) -> Option<Vec<Position>> {
+        let distances = self.distances_to_end(rules);
+        let start = starts.iter()
+            .filter(|&&start| distances.get(start).is_some())
+            .min_by_key(|&&start| distances.get(start))?;
 
-    let (start, end) = find_start_and_end(&map);
-    let map_info = MapState { map: &map, pos: start, path: vec![] };
-    let mut reached_fields = HashMap::new();
+        let mut path = vec![*start];
+        while let Some(next) = distances.next[path.last().unwrap().row][path.last().unwrap().col] {
+            path.push(next);
+        }
 
-    let map_state = find_paths(map_info, &mut reached_fields, end).unwrap();
+        Some(path)
+    }
 
-    print_map_with_path(map_state.map, &map_state.path);
+    /// Elevation map with the path drawn as arrows pointing to the next step, as in the puzzle.
+    pub fn render(&self, path: &[Position]) -> String {
+        let mut tiles = self.heights.iter()
+            .map(|row| row.iter().map(|&height| (b'a' + height) as char).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        tiles[self.start.row][self.start.col] = 'S';
+        tiles[self.end.row][self.end.col] = 'E';
+
+        for pair in path.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            tiles[from.row][from.col] = match (to.row.cmp(&from.row), to.col.cmp(&from.col)) {
+                (Ordering::Less, _) => '^',
+                (Ordering::Greater, _) => 'v',
+                (_, Ordering::Less) => '<',
+                _ => '>',
+            };
+        }
 
-    *(reached_fields.get(&end).unwrap())
+        tiles.into_iter().map(|row| row.into_iter().collect::<String>() + "\n").collect()
+    }
 }
 
-fn find_start_positions(map: &[Vec<u8>]) -> Vec<Position> {
-    let mut result = Vec::new();
+/// Steps of the shortest path from the nearest of the starts.
+fn fewest_steps(map: &HeightMap, starts: &[Position]) -> usize {
+    let path = map.shortest_path(starts, ClimbRules::default()).expect("the end should be reachable");
+    println!("{}", map.render(&path));
 
-    for (i, row) in map.iter().enumerate() {
-        for (j, elem) in row.iter().enumerate() {
-            if *elem == 1 {
-                result.push(Position { row: i, col: j })
-            }
-        }
-    }
-    result
+    path.len() - 1
 }
 
-fn check(paths: &[Vec<Position>], pos: &Position) -> i32 {
-    for path in paths {
-        for (i, position) in path.iter().enumerate() {
-            if position == pos {
-                return (path.len() - 1 - i) as i32;
-            }
-        }
-    }
-    -1
+pub fn hill_climbing_algorithm_part_1(file_name: &str) -> usize {
+    hill_climbing_algorithm_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn hill_climbing_algorithm_part_1_from_str(input: &str) -> usize {
+    let map = parse_input(input);
+
+    fewest_steps(&map, &[map.start])
 }
 
 pub fn hill_climbing_algorithm_part_2(file_name: &str) -> usize {
-    let map = read_input(file_name);
+    hill_climbing_algorithm_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-    let starts: Vec<Position> = find_start_positions(&map);
-    let (_, end) = find_start_and_end(&map);
-    let mut results = Vec::new();
-    let mut shortest_paths = Vec::new();
+pub fn hill_climbing_algorithm_part_2_from_str(input: &str) -> usize {
+    let map = parse_input(input);
 
-    println!("there are {} starts positions", starts.len());
-    for (progress, start) in starts.iter().enumerate() {
-        let map_info = MapState { map: &map, pos: *start, path: vec![] };
-        let mut reached_fields = HashMap::new();
+    fewest_steps(&map, &map.positions_at('a'))
+}
 
-        // check if there is path cached
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let dist = check(&shortest_paths, start);
-        if dist != -1 {
-            println!("skipped calculation!!!");
-            println!("shortest path starting from {start:?} has length: {dist:?}");
-            results.push(dist as usize);
+    #[test]
+    fn part_1_input_example() {
+        let answer = hill_climbing_algorithm_part_1("inputs/12_input_example.txt");
 
-            continue;
-        }
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 31);
+    }
 
-        let map_state = find_paths(map_info, &mut reached_fields, end);
+    #[test]
+    fn part_1_input() {
+        let answer = hill_climbing_algorithm_part_1("inputs/12_input.txt");
 
-        match map_state {
-            None => {}
-            Some(state) => {
-                shortest_paths.push(state.path.clone());
-            }
-        }
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 391);
+    }
 
-        print!("{progress:4}/{}\t", starts.len());
-        match reached_fields.get(&end) {
-            None => {
-                println!("no result for position {start:?}");
-            }
-            Some(&shortest) => {
-                println!("shortest path starting from {start:?} has length: {shortest:?}");
-                results.push(shortest);
-            }
-        };
+    #[test]
+    fn part_2_input_example() {
+        let answer = hill_climbing_algorithm_part_2("inputs/12_input_example.txt");
+
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 29);
     }
 
-    *(results.iter().min().unwrap())
-}
+    #[test]
+    fn part_2_input() {
+        let answer = hill_climbing_algorithm_part_2("inputs/12_input.txt");
 
-#[allow(dead_code)]
-fn print_map(map: &Vec<Vec<u8>>) {
-    for row in map {
-        for elem in row {
-            print!("{elem:4}");
-        }
-        println!();
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 386);
     }
-}
 
-#[allow(dead_code)]
-fn print_reached_fields(map: &[Vec<u8>], reached: &HashMap<Position, usize>) {
-    for (i, row) in map.iter().enumerate() {
-        for (j, _elem) in row.iter().enumerate() {
-            let position = Position { row: i, col: j };
+    #[test]
+    fn render_input_example() {
+        let map = parse_input(&fs::read_to_string("inputs/12_input_example.txt").unwrap());
+        let path = map.shortest_path(&[map.start], ClimbRules::default()).unwrap();
 
-            match reached.get(&position) {
-                None => print!("   ."),
-                Some(dist) => print!("{:4}", dist)
-            }
-        }
-        println!();
+        assert_eq!(path.first(), Some(&map.start));
+        assert_eq!(path.last(), Some(&map.end));
+        assert_eq!(map.render(&path), ">>vv<<<<\nabvvv<<^\nacvv>E^^\nacv>>>^^\nab>>>>>^\n");
     }
-}
 
-#[allow(dead_code)]
-fn print_map_with_path(map: &[Vec<u8>], path: &[Position]) {
-    for (i, row) in map.iter().enumerate() {
-        for (j, _elem) in row.iter().enumerate() {
-            let position = Position { row: i, col: j };
-            if !path.contains(&position) {
-                print!("  .");
-            } else {
-                let result = path.iter().position(|&r| r == position).unwrap();
-                print!("{:3}", result);
-            }
-        }
-        println!();
+    #[test]
+    fn custom_rules() {
+        let map = parse_input(&fs::read_to_string("inputs/12_input_example.txt").unwrap());
+
+        // climbing two at once allows shortcuts
+        let steep = map.shortest_path(&[map.start], ClimbRules { max_ascent: 2, max_descent: u8::MAX }).unwrap();
+        assert_eq!(steep.len() - 1, 27);
+
+        // not going down at all
+        let rules = ClimbRules { max_ascent: 1, max_descent: 0 };
+        let distances = map.distances_to_end(rules);
+        let path = map.shortest_path(&[map.start], rules).unwrap();
+        assert_eq!(distances.get(map.start), Some(path.len() - 1));
+        assert!(path.windows(2).all(|pair| map.height(pair[0]) <= map.height(pair[1])));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,287 @@
+//! Seeded generators of shareable inputs, for the puzzles whose solutions are worth testing
+//! on other data or at other sizes: days 14, 16, 17, 18, 19 and 20.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const START_VALVE: &str = "AA";
+
+/// Every two letter valve name except the start valve, in random order.
+fn valve_names(rng: &mut impl Rng) -> Vec<String> {
+    let mut names = ('A'..='Z')
+        .cartesian_product('A'..='Z')
+        .map(|(first, second)| format!("{first}{second}"))
+        .filter(|name| name != START_VALVE)
+        .collect::<Vec<_>>();
+
+    names.shuffle(rng);
+    names
+}
+
+/// Random connected valve network in the format of `proboscidea_volcanium`.
+/// The start valve `AA` is always there and always stuck, `working_valves` others have a flow rate.
+pub fn valve_network(rng: &mut impl Rng, valves: usize, working_valves: usize) -> String {
+    assert!((1..=26 * 26).contains(&valves), "there are only {} valve names", 26 * 26);
+    assert!(working_valves < valves, "the start valve is never working");
+
+    let mut names = vec![START_VALVE.to_string()];
+    names.extend(valve_names(rng).into_iter().take(valves - 1));
+
+    // spanning tree keeps the network connected, the extra tunnels make loops
+    let mut tunnels = vec![BTreeSet::new(); valves];
+    let mut connect = |from: usize, to: usize| {
+        tunnels[from].insert(to);
+        tunnels[to].insert(from);
+    };
+    for valve in 1..valves {
+        connect(valve, rng.gen_range(0..valve));
+    }
+    for _ in 0..valves / 2 {
+        let (from, to) = (rng.gen_range(0..valves), rng.gen_range(0..valves));
+        if from != to {
+            connect(from, to);
+        }
+    }
+
+    let mut rates = vec![0; valves];
+    let mut working = (1..valves).collect::<Vec<_>>();
+    working.shuffle(rng);
+    for &valve in working.iter().take(working_valves) {
+        rates[valve] = rng.gen_range(1..=25);
+    }
+
+    let mut order = (0..valves).collect::<Vec<_>>();
+    order.shuffle(rng);
+
+    order.into_iter()
+        .map(|valve| {
+            let neighbors = tunnels[valve].iter().map(|&neighbor| &names[neighbor]).join(", ");
+            let tunnels_description = if tunnels[valve].len() == 1 {
+                format!("tunnel leads to valve {neighbors}")
+            } else {
+                format!("tunnels lead to valves {neighbors}")
+            };
+
+            format!("Valve {} has flow rate={}; {}\n", names[valve], rates[valve], tunnels_description)
+        })
+        .collect()
+}
+
+/// Renames the valves of a `proboscidea_volcanium` input and shuffles its lines.
+/// The start valve `AA` keeps its name, so the answers stay the same.
+pub fn anonymise_valve_network(rng: &mut impl Rng, input: &str) -> String {
+    let is_valve_name = |word: &str| word.len() == 2 && word.chars().all(|ch| ch.is_ascii_uppercase());
+
+    let mut new_names = valve_names(rng).into_iter();
+    let mut renames: HashMap<&str, String> = HashMap::from([(START_VALVE, START_VALVE.to_string())]);
+
+    let mut lines = input.lines()
+        .map(|line| {
+            line.split(' ')
+                .map(|word| {
+                    let name = word.trim_end_matches([',', ';']);
+                    if !is_valve_name(name) {
+                        return word.to_string();
+                    }
+
+                    let new_name = renames.entry(name)
+                        .or_insert_with(|| new_names.next().expect("more valves than valve names"));
+                    word.replacen(name, new_name, 1)
+                })
+                .join(" ")
+        })
+        .collect::<Vec<_>>();
+
+    lines.shuffle(rng);
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+/// Random blueprints in the format of `not_enough_minerals`, with costs in the range of real inputs.
+pub fn blueprints(rng: &mut impl Rng, count: usize) -> String {
+    (1..=count)
+        .map(|id| {
+            format!(
+                "Blueprint {id}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+                Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.\n",
+                rng.gen_range(2..=4),
+                rng.gen_range(2..=4),
+                rng.gen_range(2..=4),
+                rng.gen_range(5..=20),
+                rng.gen_range(2..=4),
+                rng.gen_range(5..=20),
+            )
+        })
+        .collect()
+}
+
+/// Random jet pattern in the format of `pyroclastic_flow`, which has no trailing newline.
+pub fn jet_pattern(rng: &mut impl Rng, length: usize) -> String {
+    (0..length)
+        .map(|_| if rng.gen_bool(0.5) { '>' } else { '<' })
+        .collect()
+}
+
+/// Random rock paths in the format of `regolith_reservoir`, each of `segments` lines that turn
+/// between horizontal and vertical, all of them below the sand source at `500,0`.
+pub fn rock_paths(rng: &mut impl Rng, paths: usize, segments: usize) -> String {
+    assert!(segments >= 1, "a path has at least one segment");
+
+    (0..paths)
+        .map(|_| {
+            let mut point = (rng.gen_range(470..=530), rng.gen_range(10..=100));
+            let mut points = vec![point];
+            for segment in 0..segments {
+                let length = rng.gen_range(1..=8);
+                if segment % 2 == 0 {
+                    point.0 += if rng.gen_bool(0.5) { length } else { -length };
+                } else {
+                    point.1 += if point.1 > length && rng.gen_bool(0.5) { -length } else { length };
+                }
+                points.push(point);
+            }
+
+            points.iter().map(|(x, y)| format!("{x},{y}")).join(" -> ") + "\n"
+        })
+        .collect()
+}
+
+/// Random droplet in the format of `boiling_boulders`, `cubes` distinct lava cubes within a `size`³ box.
+pub fn droplet(rng: &mut impl Rng, size: i32, cubes: usize) -> String {
+    assert!(cubes <= size.pow(3) as usize, "the box has room for {} cubes only", size.pow(3));
+
+    let mut placed = HashSet::new();
+    let mut lines = String::new();
+    while placed.len() < cubes {
+        let cube = (rng.gen_range(0..size), rng.gen_range(0..size), rng.gen_range(0..size));
+        if placed.insert(cube) {
+            lines.push_str(&format!("{},{},{}\n", cube.0, cube.1, cube.2));
+        }
+    }
+
+    lines
+}
+
+/// Random encrypted file in the format of `grove_positioning_system`, `count` numbers of which exactly one is zero.
+pub fn encrypted_file(rng: &mut impl Rng, count: usize) -> String {
+    assert!(count >= 1, "the file needs its zero");
+
+    let zero = rng.gen_range(0..count);
+    (0..count)
+        .map(|index| match index {
+            _ if index == zero => 0,
+            _ if rng.gen_bool(0.5) => rng.gen_range(1..=10_000),
+            _ => -rng.gen_range(1..=10_000),
+        })
+        .map(|number: i64| format!("{number}\n"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::not_enough_minerals::not_enough_minerals_part_1_from_str;
+    use crate::proboscidea_volcanium::proboscidea_volcanium_part_1_from_str;
+    use crate::pyroclastic_flow::pyroclastic_flow_from_str;
+
+    use super::*;
+
+    #[test]
+    fn same_seed_same_input() {
+        let first = valve_network(&mut StdRng::seed_from_u64(16), 20, 8);
+        let second = valve_network(&mut StdRng::seed_from_u64(16), 20, 8);
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn valve_network_is_solvable() {
+        let input = valve_network(&mut StdRng::seed_from_u64(16), 12, 6);
+
+        assert_eq!(input.lines().count(), 12);
+        assert!(proboscidea_volcanium_part_1_from_str(&input) > 0);
+    }
+
+    /// Flow rate of every valve along with the flow rates of its neighbors, which survives renaming.
+    fn valve_rates_signature(input: &str) -> Vec<(u32, Vec<u32>)> {
+        let valves = input.lines()
+            .map(|line| {
+                let words = line.split(' ').collect::<Vec<_>>();
+                let rate = words[4].trim_start_matches("rate=").trim_end_matches(';').parse::<u32>().unwrap();
+                let neighbors = words[9..].iter().map(|word| word.trim_end_matches(',')).collect::<Vec<_>>();
+
+                (words[1], (rate, neighbors))
+            })
+            .collect::<HashMap<_, _>>();
+
+        valves.values()
+            .map(|(rate, neighbors)| (*rate, neighbors.iter().map(|name| valves[name].0).sorted().collect()))
+            .sorted()
+            .collect()
+    }
+
+    #[test]
+    fn anonymised_valve_network_keeps_structure() {
+        let input = fs::read_to_string("inputs/16_input_example.txt").unwrap();
+        let anonymised = anonymise_valve_network(&mut StdRng::seed_from_u64(16), &input);
+
+        assert!(anonymised.contains("Valve AA has flow rate=0"));
+        assert!(!anonymised.contains("BB") && !anonymised.contains("JJ"));
+        assert_eq!(valve_rates_signature(&anonymised), valve_rates_signature(&input));
+        assert_eq!(proboscidea_volcanium_part_1_from_str(&anonymised), 1651);
+    }
+
+    #[test]
+    fn blueprints_are_solvable() {
+        let input = blueprints(&mut StdRng::seed_from_u64(19), 2);
+
+        assert_eq!(input.lines().count(), 2);
+        assert_eq!(not_enough_minerals_part_1_from_str(&input), 53);
+    }
+
+    #[test]
+    fn jet_pattern_is_solvable() {
+        let input = jet_pattern(&mut StdRng::seed_from_u64(17), 200);
+
+        assert_eq!(input.len(), 200);
+        assert!(pyroclastic_flow_from_str(&input, 2022) > 0);
+    }
+
+    #[test]
+    fn rock_paths_turn_below_source() {
+        let input = rock_paths(&mut StdRng::seed_from_u64(14), 20, 4);
+
+        assert_eq!(input.lines().count(), 20);
+        for line in input.lines() {
+            let points = line.split(" -> ")
+                .map(|point| point.split(',').map(|coordinate| coordinate.parse::<i32>().unwrap()).collect_tuple::<(_, _)>().unwrap())
+                .collect::<Vec<_>>();
+
+            assert_eq!(points.len(), 5);
+            assert!(points.iter().all(|&(_, y)| y > 0));
+            assert!(points.windows(2).all(|pair| (pair[0].0 == pair[1].0) != (pair[0].1 == pair[1].1)));
+        }
+    }
+
+    #[test]
+    fn droplet_has_distinct_cubes() {
+        let input = droplet(&mut StdRng::seed_from_u64(18), 5, 100);
+
+        assert_eq!(input.lines().count(), 100);
+        assert_eq!(input.lines().unique().count(), 100);
+    }
+
+    #[test]
+    fn encrypted_file_has_single_zero() {
+        let input = encrypted_file(&mut StdRng::seed_from_u64(20), 100);
+
+        assert_eq!(input.lines().count(), 100);
+        assert_eq!(input.lines().filter(|&line| line == "0").count(), 1);
+    }
+}
@@ -10,7 +10,6 @@ pub mod cathode_ray_tube;
 pub mod monkey_in_the_middle;
 pub mod camp_cleanup;
 pub mod hill_climbing_algorithm;
-pub mod distress_signal_obsolete;
 pub mod distress_signal;
 pub mod beacon_exclusion_zone;
 pub mod proboscidea_volcanium;
@@ -24,3 +23,7 @@ pub mod monkey_map;
 pub mod unstable_diffusion;
 pub mod blizzard_basin;
 pub mod tuning_trouble_2;
+pub mod playground;
+pub mod input_generator;
+pub mod manhattan;
+pub mod full_of_hot_air;
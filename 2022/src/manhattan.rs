@@ -0,0 +1,254 @@
+//! Geometry of balls in the Manhattan metric.
+//!
+//! The ball `|x - cx| + |y - cy| <= r` is the square `|u - cu| <= r, |v - cv| <= r`
+//! in the rotated coordinates `u = x + y`, `v = x - y`, so its boundary is made of lines of constant `u` or `v`.
+
+use std::fmt::{Debug, Formatter};
+
+use itertools::Itertools;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub fn distance(&self, other: &Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Point from rotated coordinates, if `u` and `v` have the same parity.
+    fn from_rotated(u: i64, v: i64) -> Option<Point> {
+        ((u + v) % 2 == 0).then_some(Point { x: (u + v) / 2, y: (u - v) / 2 })
+    }
+}
+
+impl Debug for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Ball {
+    pub center: Point,
+    pub radius: i64,
+}
+
+impl Ball {
+    pub fn contains(&self, point: &Point) -> bool {
+        self.center.distance(point) <= self.radius
+    }
+
+    /// Columns of the ball in given row.
+    pub fn row(&self, y: i64) -> Option<(i64, i64)> {
+        let half_width = self.radius - (y - self.center.y).abs();
+        (half_width >= 0).then_some((self.center.x - half_width, self.center.x + half_width))
+    }
+
+    fn u(&self) -> i64 {
+        self.center.x + self.center.y
+    }
+
+    fn v(&self) -> i64 {
+        self.center.x - self.center.y
+    }
+}
+
+/// Rectangle of the points with `min.x <= x <= max.x` and `min.y <= y <= max.y`.
+#[derive(Copy, Clone, Debug)]
+pub struct Rectangle {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rectangle {
+    pub fn contains(&self, point: &Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    fn width(&self) -> u64 {
+        (self.max.x - self.min.x + 1).max(0) as u64
+    }
+}
+
+/// Sorted disjoint inclusive intervals of integers, touching intervals are merged.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntervalSet {
+    intervals: Vec<(i64, i64)>,
+}
+
+impl IntervalSet {
+    pub fn intervals(&self) -> &[(i64, i64)] {
+        &self.intervals
+    }
+
+    /// Count of the integers in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(|(start, end)| (end - start + 1) as u64).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let index = self.intervals.partition_point(|&(_, end)| end < value);
+        self.intervals.get(index).is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// Part of the set within `min..=max`.
+    pub fn clip(&self, min: i64, max: i64) -> IntervalSet {
+        IntervalSet {
+            intervals: self.intervals.iter()
+                .filter(|&&(start, end)| start <= max && end >= min)
+                .map(|&(start, end)| (start.max(min), end.min(max)))
+                .collect(),
+        }
+    }
+}
+
+impl FromIterator<(i64, i64)> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = (i64, i64)>>(iter: T) -> Self {
+        let mut intervals: Vec<(i64, i64)> = Vec::new();
+
+        for (start, end) in iter.into_iter().filter(|(start, end)| start <= end).sorted() {
+            match intervals.last_mut() {
+                Some((_, last_end)) if start <= *last_end + 1 => *last_end = end.max(*last_end),
+                _ => intervals.push((start, end)),
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+}
+
+/// Columns of given row covered by the balls.
+pub fn row_coverage(balls: &[Ball], y: i64) -> IntervalSet {
+    balls.iter().filter_map(|ball| ball.row(y)).collect()
+}
+
+/// A point of the rectangle not covered by any ball.
+///
+/// An isolated uncovered point lies just outside of the balls covering its neighbours, so it is searched for
+/// on the crossings of the lines one and two steps outside of the balls with each other and with the sides of the rectangle.
+/// The second line is needed, when the point lies in a diagonal gap and only the ball covering its diagonal neighbour crosses it.
+pub fn uncovered_point(balls: &[Ball], rectangle: &Rectangle) -> Option<Point> {
+    let outside = |center: i64, radius: i64| [center - radius - 2, center - radius - 1, center + radius + 1, center + radius + 2];
+    let us = balls.iter().flat_map(|ball| outside(ball.u(), ball.radius)).collect::<Vec<_>>();
+    let vs = balls.iter().flat_map(|ball| outside(ball.v(), ball.radius)).collect::<Vec<_>>();
+    let (min, max) = (rectangle.min, rectangle.max);
+
+    let crossings = us.iter().cartesian_product(vs.iter()).filter_map(|(&u, &v)| Point::from_rotated(u, v));
+    let on_sides = us.iter()
+        .flat_map(|&u| [Point { x: min.x, y: u - min.x }, Point { x: max.x, y: u - max.x }, Point { x: u - min.y, y: min.y }, Point { x: u - max.y, y: max.y }])
+        .chain(vs.iter().flat_map(|&v| [Point { x: min.x, y: min.x - v }, Point { x: max.x, y: max.x - v }, Point { x: v + min.y, y: min.y }, Point { x: v + max.y, y: max.y }]));
+    let corners = [min, max, Point { x: min.x, y: max.y }, Point { x: max.x, y: min.y }];
+
+    crossings.chain(on_sides)
+        .chain(corners)
+        .find(|point| rectangle.contains(point) && !balls.iter().any(|ball| ball.contains(point)))
+}
+
+/// Count of the points of the rectangle not covered by any ball.
+///
+/// Ends of the covered intervals move by one column per row, so the uncovered width of a row is linear
+/// between the rows, where a ball starts, ends or turns, or where the ends cross each other or the sides.
+pub fn uncovered_area(balls: &[Ball], rectangle: &Rectangle) -> u64 {
+    let (min, max) = (rectangle.min, rectangle.max);
+    if rectangle.width() == 0 || min.y > max.y {
+        return 0;
+    }
+
+    let uncovered_width = |y: i64| rectangle.width() - row_coverage(balls, y).clip(min.x, max.x).len();
+
+    // lines followed by the left ends and by the columns right after the right ends
+    let us = balls.iter().flat_map(|ball| [ball.u() - ball.radius, ball.u() + ball.radius + 1]).collect::<Vec<_>>();
+    let vs = balls.iter().flat_map(|ball| [ball.v() - ball.radius, ball.v() + ball.radius + 1]).collect::<Vec<_>>();
+    let sides = [min.x, max.x + 1];
+
+    let crossings = us.iter()
+        .cartesian_product(vs.iter())
+        .map(|(u, v)| (u - v).div_euclid(2))
+        .chain(us.iter().cartesian_product(sides).map(|(u, x)| u - x))
+        .chain(vs.iter().cartesian_product(sides).map(|(v, x)| x - v))
+        .flat_map(|y| [y, y + 1]);
+    let turns = balls.iter().flat_map(|ball| [ball.center.y - ball.radius, ball.center.y, ball.center.y + 1, ball.center.y + ball.radius + 1]);
+
+    let rows = crossings.chain(turns)
+        .chain([min.y, max.y + 1])
+        .filter(|y| (min.y..=max.y + 1).contains(y))
+        .sorted()
+        .dedup()
+        .collect::<Vec<_>>();
+
+    rows.iter()
+        .tuple_windows()
+        .map(|(&first, &next)| {
+            let last = next - 1;
+            (uncovered_width(first) + uncovered_width(last)) * (last - first + 1) as u64 / 2
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    fn brute_force_area(balls: &[Ball], rectangle: &Rectangle) -> u64 {
+        (rectangle.min.y..=rectangle.max.y)
+            .cartesian_product(rectangle.min.x..=rectangle.max.x)
+            .filter(|&(y, x)| !balls.iter().any(|ball| ball.contains(&Point { x, y })))
+            .count() as u64
+    }
+
+    #[test]
+    fn interval_set() {
+        let set = [(5, 7), (1, 2), (3, 3), (10, 12), (11, 15), (20, 19)].into_iter().collect::<IntervalSet>();
+
+        assert_eq!(set.intervals(), &[(1, 3), (5, 7), (10, 15)]);
+        assert_eq!(set.len(), 12);
+        assert!(set.contains(6) && !set.contains(4) && !set.contains(16));
+        assert_eq!(set.clip(2, 11).intervals(), &[(2, 3), (5, 7), (10, 11)]);
+    }
+
+    #[test]
+    fn area_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(15);
+
+        for _ in 0..200 {
+            let balls = (0..rng.gen_range(1..6))
+                .map(|_| Ball { center: Point { x: rng.gen_range(-10..30), y: rng.gen_range(-10..30) }, radius: rng.gen_range(0..12) })
+                .collect::<Vec<_>>();
+            let min = Point { x: rng.gen_range(-5..15), y: rng.gen_range(-5..15) };
+            let rectangle = Rectangle { min, max: Point { x: min.x + rng.gen_range(0..20), y: min.y + rng.gen_range(0..20) } };
+
+            assert_eq!(uncovered_area(&balls, &rectangle), brute_force_area(&balls, &rectangle), "{balls:?} in {rectangle:?}");
+        }
+    }
+
+    #[test]
+    fn isolated_point_is_found() {
+        let mut rng = StdRng::seed_from_u64(15);
+        let rectangle = Rectangle { min: Point { x: 0, y: 0 }, max: Point { x: 19, y: 19 } };
+
+        for _ in 0..100 {
+            let hidden = Point { x: rng.gen_range(0..20), y: rng.gen_range(0..20) };
+            let mut balls = Vec::new();
+
+            // cover everything around the hidden point
+            while balls.is_empty() || brute_force_area(&balls, &rectangle) > 1 {
+                let ball = Ball { center: Point { x: rng.gen_range(-5..25), y: rng.gen_range(-5..25) }, radius: rng.gen_range(0..10) };
+                if !ball.contains(&hidden) {
+                    balls.push(ball);
+                }
+            }
+
+            assert_eq!(uncovered_point(&balls, &rectangle), Some(hidden), "{balls:?}");
+        }
+    }
+}
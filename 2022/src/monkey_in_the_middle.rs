@@ -1,17 +1,23 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs;
+use std::hash::Hash;
+use std::io::BufRead;
+use std::iter::Peekable;
+use std::ops::{Add, Mul};
 
 use itertools::Itertools;
+use num::integer::lcm;
+use num::{BigUint, Zero};
 
-fn read_input(file_name: &str) -> Vec<Monkey> {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
+fn parse_input(input: &str) -> Vec<Monkey> {
+    let mut reader = input.as_bytes();
 
     let mut monkeys = Vec::new();
 
     loop {
         let mut items: Vec<u64> = Vec::new();
-        let mut operation: Operation = Operation { type_: OperationType::Add, elem: Some(0) };
+        let mut operation = Expr::Old;
         let mut divisor: u64 = 0;
         let mut true_target: usize = 0;
         let mut false_target: usize = 0;
@@ -22,9 +28,10 @@ fn read_input(file_name: &str) -> Vec<Monkey> {
             buf = buf.trim().to_string();
 
             if buf.starts_with("Starting items:") {
-                items = buf.trim_start_matches("Starting items: ")
-                    .split(", ")
-                    .map(|s| s.parse::<u64>().unwrap())
+                items = buf.trim_start_matches("Starting items:")
+                    .split(',')
+                    .filter(|s| !s.trim().is_empty())
+                    .map(|s| s.trim().parse::<u64>().unwrap())
                     .collect::<Vec<_>>();
             } else if buf.starts_with("Operation: ") {
                 operation = parse_operation(buf.trim_start_matches("Operation: "));
@@ -44,7 +51,6 @@ fn read_input(file_name: &str) -> Vec<Monkey> {
         }
 
         monkeys.push(Monkey {
-            inspects_counter: 0,
             items,
             operation,
             test: Test {
@@ -64,47 +70,71 @@ fn read_input(file_name: &str) -> Vec<Monkey> {
     monkeys
 }
 
-fn parse_operation(s: &str) -> Operation {
-    let ingredients = s.trim_start_matches("new = old ").split(' ').collect::<Vec<_>>();
-    let (op, second) = (ingredients[0], ingredients[1]);
-
-    match second.parse::<u64>() {
-        Ok(value) => match op {
-            "+" => Operation { type_: OperationType::Add, elem: Some(value) },
-            "*" => Operation { type_: OperationType::Multiply, elem: Some(value) },
-            &_ => panic!()
-        },
-        Err(_) => match op {
-            "+" => Operation { type_: OperationType::Add, elem: None },
-            "*" => Operation { type_: OperationType::Multiply, elem: None },
-            &_ => panic!()
-        }
+fn parse_operation(s: &str) -> Expr {
+    let expression = s.trim_start_matches("new =")
+        .replace('(', " ( ")
+        .replace(')', " ) ");
+    let mut tokens = expression.split_whitespace().peekable();
+
+    let expr = parse_sum(&mut tokens);
+    assert!(tokens.next().is_none(), "unexpected end of operation: {s}");
+
+    expr
+}
+
+/// `sum = product ("+" product)*`
+fn parse_sum<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Expr {
+    let mut expr = parse_product(tokens);
+
+    while tokens.next_if_eq(&"+").is_some() {
+        expr = Expr::Add(Box::new(expr), Box::new(parse_product(tokens)));
     }
+
+    expr
 }
 
-#[derive(Debug)]
-enum OperationType {
-    Add,
-    Multiply,
+/// `product = term ("*" term)*`
+fn parse_product<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Expr {
+    let mut expr = parse_term(tokens);
+
+    while tokens.next_if_eq(&"*").is_some() {
+        expr = Expr::Multiply(Box::new(expr), Box::new(parse_term(tokens)));
+    }
+
+    expr
 }
 
+/// `term = "old" | number | "(" sum ")"`
+fn parse_term<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Expr {
+    match tokens.next() {
+        Some("old") => Expr::Old,
+        Some("(") => {
+            let expr = parse_sum(tokens);
+            assert_eq!(tokens.next(), Some(")"), "unclosed parenthesis");
+            expr
+        }
+        Some(token) => Expr::Constant(token.parse::<u64>().unwrap_or_else(|_| panic!("invalid token: {token}"))),
+        None => panic!("operation ended too early"),
+    }
+}
+
+/// Operation computing the new worry level from the old one.
+/// Only addition and multiplication are supported, they keep the divisibility tests intact under modulo.
 #[derive(Debug)]
-struct Operation {
-    type_: OperationType,
-    elem: Option<u64>,
+enum Expr {
+    Old,
+    Constant(u64),
+    Add(Box<Expr>, Box<Expr>),
+    Multiply(Box<Expr>, Box<Expr>),
 }
 
-impl Operation {
-    fn calculate(&self, old: u64) -> u64 {
-        match self.elem {
-            None => match self.type_ {
-                OperationType::Add => old + old,
-                OperationType::Multiply => old * old,
-            },
-            Some(val) => match self.type_ {
-                OperationType::Add => old + val,
-                OperationType::Multiply => old * val,
-            }
+impl Expr {
+    fn evaluate<W: Worry>(&self, old: &W) -> W {
+        match self {
+            Expr::Old => old.clone(),
+            Expr::Constant(value) => W::from(*value),
+            Expr::Add(lhs, rhs) => lhs.evaluate(old) + rhs.evaluate(old),
+            Expr::Multiply(lhs, rhs) => lhs.evaluate(old) * rhs.evaluate(old),
         }
     }
 }
@@ -116,89 +146,304 @@ struct Test {
     false_target: usize,
 }
 
-impl Test {
-    fn test(&self, dividend: u64) -> usize {
-        if dividend % self.divisor == 0 {
-            self.true_target
-        } else {
-            self.false_target
-        }
-    }
-}
-
 #[derive(Debug)]
 struct Monkey {
-    inspects_counter: usize,
     items: Vec<u64>,
-    operation: Operation,
+    operation: Expr,
     test: Test,
 }
 
-pub enum DecreaseStrategy {
-    DivideByThree,
-    DivideByDivisorProduct,
+pub trait Worry: Clone + Debug + Eq + Hash + From<u64> + Add<Output = Self> + Mul<Output = Self> {}
+
+impl<W: Clone + Debug + Eq + Hash + From<u64> + Add<Output = W> + Mul<Output = W>> Worry for W {}
+
+/// How the worry level is kept in check after each inspection.
+pub trait WorryPolicy {
+    type Worry: Worry;
+
+    /// Called once with the divisors of all monkeys' tests before the first round.
+    fn prepare(&mut self, _divisors: &[u64]) {}
+
+    fn relieve(&self, worry: Self::Worry) -> Self::Worry;
+
+    fn is_divisible(&self, worry: &Self::Worry, divisor: u64) -> bool;
+}
+
+/// Worry level is divided by `k` after each inspection, as in part 1 with `k` = 3.
+pub struct DivideBy(pub u64);
+
+impl WorryPolicy for DivideBy {
+    type Worry = u64;
+
+    fn relieve(&self, worry: u64) -> u64 {
+        worry / self.0
+    }
+
+    fn is_divisible(&self, worry: &u64, divisor: u64) -> bool {
+        worry.is_multiple_of(divisor)
+    }
+}
+
+/// Worry level is kept modulo the least common multiple of the divisors, which changes no test result.
+#[derive(Default)]
+pub struct ModuloLcm {
+    modulus: u64,
 }
 
-pub fn monkey_in_the_middle(file_name: &str, round_count: usize, decrease_strategy: DecreaseStrategy) -> usize {
-    let mut monkeys = read_input(file_name);
-    let divisors_product = monkeys.iter().map(|m| m.test.divisor).reduce(|acc, e| acc * e).unwrap();
+impl WorryPolicy for ModuloLcm {
+    type Worry = u64;
 
-    for round in 1..=round_count {
-        for id in 0..monkeys.len() {
-            let mut items_to_throw = Vec::new();
-            let monkey = &mut monkeys[id];
+    fn prepare(&mut self, divisors: &[u64]) {
+        self.modulus = divisors.iter().copied().reduce(lcm).unwrap_or(1);
+    }
 
-            for item in monkey.items.iter() {
-                monkey.inspects_counter += 1;
-                let new_worry = monkey.operation.calculate(*item);
+    fn relieve(&self, worry: u64) -> u64 {
+        worry % self.modulus
+    }
 
-                let new_worry = match decrease_strategy {
-                    DecreaseStrategy::DivideByThree => decrease_worry(new_worry),
-                    DecreaseStrategy::DivideByDivisorProduct => decrease_worry_2(new_worry, divisors_product)
-                };
+    fn is_divisible(&self, worry: &u64, divisor: u64) -> bool {
+        worry.is_multiple_of(divisor)
+    }
+}
 
-                let throw_target = monkey.test.test(new_worry);
+/// Worry level is never relieved, so it grows without bound.
+pub struct Unbounded;
 
-                items_to_throw.push((throw_target, new_worry));
-            }
-            monkey.items = Vec::new();
+impl WorryPolicy for Unbounded {
+    type Worry = BigUint;
 
-            for (target_id, item) in items_to_throw {
-                monkeys[target_id].items.push(item);
+    fn relieve(&self, worry: BigUint) -> BigUint {
+        worry
+    }
+
+    fn is_divisible(&self, worry: &BigUint, divisor: u64) -> bool {
+        (worry % divisor).is_zero()
+    }
+}
+
+/// Inspection by a monkey in given round, both 0-based.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Inspection {
+    pub round: u64,
+    pub monkey: usize,
+}
+
+#[derive(Debug)]
+pub struct ItemTrace {
+    pub starting_worry: u64,
+    pub inspections: Vec<Inspection>,
+}
+
+/// Keep away game of the monkeys.
+/// Items never affect each other, so every item is followed on its own through the rounds.
+pub struct MonkeyGame<P: WorryPolicy> {
+    monkeys: Vec<Monkey>,
+    policy: P,
+}
+
+impl<P: WorryPolicy> MonkeyGame<P> {
+    pub fn new(input: &str, mut policy: P) -> MonkeyGame<P> {
+        let monkeys = parse_input(input);
+        policy.prepare(&monkeys.iter().map(|monkey| monkey.test.divisor).collect::<Vec<_>>());
+
+        MonkeyGame { monkeys, policy }
+    }
+
+    /// Every item as its holder and its worry level.
+    fn items(&self) -> impl Iterator<Item = (usize, u64)> + '_ {
+        self.monkeys.iter()
+            .enumerate()
+            .flat_map(|(id, monkey)| monkey.items.iter().map(move |&worry| (id, worry)))
+    }
+
+    /// Plays one round for an item held by given monkey, calling `inspected` with every monkey inspecting it.
+    /// Returns its holder and worry level at the end of the round.
+    fn play_round(&self, mut holder: usize, mut worry: P::Worry, mut inspected: impl FnMut(usize)) -> (usize, P::Worry) {
+        loop {
+            let monkey = &self.monkeys[holder];
+            inspected(holder);
+
+            worry = self.policy.relieve(monkey.operation.evaluate(&worry));
+            let target = if self.policy.is_divisible(&worry, monkey.test.divisor) {
+                monkey.test.true_target
+            } else {
+                monkey.test.false_target
+            };
+
+            // monkeys ahead in order get their turn in this round yet
+            if target <= holder {
+                return (target, worry);
             }
+            holder = target;
         }
+    }
+
+    /// Path of every item through the monkeys in the first `rounds` rounds.
+    pub fn trace(&self, rounds: u64) -> Vec<ItemTrace> {
+        self.items()
+            .map(|(mut holder, starting_worry)| {
+                let mut worry = P::Worry::from(starting_worry);
+                let mut inspections = Vec::new();
+
+                for round in 0..rounds {
+                    (holder, worry) = self.play_round(holder, worry, |monkey| inspections.push(Inspection { round, monkey }));
+                }
+
+                ItemTrace { starting_worry, inspections }
+            })
+            .collect()
+    }
+
+    /// Inspections of every monkey in `rounds` rounds.
+    /// An item, which comes back to a holder and worry level it had at the start of a round, repeats from there,
+    /// so only the rounds until that repetition are played.
+    pub fn inspections(&self, rounds: u64) -> Vec<u64> {
+        let mut inspections = vec![0; self.monkeys.len()];
+
+        for (holder, starting_worry) in self.items() {
+            let mut state = (holder, P::Worry::from(starting_worry));
+            let mut seen: HashMap<(usize, P::Worry), u64> = HashMap::new();
+            // inspections per monkey, summed over the rounds played so far, at the start of each round
+            let mut history: Vec<Vec<u64>> = vec![vec![0; self.monkeys.len()]];
+
+            for round in 0..rounds {
+                if let Some(&cycle_start) = seen.get(&state) {
+                    let cycle_length = round - cycle_start;
+                    let (cycles, remainder) = ((rounds - cycle_start) / cycle_length, (rounds - cycle_start) % cycle_length);
+                    let (start, end) = (&history[cycle_start as usize], &history[round as usize]);
+                    let rest = &history[(cycle_start + remainder) as usize];
 
-        if [1, 20, 1000, 2000, 3000, 4000, 5000, 6000, 10000].contains(&round) {
-            print_round_summarize(round, &monkeys);
+                    for monkey in 0..self.monkeys.len() {
+                        inspections[monkey] += start[monkey] + cycles * (end[monkey] - start[monkey]) + (rest[monkey] - start[monkey]);
+                    }
+                    break;
+                }
+
+                let mut counts = history.last().unwrap().clone();
+                let next_state = self.play_round(state.0, state.1.clone(), |monkey| counts[monkey] += 1);
+                seen.insert(state, round);
+                history.push(counts);
+                state = next_state;
+
+                if round + 1 == rounds {
+                    for (total, count) in inspections.iter_mut().zip(history.last().unwrap()) {
+                        *total += count;
+                    }
+                }
+            }
         }
+
+        inspections
     }
+}
 
-    monkeys.iter()
-        .map(|monkey| monkey.inspects_counter)
+/// Product of the two highest inspection counts.
+pub fn monkey_business(inspections: &[u64]) -> u64 {
+    inspections.iter()
         .sorted_by(|count1, count2| count2.cmp(count1))
         .take(2)
-        .reduce(|acc, e| acc * e)
-        .unwrap()
+        .product()
+}
+
+pub fn monkey_in_the_middle<P: WorryPolicy>(file_name: &str, round_count: u64, policy: P) -> u64 {
+    monkey_in_the_middle_from_str(&fs::read_to_string(file_name).unwrap(), round_count, policy)
 }
 
-#[allow(dead_code)]
-fn print_round_summarize(round: usize, monkeys: &[Monkey]) {
-    println!("After round {round}");
-    // for (i, monkey) in monkeys.iter().enumerate() {
-    //     println!("Monkey {i}: {:?}", monkey.items)
-    // }
-    // println!();
+pub fn monkey_in_the_middle_from_str<P: WorryPolicy>(input: &str, round_count: u64, policy: P) -> u64 {
+    let inspections = MonkeyGame::new(input, policy).inspections(round_count);
 
-    for (i, monkey) in monkeys.iter().enumerate() {
-        println!("Monkey {i} inspected items {} times", monkey.inspects_counter)
+    for (i, count) in inspections.iter().enumerate() {
+        println!("Monkey {i} inspected items {count} times")
     }
-    println!();
-}
 
-fn decrease_worry(worry: u64) -> u64 {
-    worry.div_euclid(3)
+    monkey_business(&inspections)
 }
 
-fn decrease_worry_2(worry: u64, num: u64) -> u64 {
-    worry % num
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_input_example() {
+        let answer = monkey_in_the_middle("inputs/11_input_example.txt", 20, DivideBy(3));
+
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 10605);
+    }
+
+    #[test]
+    fn part_1_input() {
+        let answer = monkey_in_the_middle("inputs/11_input.txt", 20, DivideBy(3));
+
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 50172);
+    }
+
+    #[test]
+    fn part_2_input_example() {
+        let answer = monkey_in_the_middle("inputs/11_input_example.txt", 10_000, ModuloLcm::default());
+
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 2713310158);
+    }
+
+    #[test]
+    fn part_2_input() {
+        let answer = monkey_in_the_middle("inputs/11_input.txt", 10_000, ModuloLcm::default());
+
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 11614682178);
+    }
+
+    #[test]
+    fn cycles_match_playing_every_round() {
+        let input = fs::read_to_string("inputs/11_input_example.txt").unwrap();
+        let game = MonkeyGame::new(&input, ModuloLcm::default());
+
+        for rounds in [1, 20, 1000, 5000] {
+            let mut played = vec![0; 4];
+            for trace in game.trace(rounds) {
+                for inspection in trace.inspections {
+                    played[inspection.monkey] += 1;
+                }
+            }
+
+            assert_eq!(game.inspections(rounds), played);
+        }
+    }
+
+    #[test]
+    fn billion_rounds_input() {
+        let game = MonkeyGame::new(&fs::read_to_string("inputs/11_input.txt").unwrap(), ModuloLcm::default());
+
+        assert!(game.inspections(1_000_000_000).iter().sum::<u64>() > 1_000_000_000);
+    }
+
+    #[test]
+    fn unbounded_matches_modulo_lcm() {
+        let input = fs::read_to_string("inputs/11_input_example.txt").unwrap();
+
+        let unbounded = MonkeyGame::new(&input, Unbounded).inspections(30);
+        let modulo = MonkeyGame::new(&input, ModuloLcm::default()).inspections(30);
+
+        assert_eq!(unbounded, modulo);
+    }
+
+    #[test]
+    fn trace_input_example() {
+        let input = fs::read_to_string("inputs/11_input_example.txt").unwrap();
+        let traces = MonkeyGame::new(&input, DivideBy(3)).trace(1);
+
+        // 79 goes to monkey 3 as 500, which throws it to monkey 0 as 620
+        assert_eq!(traces[0].starting_worry, 79);
+        assert_eq!(traces[0].inspections, vec![Inspection { round: 0, monkey: 0 }, Inspection { round: 0, monkey: 3 }]);
+    }
+
+    #[test]
+    fn general_operations() {
+        let expr = parse_operation("new = (old + 2) * old + 3 * 4");
+
+        assert_eq!(expr.evaluate(&5_u64), 47);
+        assert_eq!(expr.evaluate(&BigUint::from(5_u64)), BigUint::from(47_u64));
+    }
 }
@@ -1,8 +1,5 @@
-use std::collections::HashMap;
-use std::fmt::{Debug, Formatter};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::iter;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
 
 use itertools::Itertools;
 use strum::IntoEnumIterator;
@@ -36,6 +33,25 @@ impl Direction {
         }
     }
 
+    fn opposite(&self) -> Self {
+        match self {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+        }
+    }
+
+    /// Value of the facing in the password.
+    fn facing(&self) -> usize {
+        match self {
+            Direction::Right => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Up => 3,
+        }
+    }
+
     fn turn_left(&mut self) {
         // println!("turning left");
         *self = match self {
@@ -47,20 +63,13 @@ impl Direction {
     }
 }
 
-fn read_input(file_name: &str) -> (Map, Vec<MoveInstruction>) {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
-    let mut buf = String::new();
-
-    let mut map = Vec::new();
+fn parse_input(input: &str) -> (Map, Vec<MoveInstruction>) {
+    let (map, path) = input.trim_end().split_once("\n\n")
+        .or_else(|| input.trim_end().split_once("\r\n\r\n"))
+        .expect("map and path should be separated by an empty line");
 
-    // parse map
-    while reader.read_line(&mut buf).is_ok() {
-        if buf == "\r\n" {
-            break;
-        }
-
-        let line = buf.trim_end_matches("\r\n")
+    let mut map = map.lines()
+        .map(|line| line.trim_end_matches('\r')
             .chars()
             .map(|ch| match ch {
                 ' ' => -1,
@@ -68,54 +77,29 @@ fn read_input(file_name: &str) -> (Map, Vec<MoveInstruction>) {
                 '#' => 1,
                 _ => panic!("invalid char: {}", ch)
             })
-            .collect::<Vec<_>>();
-
-        map.push(line);
-        buf = String::new();
-    };
-
-    let _ = reader.read_line(&mut buf).unwrap();
+            .collect::<Vec<_>>())
+        .collect::<Map>();
 
     // parse instructions
     let mut instructions = Vec::new();
-    let mut number_buff = Vec::new();
-    for ch in buf.trim().chars() {
-        if ch.is_ascii_digit() {
-            number_buff.push(ch);
-            continue;
-        }
-
-        let mut sum = 0;
-        let mut magnitude = 1;
-        while let Some(digit) = number_buff.pop() {
-            sum += magnitude * digit.to_digit(10).unwrap() as usize;
-            magnitude *= 10;
-        }
-
-        instructions.push(MoveInstruction::Go(sum));
-
+    let mut number = 0;
+    for ch in path.trim().chars() {
         match ch {
-            'L' => instructions.push(MoveInstruction::TurnLeft),
-            'R' => instructions.push(MoveInstruction::TurnRight),
-            _ => {}
-        };
-    }
-
-    if !buf.is_empty() {
-        let mut sum = 0;
-        let mut magnitude = 1;
-        while let Some(digit) = number_buff.pop() {
-            sum += magnitude * digit.to_digit(10).unwrap() as usize;
-            magnitude *= 10;
+            '0'..='9' => number = 10 * number + ch.to_digit(10).unwrap() as usize,
+            'L' | 'R' => {
+                instructions.push(MoveInstruction::Go(number));
+                instructions.push(if ch == 'L' { MoveInstruction::TurnLeft } else { MoveInstruction::TurnRight });
+                number = 0;
+            }
+            _ => panic!("invalid instruction: {}", ch)
         }
-
-        instructions.push(MoveInstruction::Go(sum));
     }
+    instructions.push(MoveInstruction::Go(number));
 
     // pad rows to have the same length for convenience
     let max_length = map.iter().map(|row| row.len()).max().unwrap();
     for row in map.iter_mut() {
-        row.append(&mut iter::repeat(-1).take(max_length - row.len()).collect());
+        row.resize(max_length, -1);
     }
 
     (map, instructions)
@@ -197,7 +181,11 @@ fn add_wrapped_obstacles_indexes(obstacles_indexes: &mut Vec<i32>, road_start: u
 }
 
 pub fn monkey_map_part_1(file_name: &str) -> i32 {
-    let (map, instructions) = read_input(file_name);
+    monkey_map_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn monkey_map_part_1_from_str(input: &str) -> i32 {
+    let (map, instructions) = parse_input(input);
 
     print_map(&map);
     println!("{:?}", instructions);
@@ -248,501 +236,269 @@ pub fn monkey_map_part_1(file_name: &str) -> i32 {
         }
     }
 
-    1000 * (row + 1) + 4 * (col + 1) + match dir {
-        Direction::Right => 0,
-        Direction::Down => 1,
-        Direction::Left => 2,
-        Direction::Up => 3,
-    }
+    1000 * (row + 1) + 4 * (col + 1) + dir.facing() as i32
 }
 
 // Part 2
 
-// Input net:
-//   ■ ■   |   6 5
-//   ■     |   3
-// ■ ■     | 2 1
-// ■       | 4
-//
-//   x ->
-// y (0,0) (0,1)
-// | (0,1) (1,1)
-// v
-
-type PointsMap = Vec<Vec<Point>>;
-type EdgeIdentifier = (DiceSide, Direction);
-type DiceSideInfo = ((usize, usize), Direction);
-
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+/// Unit vector in the space of the folded cube.
+type Vector = [i32; 3];
+
+fn negate([x, y, z]: Vector) -> Vector {
+    [-x, -y, -z]
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 struct Point {
     x: usize,
     y: usize,
 }
 
-impl Debug for Point {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
-    }
+/// Face of the cube as laid out in the net, with the directions its normal and net axes point to once folded.
+#[derive(Debug)]
+struct Face {
+    /// upper-left tile in the net
+    corner: Point,
+    normal: Vector,
+    right: Vector,
+    down: Vector,
 }
 
-#[derive(Copy, Clone, Debug, Eq, EnumIter, Ord, PartialOrd, PartialEq, Hash)]
-enum DiceSide {
-    Side1,
-    Side2,
-    Side3,
-    Side4,
-    Side5,
-    Side6,
-}
+impl Face {
+    fn vector(&self, dir: Direction) -> Vector {
+        match dir {
+            Direction::Right => self.right,
+            Direction::Left => negate(self.right),
+            Direction::Down => self.down,
+            Direction::Up => negate(self.down),
+        }
+    }
 
-// info about relations between dice's sides
-// dice net:
-//   ■       |   6
-//   ■       |   3
-// ■ ■ ■     | 2 1 5
-//   ■       |   4
-struct DiceInfo {
-    /// Map with information about adjacent edges of the dice and information if order of points is reversed
-    adjacent_edges: HashMap<EdgeIdentifier, (EdgeIdentifier, bool)>,
-}
+    /// Face next to this one in the net in given direction, rolled over their common edge.
+    fn roll(&self, dir: Direction, side_length: usize) -> Option<Face> {
+        let Point { x, y } = self.corner;
+        let corner = match dir {
+            Direction::Left => Point { x: x.checked_sub(side_length)?, y },
+            Direction::Right => Point { x: x + side_length, y },
+            Direction::Up => Point { x, y: y.checked_sub(side_length)? },
+            Direction::Down => Point { x, y: y + side_length },
+        };
 
-impl DiceInfo {
-    fn new() -> Self {
-        let adjacent_edges = HashMap::from([
-            ((DiceSide::Side1, Direction::Up), ((DiceSide::Side3, Direction::Down), false)),
-            ((DiceSide::Side1, Direction::Down), ((DiceSide::Side4, Direction::Down), true)),
-            ((DiceSide::Side1, Direction::Left), ((DiceSide::Side2, Direction::Down), true)),
-            ((DiceSide::Side1, Direction::Right), ((DiceSide::Side5, Direction::Down), false)),
-            ((DiceSide::Side2, Direction::Up), ((DiceSide::Side6, Direction::Right), true)),
-            ((DiceSide::Side2, Direction::Down), ((DiceSide::Side1, Direction::Left), true)),
-            ((DiceSide::Side2, Direction::Left), ((DiceSide::Side4, Direction::Right), false)),
-            ((DiceSide::Side2, Direction::Right), ((DiceSide::Side3, Direction::Left), false)),
-            ((DiceSide::Side3, Direction::Up), ((DiceSide::Side6, Direction::Up), true)),
-            ((DiceSide::Side3, Direction::Down), ((DiceSide::Side1, Direction::Up), false)),
-            ((DiceSide::Side3, Direction::Left), ((DiceSide::Side2, Direction::Right), false)),
-            ((DiceSide::Side3, Direction::Right), ((DiceSide::Side5, Direction::Left), false)),
-            ((DiceSide::Side4, Direction::Up), ((DiceSide::Side6, Direction::Down), false)),
-            ((DiceSide::Side4, Direction::Down), ((DiceSide::Side1, Direction::Down), true)),
-            ((DiceSide::Side4, Direction::Left), ((DiceSide::Side5, Direction::Right), false)),
-            ((DiceSide::Side4, Direction::Right), ((DiceSide::Side2, Direction::Left), false)),
-            ((DiceSide::Side5, Direction::Up), ((DiceSide::Side6, Direction::Left), false)),
-            ((DiceSide::Side5, Direction::Down), ((DiceSide::Side1, Direction::Right), false)),
-            ((DiceSide::Side5, Direction::Left), ((DiceSide::Side3, Direction::Right), false)),
-            ((DiceSide::Side5, Direction::Right), ((DiceSide::Side4, Direction::Left), false)),
-            ((DiceSide::Side6, Direction::Up), ((DiceSide::Side3, Direction::Up), true)),
-            ((DiceSide::Side6, Direction::Down), ((DiceSide::Side4, Direction::Up), false)),
-            ((DiceSide::Side6, Direction::Left), ((DiceSide::Side5, Direction::Up), false)),
-            ((DiceSide::Side6, Direction::Right), ((DiceSide::Side2, Direction::Up), true)),
-        ]);
-
-        Self {
-            adjacent_edges,
-        }
+        // the axis along the direction turns into the opposite of the old normal
+        let (right, down) = match dir {
+            Direction::Left => (self.normal, self.down),
+            Direction::Right => (negate(self.normal), self.down),
+            Direction::Up => (self.right, self.normal),
+            Direction::Down => (self.right, negate(self.normal)),
+        };
+
+        Some(Face { corner, normal: self.vector(dir), right, down })
     }
 }
 
-pub struct InputInfo {
-    start_side: DiceSide,
-    start_dir: Direction,
-    // length of an edge of a dice's side
+/// Cube folded from the net of the map.
+struct DiceInfo {
+    /// length of an edge of a dice's side
     side_length: usize,
-    // cube's net of my input; maps side of a dice from an input net to coords (x,y) of upper-left corner
-    // and its rotation relative to original rotation in dice
-    dice_sides_info: HashMap<DiceSide, DiceSideInfo>,
+    faces: Vec<Face>,
+    /// (face, direction leaving it over an edge) -> (adjacent face, direction entering it)
+    adjacent_edges: HashMap<(usize, Direction), (usize, Direction)>,
 }
 
-impl InputInfo {
-    pub fn input22() -> Self {
-        let dice_sides_info = HashMap::from([
-            (DiceSide::Side1, ((50, 100), Direction::Up)),
-            (DiceSide::Side2, ((0, 100), Direction::Left)),
-            (DiceSide::Side3, ((50, 50), Direction::Up)),
-            (DiceSide::Side4, ((0, 150), Direction::Left)),
-            (DiceSide::Side5, ((100, 0), Direction::Left)),
-            (DiceSide::Side6, ((50, 0), Direction::Down)),
-        ]);
-
-        Self {
-            start_side: DiceSide::Side6,
-            start_dir: Direction::Left,
-            side_length: 50,
-            dice_sides_info,
+impl DiceInfo {
+    /// Folds any of the 11 cube nets, the side length is deduced from the number of tiles.
+    fn fold(map: &Map) -> Self {
+        let tiles = map.iter().flatten().filter(|&&tile| tile != -1).count();
+        let side_length = (tiles / 6).isqrt();
+        assert!(side_length > 0 && 6 * side_length * side_length == tiles, "map of {tiles} tiles is not a cube net");
+
+        let is_face = |corner: &Point| map.get(corner.y)
+            .and_then(|row| row.get(corner.x))
+            .is_some_and(|&tile| tile != -1);
+
+        let start = map[0].iter().position(|&tile| tile != -1).unwrap();
+        let mut faces = vec![Face { corner: Point { x: start, y: 0 }, normal: [0, 0, -1], right: [1, 0, 0], down: [0, 1, 0] }];
+        let mut queue = VecDeque::from([0]);
+
+        while let Some(index) = queue.pop_front() {
+            for dir in Direction::iter() {
+                if let Some(face) = faces[index].roll(dir, side_length) {
+                    if is_face(&face.corner) && faces.iter().all(|other| other.corner != face.corner) {
+                        queue.push_back(faces.len());
+                        faces.push(face);
+                    }
+                }
+            }
         }
-    }
 
-    pub fn input22_example() -> Self {
-        let dice_sides_info = HashMap::from([
-            (DiceSide::Side1, ((8, 4), Direction::Up)),
-            (DiceSide::Side2, ((4, 4), Direction::Left)),
-            (DiceSide::Side3, ((8, 0), Direction::Up)),
-            (DiceSide::Side4, ((8, 8), Direction::Down)),
-            (DiceSide::Side5, ((12, 8), Direction::Down)),
-            (DiceSide::Side6, ((0, 4), Direction::Up)),
-        ]);
-
-        Self {
-            start_side: DiceSide::Side3,
-            start_dir: Direction::Right,
-            side_length: 4,
-            dice_sides_info,
-        }
-    }
+        assert!(faces.len() == 6 && faces.iter().map(|face| face.normal).all_unique(), "map is not a cube net");
 
-    pub fn input22_example_1() -> Self {
-        let dice_sides_info = HashMap::from([
-            (DiceSide::Side1, ((2, 4), Direction::Up)),
-            (DiceSide::Side2, ((0, 4), Direction::Left)),
-            (DiceSide::Side3, ((2, 2), Direction::Up)),
-            (DiceSide::Side4, ((0, 6), Direction::Left)),
-            (DiceSide::Side5, ((4, 0), Direction::Left)),
-            (DiceSide::Side6, ((2, 0), Direction::Down)),
-        ]);
-
-        Self {
-            start_side: DiceSide::Side6,
-            start_dir: Direction::Left,
-            side_length: 2,
-            dice_sides_info,
+        let mut adjacent_edges = HashMap::new();
+        for (index, face) in faces.iter().enumerate() {
+            for dir in Direction::iter() {
+                let next = faces.iter().position(|other| other.normal == face.vector(dir)).unwrap();
+                let next_dir = Direction::iter().find(|&next_dir| faces[next].vector(next_dir) == negate(face.normal)).unwrap();
+                adjacent_edges.insert((index, dir), (next, next_dir));
+            }
         }
-    }
 
-    pub fn input22_example_2() -> Self {
-        let dice_sides_info = HashMap::from([
-            (DiceSide::Side1, ((4, 8), Direction::Up)),
-            (DiceSide::Side2, ((0, 8), Direction::Left)),
-            (DiceSide::Side3, ((4, 4), Direction::Up)),
-            (DiceSide::Side4, ((0, 12), Direction::Left)),
-            (DiceSide::Side5, ((8, 0), Direction::Left)),
-            (DiceSide::Side6, ((4, 0), Direction::Down)),
-        ]);
-
-        Self {
-            start_side: DiceSide::Side6,
-            start_dir: Direction::Left,
-            side_length: 4,
-            dice_sides_info,
-        }
+        Self { side_length, faces, adjacent_edges }
     }
 
-    pub fn input22_example_3() -> Self {
-        let dice_sides_info = HashMap::from([
-            (DiceSide::Side1, ((4, 8), Direction::Up)),
-            (DiceSide::Side2, ((0, 8), Direction::Left)),
-            (DiceSide::Side3, ((4, 4), Direction::Up)),
-            (DiceSide::Side4, ((0, 12), Direction::Left)),
-            (DiceSide::Side5, ((8, 0), Direction::Left)),
-            (DiceSide::Side6, ((4, 0), Direction::Down)),
-        ]);
-
-        Self {
-            start_side: DiceSide::Side6,
-            start_dir: Direction::Left,
-            side_length: 4,
-            dice_sides_info,
-        }
-    }
-}
+    /// Tile and direction after a step from given tile, wrapping around the cube at the edges of the faces.
+    fn step(&self, point: Point, dir: Direction) -> (Point, Direction) {
+        let n = self.side_length;
+        let (col, row) = (point.x % n, point.y % n);
 
-// rotates map till top of a side will point to up direction
-fn rotate(map: &Vec<Vec<Point>>, initial_rotation: &Direction) -> PointsMap {
-    match initial_rotation {
-        Direction::Up => map.clone(),
-        Direction::Left => rotate_once(map),
-        Direction::Down => rotate_once(&rotate_once(map)),
-        Direction::Right => rotate_once(&rotate_once(&rotate_once(map))),
-    }
-}
+        match dir {
+            Direction::Left if col > 0 => return (Point { x: point.x - 1, ..point }, dir),
+            Direction::Right if col < n - 1 => return (Point { x: point.x + 1, ..point }, dir),
+            Direction::Up if row > 0 => return (Point { y: point.y - 1, ..point }, dir),
+            Direction::Down if row < n - 1 => return (Point { y: point.y + 1, ..point }, dir),
+            _ => {}
+        }
 
-fn rotate_once<T>(v: &Vec<Vec<T>>) -> Vec<Vec<T>> where T: Clone {
-    assert!(!v.is_empty());
-    let n = v.len();
-    (0..n).map(|i| (0..n).map(|j| v[n - j - 1][i].clone()).collect::<Vec<_>>()).collect()
-}
+        let face = self.faces.iter()
+            .position(|face| face.corner == Point { x: point.x - col, y: point.y - row })
+            .unwrap();
+        let (next, next_dir) = self.adjacent_edges[&(face, dir)];
+
+        // position along the edge clockwise around the face, glued edges run clockwise in opposite ways
+        let offset = match dir {
+            Direction::Right => row,
+            Direction::Down => n - 1 - col,
+            Direction::Left => n - 1 - row,
+            Direction::Up => col,
+        };
+        let offset = n - 1 - offset;
+        let (col, row) = match next_dir.opposite() {
+            Direction::Right => (n - 1, offset),
+            Direction::Down => (n - 1 - offset, n - 1),
+            Direction::Left => (0, n - 1 - offset),
+            Direction::Up => (offset, 0),
+        };
 
-/// 1 2 3
-///
-/// 4 5 6
-///
-/// 7 8 9
-///
-/// gives:
-/// up    -> [1,2,3]
-/// right -> [3,6,9]
-/// down  -> [7,8,9]
-/// left  -> [1,4,7]
-fn get_edges(side_maps: &HashMap<DiceSide, PointsMap>) -> HashMap<EdgeIdentifier, Vec<Point>> {
-    let mut edges = HashMap::new();
-    for (dice_side, map) in side_maps {
-        // top edge
-        edges.insert((*dice_side, Direction::Up), map.first().unwrap().clone());
-
-        // bottom edge
-        edges.insert((*dice_side, Direction::Down), map.last().unwrap().clone());
-
-        // right edge
-        let right_edge = map.iter().map(|row| *row.last().unwrap()).collect::<Vec<_>>();
-        edges.insert((*dice_side, Direction::Right), right_edge);
-
-        // left edge
-        let left_edge = map.iter().map(|row| *row.first().unwrap()).collect::<Vec<_>>();
-        edges.insert((*dice_side, Direction::Left), left_edge);
+        let corner = self.faces[next].corner;
+        (Point { x: corner.x + col, y: corner.y + row }, next_dir)
     }
-
-    edges
 }
 
-fn get_point(
-    edges: &HashMap<EdgeIdentifier, Vec<Point>>,
-    side_dir: (DiceSide, Direction),
-    n: usize,
-    tile_index: usize,
-) -> Point {
-    let dice_info = DiceInfo::new();
-    let adjacent_edges = dice_info.adjacent_edges;
-
-    // let (edge_identifier, is_reversed) = &adjacent_edges[&(side, Direction::Left)];
-    let (edge_identifier, is_reversed) = &adjacent_edges[&side_dir];
-    let adjacent_edge = &edges[edge_identifier];
-    let index = if !is_reversed { tile_index } else { n - 1 - tile_index };
-
-    adjacent_edge[index]
+pub fn monkey_map_part_2(file_name: &str) -> usize {
+    monkey_map_part_2_from_str(&fs::read_to_string(file_name).unwrap())
 }
 
-fn find_neighbors(
-    input_info: &InputInfo,
-    sides_transformations: &HashMap<DiceSide, PointsMap>,
-    edges: &HashMap<EdgeIdentifier, Vec<Point>>,
-) -> HashMap<Point, HashMap<Direction, Point>> {
-    let mut neighbors = HashMap::new();
-
-    for (&side, side_map) in sides_transformations {
-        let n = input_info.side_length;
-        for row in 0..n {
-            for col in 0..n {
-                let curr_point = side_map[row][col];
-                let mut tiles_around = HashMap::new();
-
-                if col == 0 { // cannot go left
-                    let point = get_point(edges, (side, Direction::Left), n, row);
-                    tiles_around.insert(Direction::Left, point);
-                } else if col == n - 1 { // cannot go right
-                    let point = get_point(edges, (side, Direction::Right), n, row);
-                    tiles_around.insert(Direction::Right, point);
-                }
+pub fn monkey_map_part_2_from_str(input: &str) -> usize {
+    let (map, instructions) = parse_input(input);
+    let dice_info = DiceInfo::fold(&map);
 
-                if row == 0 { // we cannot go up:
-                    let point = get_point(edges, (side, Direction::Up), n, col);
-                    tiles_around.insert(Direction::Up, point);
-                } else if row == n - 1 { // we cannot go down:
-                    let point = get_point(edges, (side, Direction::Down), n, col);
-                    tiles_around.insert(Direction::Down, point);
-                }
+    let mut point = Point { x: map[0].iter().position(|&tile| tile == 0).unwrap(), y: 0 };
+    let mut dir = Direction::Right;
 
-                // fill ordinary neighbors
-                for dir in Direction::iter() {
-                    if tiles_around.contains_key(&dir) {
-                        continue;
+    for instruction in instructions.into_iter() {
+        match instruction {
+            MoveInstruction::TurnLeft => dir.turn_left(),
+            MoveInstruction::TurnRight => dir.turn_right(),
+            MoveInstruction::Go(amount) => {
+                for _ in 0..amount {
+                    let (next_point, next_dir) = dice_info.step(point, dir);
+                    if map[next_point.y][next_point.x] == 1 {
+                        break;
                     }
-
-                    let tile_point = match dir {
-                        Direction::Left => side_map[row][col - 1],
-                        Direction::Right => side_map[row][col + 1],
-                        Direction::Up => side_map[row - 1][col],
-                        Direction::Down => side_map[row + 1][col],
-                    };
-
-                    tiles_around.insert(dir, tile_point);
+                    (point, dir) = (next_point, next_dir);
                 }
-
-                neighbors.insert(curr_point, tiles_around);
             }
         }
     }
 
-    neighbors
+    1000 * (point.y + 1) + 4 * (point.x + 1) + dir.facing()
 }
 
-fn create_sides_maps(input_info: &InputInfo) -> HashMap<DiceSide, PointsMap> {
-    let n = input_info.side_length;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    input_info.dice_sides_info.iter()
-        .map(|(&dice_side, ((x_begin, y_begin), top_direction))| {
-            let points_map = (*y_begin..(*y_begin + n)).map(|y| {
-                (*x_begin..(*x_begin + n)).map(|x| Point { x, y }).collect::<Vec<_>>()
-            }).collect::<Vec<_>>();
+    #[test]
+    fn part_1_input_example() {
+        let answer = monkey_map_part_1("inputs/22_input_example.txt");
 
-            let normalized_side = rotate(&points_map, top_direction);
-            (dice_side, normalized_side)
-        }).collect::<HashMap<_, _>>()
-}
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 6032);
+    }
 
-pub fn monkey_map_part_2(file_name: &str, input_info: InputInfo) -> usize {
-    let (original_map, instructions) = read_input(file_name);
-    // print_map(&original_map);
-    // println!("{:?}", instructions);
-
-    let sides_maps = create_sides_maps(&input_info);
-    // print_rotations(&sides_maps);
-
-    let edges = get_edges(&sides_maps);
-    // println!("Edges");
-    // for (key, vec) in edges.iter().sorted() {
-    //     println!("{key:?} -> {vec:?}");
-    // }
-
-    let neighbors: HashMap<Point, HashMap<Direction, Point>> = find_neighbors(&input_info, &sides_maps, &edges);
-    // println!("Neighbors:");
-    // for (center, around) in neighbors.iter().sorted_by_key(|&(p, _)| p) {
-    //     let up = around[&Direction::Up];
-    //     let right = around[&Direction::Right];
-    //     let down = around[&Direction::Down];
-    //     let left = around[&Direction::Left];
-    //
-    //     println!("{center:?} -> up:{up:?} right:{right:?} down:{down:?} left:{left:?}")
-    // }
-
-    let mut start_x = 0;
-    while original_map[0][start_x] != 0 { start_x += 1; };
-
-    let mut curr_point = Point { x: start_x, y: 0 };
-    let mut curr_side = input_info.start_side;
-    let mut dir = input_info.start_dir;
-    // let mut walk_history = Vec::new();
-    // let mut walk_ins_counter = 0;
+    #[test]
+    fn part_1_input() {
+        let answer = monkey_map_part_1("inputs/22_input.txt");
 
-    for instruction in instructions.into_iter() {
-        match instruction {
-            MoveInstruction::TurnLeft => {
-                // walk_ins_counter -= 1;
-                dir.turn_left();
-            }
-            MoveInstruction::TurnRight => {
-                // walk_ins_counter -= 1;
-                dir.turn_right();
-            }
-            MoveInstruction::Go(amount) => {
-                // println!("\npos: {:?}", (curr_point, dir));
-
-                let mut counter = 0usize;
-                loop {
-                    // walk_ins_counter += 1;
-                    // match walk_history.iter().map(|(p, _)| p).position(|&p| p == curr_point) {
-                    //     Some(pos) => walk_history[pos] = (curr_point, walk_ins_counter),
-                    //     None => walk_history.push((curr_point, walk_ins_counter)),
-                    // }
-
-                    // when jumping to other side we need to adjust direction
-                    for (dice_side, side_map) in sides_maps.iter() {
-                        if side_map.iter().flatten().contains(&curr_point) && curr_side != *dice_side {
-                            let new_dir = match (curr_side, *dice_side) {
-                                (DiceSide::Side1, DiceSide::Side2) => Direction::Up,
-                                (DiceSide::Side1, DiceSide::Side3) => Direction::Up,
-                                (DiceSide::Side1, DiceSide::Side4) => Direction::Up,
-                                (DiceSide::Side1, DiceSide::Side5) => Direction::Up,
-
-                                (DiceSide::Side2, DiceSide::Side1) => Direction::Right,
-                                (DiceSide::Side2, DiceSide::Side4) => Direction::Left,
-                                (DiceSide::Side2, DiceSide::Side3) => Direction::Right,
-                                (DiceSide::Side2, DiceSide::Side6) => Direction::Left,
-
-                                (DiceSide::Side3, DiceSide::Side1) => Direction::Down,
-                                (DiceSide::Side3, DiceSide::Side2) => Direction::Left,
-                                (DiceSide::Side3, DiceSide::Side5) => Direction::Right,
-                                (DiceSide::Side3, DiceSide::Side6) => Direction::Down,
-
-                                (DiceSide::Side4, DiceSide::Side1) => Direction::Up,
-                                (DiceSide::Side4, DiceSide::Side2) => Direction::Right,
-                                (DiceSide::Side4, DiceSide::Side5) => Direction::Left,
-                                (DiceSide::Side4, DiceSide::Side6) => Direction::Up,
-
-                                (DiceSide::Side5, DiceSide::Side1) => Direction::Left,
-                                (DiceSide::Side5, DiceSide::Side3) => Direction::Left,
-                                (DiceSide::Side5, DiceSide::Side4) => Direction::Right,
-                                (DiceSide::Side5, DiceSide::Side6) => Direction::Right,
-
-                                (DiceSide::Side6, DiceSide::Side2) => Direction::Down,
-                                (DiceSide::Side6, DiceSide::Side3) => Direction::Down,
-                                (DiceSide::Side6, DiceSide::Side4) => Direction::Down,
-                                (DiceSide::Side6, DiceSide::Side5) => Direction::Down,
-                                _ => dir,
-                            };
-
-                            // println!("jumped for {curr_side:?} to {dice_side:?} and change dir from {dir:?} to {new_dir:?}");
-                            dir = new_dir;
-                            curr_side = *dice_side;
-                        }
-                    }
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 43466);
+    }
 
-                    if counter == amount {
-                        // println!("moved exactly {amount} of tiles");
-                        break;
-                    }
+    #[test]
+    fn part_2_input_example() {
+        let answer = monkey_map_part_2("inputs/22_input_example.txt");
 
-                    let next_point = neighbors[&curr_point][&dir];
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 5031);
+    }
 
-                    if original_map[next_point.y][next_point.x] == 1 {
-                        // println!("obstacle encountered on {:?}", (next_point.x, next_point.y));
-                        break;
-                    }
+    #[test]
+    fn part_2_input_example_1_to_3() {
+        let answers = [1, 2, 3].map(|i| monkey_map_part_2(&format!("inputs/22_input_example_{i}.txt")));
 
-                    curr_point = next_point;
-                    counter += 1;
-                }
-            }
-        }
+        println!("part 2 - examples 1-3 - answers: {:?}", answers);
+        assert_eq!(answers, [1016, 9017, 12007]);
+    }
+
+    #[test]
+    fn part_2_input() {
+        let answer = monkey_map_part_2("inputs/22_input.txt");
+
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 162155);
     }
 
-    // for (row_y, row) in original_map.iter().enumerate() {
-    //     for (tile_x, tile) in row.iter().enumerate() {
-    //         let p = Point { x: tile_x, y: row_y };
-    //
-    //         let sign = match walk_history.iter().find(|&(pp, _)| *pp == p) {
-    //             Some((_, index)) => format!("{:4}", index % 1000),
-    //             None => match tile {
-    //                 -1 => "    ".to_string(),
-    //                 0 => "   .".to_string(),
-    //                 1 => "   #".to_string(),
-    //                 _ => panic!()
-    //             }
-    //         };
-    //
-    //         print!("{sign}");
-    //     }
-    //     println!();
-    // }
-
-    println!("final point: {curr_point:?} with dir: {dir:?}");
-
-    let (_, original_dir) = input_info.dice_sides_info[&curr_side];
-    if original_dir != Direction::Up {
-        match original_dir {
-            Direction::Up => {}
-            Direction::Left => dir.turn_left(),
-            Direction::Right => dir.turn_right(),
-            Direction::Down => {
-                dir.turn_right();
-                dir.turn_right();
+    #[test]
+    fn all_cube_nets_fold() {
+        let nets = [
+            "#\n####\n#", "#\n####\n #", "#\n####\n  #", "#\n####\n   #", " #\n####\n #", " #\n####\n  #",
+            "##\n ###\n #", "##\n ###\n  #", "##\n ###\n   #",
+            "##\n ##\n  ##",
+            "###\n  ###",
+        ];
+
+        for net in nets {
+            let (map, _) = parse_input(&format!("{}\n\n1", net.replace('#', ".")));
+            let dice_info = DiceInfo::fold(&map);
+
+            assert_eq!(dice_info.side_length, 1);
+            for (&(face, dir), &(next, next_dir)) in dice_info.adjacent_edges.iter() {
+                assert_ne!(face, next, "net:\n{net}");
+                assert_eq!(dice_info.adjacent_edges[&(next, next_dir.opposite())], (face, dir.opposite()), "net:\n{net}");
             }
-        };
+        }
     }
 
-    1000 * (curr_point.y + 1) + 4 * (curr_point.x + 1) + match dir {
-        Direction::Right => 0,
-        Direction::Down => 1,
-        Direction::Left => 2,
-        Direction::Up => 3,
+    #[test]
+    #[should_panic(expected = "not a cube net")]
+    fn rectangle_does_not_fold() {
+        let (map, _) = parse_input("...\n...\n\n1");
+
+        DiceInfo::fold(&map);
     }
-}
 
-#[allow(dead_code)]
-fn print_rotations(side_rotations: &HashMap<DiceSide, PointsMap>) {
-    for (side, map) in side_rotations.iter().sorted_by_key(|&(s, _)| s) {
-        println!("dice side: {:?}", side);
-        for row in map {
-            for point in row {
-                print!("{:?}", point);
+    #[test]
+    fn walking_around_the_cube_returns() {
+        let (map, _) = parse_input(&fs::read_to_string("inputs/22_input_example.txt").unwrap().replace('#', "."));
+        let dice_info = DiceInfo::fold(&map);
+        let start = Point { x: 8, y: 1 };
+
+        for dir in Direction::iter() {
+            let (mut point, mut walk_dir) = (start, dir);
+            for _ in 0..4 * dice_info.side_length {
+                (point, walk_dir) = dice_info.step(point, walk_dir);
             }
-            println!();
+
+            assert_eq!((point, walk_dir), (start, dir));
         }
     }
 }
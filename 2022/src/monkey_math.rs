@@ -1,11 +1,13 @@
-use std::collections::{HashMap, VecDeque};
-use std::fmt::{Debug, Formatter};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::fs;
+use std::io::BufRead;
 use std::str::FromStr;
 
+use num::{BigInt, BigRational, One, ToPrimitive, Zero};
+
 #[derive(Copy, Clone, PartialEq)]
-enum OperationKind {
+pub enum OperationKind {
     Addition,
     Subtraction,
     Multiplication,
@@ -45,26 +47,8 @@ enum Yell {
     Function(String, OperationKind, String),
 }
 
-#[derive(Clone, PartialEq)]
-enum PostFixElem {
-    Variable(String),
-    Number(i64),
-    Operation(OperationKind),
-}
-
-impl Debug for PostFixElem {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            PostFixElem::Variable(name) => write!(f, "{name}"),
-            PostFixElem::Number(num) => if *num != i64::MAX { write!(f, "{num}") } else { write!(f, "ME") },
-            PostFixElem::Operation(op) => write!(f, "{op:?}"),
-        }
-    }
-}
-
-fn read_input(file_name: &str) -> HashMap<String, Yell> {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
+fn parse_input(input: &str) -> HashMap<String, Yell> {
+    let mut reader = input.as_bytes();
     let mut buf = String::new();
 
     let mut instructions: HashMap<String, Yell> = HashMap::new();
@@ -96,196 +80,353 @@ fn read_input(file_name: &str) -> HashMap<String, Yell> {
     instructions
 }
 
-fn initialize_postfix_notation(input: &HashMap<String, Yell>, postfix_notation: &mut Vec<PostFixElem>, root: &String) {
-    match &input[root] {
-        Yell::Number(_) => {}
-        Yell::Function(name1, op, name2) => {
-            postfix_notation.push(PostFixElem::Variable(name1.to_string()));
-            postfix_notation.push(PostFixElem::Variable(name2.to_string()));
-            postfix_notation.push(PostFixElem::Operation(*op));
+
+const ROOT: &str = "root";
+const ME: &str = "humn";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Constant(BigRational),
+    Variable(String),
+    Operation(Box<Expr>, OperationKind, Box<Expr>),
+}
+
+impl OperationKind {
+    fn precedence(self) -> u8 {
+        match self {
+            OperationKind::Addition | OperationKind::Subtraction => 1,
+            OperationKind::Multiplication | OperationKind::Division => 2,
         }
     }
+
+    fn apply(self, lhs: &BigRational, rhs: &BigRational) -> Result<BigRational, SolveError> {
+        Ok(match self {
+            OperationKind::Addition => lhs + rhs,
+            OperationKind::Subtraction => lhs - rhs,
+            OperationKind::Multiplication => lhs * rhs,
+            OperationKind::Division if rhs.is_zero() => return Err(SolveError::DivisionByZero),
+            OperationKind::Division => lhs / rhs,
+        })
+    }
 }
 
-fn expand_variables_in_notation(input: &mut HashMap<String, Yell>, notation: &mut Vec<PostFixElem>) {
-    'outer: loop {
-        for (index, elem) in notation.clone().iter().enumerate() {
-            match elem {
-                PostFixElem::Variable(name) => {
-                    notation.remove(index);
-
-                    match &input[name] {
-                        Yell::Number(val) => notation.insert(index, PostFixElem::Number(*val)),
-                        Yell::Function(name1, op, name2) => {
-                            notation.insert(index, PostFixElem::Variable(name1.to_string()));
-                            notation.insert(index + 1, PostFixElem::Variable(name2.to_string()));
-                            notation.insert(index + 2, PostFixElem::Operation(*op));
+impl Expr {
+    pub fn constant(value: i64) -> Expr {
+        Expr::Constant(BigRational::from_integer(BigInt::from(value)))
+    }
+
+    pub fn variable(name: &str) -> Expr {
+        Expr::Variable(name.to_string())
+    }
+
+    pub fn operation(lhs: Expr, op: OperationKind, rhs: Expr) -> Expr {
+        Expr::Operation(Box::new(lhs), op, Box::new(rhs))
+    }
+
+    /// Expression yelled by given monkey, where the monkeys named in `variables` stay unknown.
+    fn from_monkeys(monkeys: &HashMap<String, Yell>, name: &str, variables: &[&str]) -> Expr {
+        if variables.contains(&name) {
+            return Expr::variable(name);
+        }
+
+        match &monkeys[name] {
+            Yell::Number(value) => Expr::constant(*value),
+            Yell::Function(name_1, op, name_2) => Expr::operation(
+                Expr::from_monkeys(monkeys, name_1, variables),
+                *op,
+                Expr::from_monkeys(monkeys, name_2, variables),
+            ),
+        }
+    }
+
+    /// Folds constant subexpressions and drops neutral elements.
+    pub fn simplify(&self) -> Result<Expr, SolveError> {
+        let Expr::Operation(lhs, op, rhs) = self else {
+            return Ok(self.clone());
+        };
+
+        let (lhs, rhs) = (lhs.simplify()?, rhs.simplify()?);
+        let is = |expr: &Expr, value: i64| *expr == Expr::constant(value);
+
+        Ok(match (lhs, op, rhs) {
+            (Expr::Constant(lhs), op, Expr::Constant(rhs)) => Expr::Constant(op.apply(&lhs, &rhs)?),
+            (_, OperationKind::Division, rhs) if is(&rhs, 0) => return Err(SolveError::DivisionByZero),
+            (lhs, OperationKind::Addition, rhs) if is(&lhs, 0) => rhs,
+            (lhs, OperationKind::Addition | OperationKind::Subtraction, rhs) if is(&rhs, 0) => lhs,
+            (lhs, OperationKind::Multiplication, rhs) if is(&lhs, 1) => rhs,
+            (lhs, OperationKind::Multiplication | OperationKind::Division, rhs) if is(&rhs, 1) => lhs,
+            (lhs, op, rhs) => Expr::operation(lhs, *op, rhs),
+        })
+    }
+
+    /// Value as `coefficient * variable + constant`, if the expression is linear in the variable.
+    fn linear(&self, variable: &str) -> Result<Linear, SolveError> {
+        match self {
+            Expr::Constant(value) => Ok(Linear { coefficient: BigRational::zero(), constant: value.clone() }),
+            Expr::Variable(name) if name == variable =>
+                Ok(Linear { coefficient: BigRational::one(), constant: BigRational::zero() }),
+            Expr::Variable(name) => Err(SolveError::Underdetermined(format!("{name} is unknown as well"))),
+            Expr::Operation(lhs, op, rhs) => {
+                let (lhs, rhs) = (lhs.linear(variable)?, rhs.linear(variable)?);
+
+                match op {
+                    OperationKind::Addition => Ok(Linear {
+                        coefficient: lhs.coefficient + rhs.coefficient,
+                        constant: lhs.constant + rhs.constant,
+                    }),
+                    OperationKind::Subtraction => Ok(Linear {
+                        coefficient: lhs.coefficient - rhs.coefficient,
+                        constant: lhs.constant - rhs.constant,
+                    }),
+                    OperationKind::Multiplication if lhs.coefficient.is_zero() => Ok(rhs.scale(&lhs.constant)),
+                    OperationKind::Multiplication if rhs.coefficient.is_zero() => Ok(lhs.scale(&rhs.constant)),
+                    OperationKind::Division if rhs.coefficient.is_zero() => {
+                        if rhs.constant.is_zero() {
+                            return Err(SolveError::DivisionByZero);
                         }
+                        Ok(lhs.scale(&rhs.constant.recip()))
                     }
-                    continue 'outer;
+                    _ => Err(SolveError::NonLinear(self.to_string())),
                 }
-                _ => continue,
             }
         }
+    }
 
-        break;
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Operation(_, op, _) => op.precedence(),
+            _ => u8::MAX,
+        }
     }
 }
 
-fn calculate_postfix(postfix_notation: &[PostFixElem]) -> i64 {
-    let mut stack: Vec<i64> = Vec::new();
-
-    for elem in postfix_notation {
-        match elem {
-            PostFixElem::Number(val) => stack.push(*val),
-            PostFixElem::Operation(op) => {
-                let num1 = stack.pop().unwrap();
-                let num2 = stack.pop().unwrap();
-
-                stack.push(
-                    match op {
-                        OperationKind::Addition => num2 + num1,
-                        OperationKind::Subtraction => num2 - num1,
-                        OperationKind::Multiplication => num2 * num1,
-                        OperationKind::Division => num2 / num1,
-                    }
-                )
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Constant(value) if value.is_integer() => write!(f, "{value}"),
+            Expr::Constant(value) => write!(f, "({value})"),
+            Expr::Variable(name) => write!(f, "{name}"),
+            Expr::Operation(lhs, op, rhs) => {
+                // right operand of - and / keeps its parentheses even at the same precedence
+                let lhs_parenthesized = lhs.precedence() < op.precedence();
+                let rhs_parenthesized = rhs.precedence() < op.precedence()
+                    || (rhs.precedence() == op.precedence() && matches!(op, OperationKind::Subtraction | OperationKind::Division));
+
+                if lhs_parenthesized { write!(f, "({lhs})")? } else { write!(f, "{lhs}")? }
+                write!(f, " {op:?} ")?;
+                if rhs_parenthesized { write!(f, "({rhs})") } else { write!(f, "{rhs}") }
             }
-            PostFixElem::Variable(_) => panic!("there should be no variables at this point"),
         }
     }
-    stack.pop().unwrap()
 }
 
-fn manipulate_postfix_notation(input_notation: &Vec<PostFixElem>, desired_result: i64) -> Vec<PostFixElem> {
-    let mut new_postfix_notation: VecDeque<PostFixElem> = VecDeque::from([PostFixElem::Number(desired_result)]);
-    let mut notation_clone: VecDeque<PostFixElem> = VecDeque::from(input_notation.clone());
-
-    // pop last element, which always should be of type 'Operator'
-    while let PostFixElem::Operation(op) = notation_clone.pop_back().unwrap() {
-
-        // find the components of the operator
-        let mut nesting_counter = 1_usize;
-        let mut index = notation_clone.len();
-        while nesting_counter != 0 {
-            let elem = &notation_clone[index - 1];
-            match elem {
-                PostFixElem::Number(_) => nesting_counter -= 1,
-                PostFixElem::Operation(_) => nesting_counter += 1,
-                PostFixElem::Variable(_) => {}
-            }
-            index -= 1;
-        }
+#[derive(Debug)]
+struct Linear {
+    coefficient: BigRational,
+    constant: BigRational,
+}
 
-        // determine which component contains 'me'
-        let mut left_contains_me = false;
-        for elem in notation_clone.iter().take(index) {
-            if let PostFixElem::Number(num) = elem {
-                if *num == i64::MAX {
-                    left_contains_me = true;
-                    break;
-                }
-            }
-        }
+impl Linear {
+    fn scale(self, factor: &BigRational) -> Linear {
+        Linear { coefficient: self.coefficient * factor, constant: self.constant * factor }
+    }
+}
 
-        // split elements into two components
-        let right_component = notation_clone.split_off(index);
-        let left_component = notation_clone.clone();
+#[derive(Debug, PartialEq)]
+pub enum SolveError {
+    /// the variable is multiplied by itself, or something divides by it
+    NonLinear(String),
+    /// the equation holds for more than one value
+    Underdetermined(String),
+    /// the equation holds for no value
+    NoSolution,
+    DivisionByZero,
+}
 
-        // point to variable with elements to move, and assign the remaining elements back to 'loop variable'
-        let elements_to_move = if left_contains_me {
-            notation_clone = left_component;
-            right_component
-        } else {
-            notation_clone = right_component;
-            left_component
-        };
+#[derive(Clone, Debug, PartialEq)]
+pub struct Equation {
+    pub lhs: Expr,
+    pub rhs: Expr,
+}
 
-        // move elements to new postfix notation and determine new operator based on component moved
-        let new_op = match op {
-            OperationKind::Addition => {
-                for elem in elements_to_move.into_iter() {
-                    new_postfix_notation.push_back(elem);
-                }
+impl Display for Equation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.lhs, self.rhs)
+    }
+}
 
-                OperationKind::Subtraction
-            }
-            OperationKind::Multiplication => {
-                for elem in elements_to_move.into_iter() {
-                    new_postfix_notation.push_back(elem);
-                }
+impl Equation {
+    pub fn simplify(&self) -> Result<Equation, SolveError> {
+        Ok(Equation { lhs: self.lhs.simplify()?, rhs: self.rhs.simplify()? })
+    }
 
-                OperationKind::Division
-            }
-            OperationKind::Subtraction => {
-                for elem in elements_to_move.into_iter().rev() {
-                    new_postfix_notation.push_front(elem);
-                }
+    /// Solves the equation for given variable, which may appear on both sides.
+    pub fn solve_for(&self, variable: &str) -> Result<BigRational, SolveError> {
+        let lhs = self.lhs.linear(variable)?;
+        let rhs = self.rhs.linear(variable)?;
 
-                if left_contains_me { OperationKind::Addition } else { OperationKind::Subtraction }
-            }
-            OperationKind::Division => {
-                for elem in elements_to_move.into_iter().rev() {
-                    new_postfix_notation.push_front(elem);
-                }
+        // (a1 - a2) * x = b2 - b1
+        let coefficient = lhs.coefficient - rhs.coefficient;
+        let constant = rhs.constant - lhs.constant;
 
-                if left_contains_me { OperationKind::Multiplication } else { OperationKind::Division }
-            }
-        };
+        match (coefficient.is_zero(), constant.is_zero()) {
+            (false, _) => Ok(constant / coefficient),
+            (true, true) => Err(SolveError::Underdetermined(format!("{self} holds for any {variable}"))),
+            (true, false) => Err(SolveError::NoSolution),
+        }
+    }
 
-        new_postfix_notation.push_back(PostFixElem::Operation(new_op))
+    /// Equation in form `variable = value`.
+    pub fn isolate(&self, variable: &str) -> Result<Equation, SolveError> {
+        Ok(Equation { lhs: Expr::variable(variable), rhs: Expr::Constant(self.solve_for(variable)?) })
     }
+}
 
-    new_postfix_notation.into()
+/// Equation of the root monkey's two operands, where the monkeys named in `variables` stay unknown.
+pub fn monkey_equation(input: &str, variables: &[&str]) -> Equation {
+    let monkeys = parse_input(input);
+
+    match &monkeys[ROOT] {
+        Yell::Number(value) => Equation { lhs: Expr::variable(ROOT), rhs: Expr::constant(*value) },
+        Yell::Function(name_1, _op, name_2) => Equation {
+            lhs: Expr::from_monkeys(&monkeys, name_1, variables),
+            rhs: Expr::from_monkeys(&monkeys, name_2, variables),
+        },
+    }
+}
+
+fn to_i64(value: &BigRational) -> i64 {
+    assert!(value.is_integer(), "{value} is not a whole number");
+    value.to_integer().to_i64().unwrap()
 }
 
 pub fn monkey_math_part_1(file_name: &str) -> i64 {
-    let mut input = read_input(file_name);
+    monkey_math_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn monkey_math_part_1_from_str(input: &str) -> i64 {
+    let monkeys = parse_input(input);
 
-    let mut postfix_notation: Vec<PostFixElem> = Vec::new();
+    match Expr::from_monkeys(&monkeys, ROOT, &[]).simplify().unwrap() {
+        Expr::Constant(value) => to_i64(&value),
+        expr => panic!("{expr} should have no unknowns"),
+    }
+}
 
-    initialize_postfix_notation(&input, &mut postfix_notation, &"root".to_string());
+pub fn monkey_math_part_2(file_name: &str) -> i64 {
+    monkey_math_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-    expand_variables_in_notation(&mut input, &mut postfix_notation);
+pub fn monkey_math_part_2_from_str(input: &str) -> i64 {
+    let solution = monkey_equation(input, &[ME]).simplify().unwrap().isolate(ME).unwrap();
 
-    calculate_postfix(&postfix_notation)
+    match solution.rhs {
+        Expr::Constant(value) => to_i64(&value),
+        _ => unreachable!(),
+    }
 }
 
-pub fn monkey_math_part_2(file_name: &str) -> i64 {
-    let mut input = read_input(file_name);
-    let mut lhs_postfix_notation: Vec<PostFixElem> = Vec::new();
-    let mut rhs_postfix_notation: Vec<PostFixElem> = Vec::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    input.entry("humn".to_string()).and_modify(|yell| *yell = Yell::Number(i64::MAX));
+    fn x() -> Expr {
+        Expr::variable("x")
+    }
 
-    let root = &input["root"];
-    let (lhs, rhs) = match root {
-        Yell::Number(val) => return *val,
-        Yell::Function(name1, _op, name2) => (name1, name2),
-    };
+    fn c(value: i64) -> Expr {
+        Expr::constant(value)
+    }
 
-    initialize_postfix_notation(&input, &mut lhs_postfix_notation, lhs);
-    initialize_postfix_notation(&input, &mut rhs_postfix_notation, rhs);
+    #[test]
+    fn part_1_input_example() {
+        let answer = monkey_math_part_1("inputs/21_input_example.txt");
 
-    let mut notations = [lhs_postfix_notation, rhs_postfix_notation];
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 152);
+    }
 
-    for notation in notations.iter_mut() {
-        expand_variables_in_notation(&mut input, notation);
+    #[test]
+    fn part_1_input() {
+        let answer = monkey_math_part_1("inputs/21_input.txt");
+
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 159591692827554);
     }
 
-    let left_contains_me = notations[0].iter()
-        .any(|elem| if let PostFixElem::Number(num) = elem { *num == i64::MAX } else { false });
+    #[test]
+    fn part_2_input_example() {
+        let answer = monkey_math_part_2("inputs/21_input_example.txt");
 
-    let (with_me, without_me) = if left_contains_me {
-        (&notations[0], &notations[1])
-    } else {
-        (&notations[1], &notations[0])
-    };
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 301);
+    }
+
+    #[test]
+    fn part_2_input() {
+        let answer = monkey_math_part_2("inputs/21_input.txt");
+
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 3509819803065);
+    }
 
-    let result = calculate_postfix(without_me);
+    #[test]
+    fn equation_input_example() {
+        let input = fs::read_to_string("inputs/21_input_example.txt").unwrap();
+        let equation = monkey_equation(&input, &[ME]).simplify().unwrap();
 
-    // manipulate notation to calculate my number
-    let new_notation = manipulate_postfix_notation(with_me, result);
+        assert_eq!(equation.to_string(), "(4 + 2 * (humn - 3)) / 4 = 150");
+        assert_eq!(equation.isolate(ME).unwrap().to_string(), "humn = 301");
+    }
+
+    #[test]
+    fn solve_variable_on_both_sides() {
+        // x + 3 = 2 * x - 1
+        let equation = Equation {
+            lhs: Expr::operation(x(), OperationKind::Addition, c(3)),
+            rhs: Expr::operation(Expr::operation(c(2), OperationKind::Multiplication, x()), OperationKind::Subtraction, c(1)),
+        };
+
+        assert_eq!(equation.solve_for("x"), Ok(BigRational::from_integer(4.into())));
+    }
+
+    #[test]
+    fn solve_fraction() {
+        // x / 3 * 2 = 1
+        let equation = Equation {
+            lhs: Expr::operation(Expr::operation(x(), OperationKind::Division, c(3)), OperationKind::Multiplication, c(2)),
+            rhs: c(1),
+        };
 
-    calculate_postfix(&new_notation)
+        assert_eq!(equation.isolate("x").unwrap().to_string(), "x = (3/2)");
+    }
+
+    #[test]
+    fn solve_reports_unsolvable_equations() {
+        let square = Equation { lhs: Expr::operation(x(), OperationKind::Multiplication, x()), rhs: c(4) };
+        let reciprocal = Equation { lhs: Expr::operation(c(1), OperationKind::Division, x()), rhs: c(4) };
+        let two_unknowns = Equation { lhs: Expr::operation(x(), OperationKind::Addition, Expr::variable("y")), rhs: c(4) };
+        let identity = Equation { lhs: Expr::operation(x(), OperationKind::Addition, x()), rhs: Expr::operation(c(2), OperationKind::Multiplication, x()) };
+        let contradiction = Equation { lhs: Expr::operation(x(), OperationKind::Subtraction, x()), rhs: c(1) };
+        let division_by_zero = Equation { lhs: Expr::operation(x(), OperationKind::Division, Expr::operation(c(2), OperationKind::Subtraction, c(2))), rhs: c(1) };
+
+        assert_eq!(square.solve_for("x"), Err(SolveError::NonLinear("x * x".to_string())));
+        assert_eq!(reciprocal.solve_for("x"), Err(SolveError::NonLinear("1 / x".to_string())));
+        assert!(matches!(two_unknowns.solve_for("x"), Err(SolveError::Underdetermined(_))));
+        assert!(matches!(identity.solve_for("x"), Err(SolveError::Underdetermined(_))));
+        assert_eq!(contradiction.solve_for("x"), Err(SolveError::NoSolution));
+        assert_eq!(division_by_zero.solve_for("x"), Err(SolveError::DivisionByZero));
+        assert_eq!(division_by_zero.simplify(), Err(SolveError::DivisionByZero));
+    }
+
+    #[test]
+    fn display_keeps_needed_parentheses() {
+        let expr = Expr::operation(
+            Expr::operation(x(), OperationKind::Subtraction, Expr::operation(c(1), OperationKind::Subtraction, c(2))),
+            OperationKind::Division,
+            Expr::operation(c(3), OperationKind::Multiplication, x()),
+        );
+
+        assert_eq!(expr.to_string(), "(x - (1 - 2)) / (3 * x)");
+    }
 }
@@ -1,7 +1,7 @@
-use std::cell::RefCell;
-use std::fs::File;
+use std::fmt::{Display, Formatter, Write};
+use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
-use std::rc::Rc;
+use std::ops::RangeBounds;
 
 #[derive(Debug)]
 pub enum CommandType {
@@ -11,7 +11,7 @@ pub enum CommandType {
 
 #[derive(Debug)]
 pub enum ItemType {
-    File { size: i32 },
+    File { name: String, size: u64 },
     Folder { name: String },
 }
 
@@ -21,12 +21,53 @@ pub enum IteratorItem {
     Info { item_type: ItemType },
 }
 
-pub struct CommandLineIterator {
-    buf_reader: BufReader<File>,
+#[derive(Debug, PartialEq)]
+pub enum InconsistencyKind {
+    MalformedLine(String),
+    UnknownCommand(String),
+    OutputWithoutLs,
+    UnknownDir(String),
+    NotADir(String),
+    CdAboveRoot,
+    ConflictingSize { name: String, size: u64, listed_size: u64 },
+    ConflictingType(String),
 }
 
-impl Iterator for CommandLineIterator {
-    type Item = IteratorItem;
+impl Display for InconsistencyKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InconsistencyKind::MalformedLine(line) => write!(f, "malformed line '{line}'"),
+            InconsistencyKind::UnknownCommand(command) => write!(f, "unknown command '{command}'"),
+            InconsistencyKind::OutputWithoutLs => write!(f, "output without preceding ls"),
+            InconsistencyKind::UnknownDir(name) => write!(f, "cd into unknown dir '{name}'"),
+            InconsistencyKind::NotADir(name) => write!(f, "cd into file '{name}'"),
+            InconsistencyKind::CdAboveRoot => write!(f, "cd .. at root"),
+            InconsistencyKind::ConflictingSize { name, size, listed_size } =>
+                write!(f, "file '{name}' listed with size {listed_size}, but it was {size} before"),
+            InconsistencyKind::ConflictingType(name) => write!(f, "'{name}' listed both as file and dir"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Inconsistency {
+    /// 1-based line of the transcript
+    pub line: usize,
+    pub kind: InconsistencyKind,
+}
+
+impl Display for Inconsistency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+pub struct CommandLineIterator<R = BufReader<File>> {
+    buf_reader: R,
+}
+
+impl<R: BufRead> Iterator for CommandLineIterator<R> {
+    type Item = Result<IteratorItem, InconsistencyKind>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let mut buf = String::new();
@@ -34,10 +75,9 @@ impl Iterator for CommandLineIterator {
             Ok(0) => None,
             Ok(_n) => {
                 let buf = buf.trim_end();
-                if buf.starts_with('$') {
-                    Some(parse_command(&buf[2..]))
-                } else {
-                    Some(parse_info(buf))
+                match buf.strip_prefix("$ ") {
+                    Some(command) => Some(parse_command(command)),
+                    None => Some(parse_info(buf)),
                 }
             }
             Err(_e) => panic!(),
@@ -47,240 +87,478 @@ impl Iterator for CommandLineIterator {
 
 pub fn read_input(file_name: &str) -> CommandLineIterator {
     let file = File::open(file_name).unwrap();
-    let reader = BufReader::new(file);
 
     CommandLineIterator {
-        buf_reader: reader,
+        buf_reader: BufReader::new(file),
     }
 }
 
-fn parse_info(line: &str) -> IteratorItem {
-    let result = line.split(' ').collect::<Vec<_>>();
+pub fn read_input_from_str(input: &str) -> CommandLineIterator<&[u8]> {
+    let reader = input.as_bytes();
 
-    match result[0].parse::<i32>() {
-        Ok(size) => IteratorItem::Info { item_type: ItemType::File { size } },
-        Err(_) => IteratorItem::Info { item_type: ItemType::Folder { name: String::from(result[1]) } },
+    CommandLineIterator {
+        buf_reader: reader,
     }
 }
 
-fn parse_command(line: &str) -> IteratorItem {
-    let result = line.split(' ').collect::<Vec<_>>();
-    let command_type: CommandType;
-    let param: String;
+fn parse_info(line: &str) -> Result<IteratorItem, InconsistencyKind> {
+    let item_type = match line.split(' ').collect::<Vec<_>>()[..] {
+        ["dir", name] => ItemType::Folder { name: String::from(name) },
+        [size, name] => match size.parse::<u64>() {
+            Ok(size) => ItemType::File { name: String::from(name), size },
+            Err(_) => return Err(InconsistencyKind::MalformedLine(line.to_string())),
+        },
+        _ => return Err(InconsistencyKind::MalformedLine(line.to_string())),
+    };
+
+    Ok(IteratorItem::Info { item_type })
+}
+
+fn parse_command(line: &str) -> Result<IteratorItem, InconsistencyKind> {
+    let (command_type, param) = match line.split(' ').collect::<Vec<_>>()[..] {
+        ["ls"] => (CommandType::Ls, ""),
+        ["cd", param] => (CommandType::Cd, param),
+        _ => return Err(InconsistencyKind::UnknownCommand(line.to_string())),
+    };
 
-    if result[0] == "ls" {
-        command_type = CommandType::Ls;
-        param = "".to_string();
-    } else {
-        command_type = CommandType::Cd;
-        param = result[1].to_string();
-    }
+    Ok(IteratorItem::Command { command_type, param: param.to_string() })
+}
+
+const DISK_SIZE: u64 = 70_000_000;
+const REQUIRED_SPACE: u64 = 30_000_000;
 
-    IteratorItem::Command { command_type, param }
+pub type NodeId = usize;
+
+#[derive(Debug)]
+enum NodeKind {
+    File,
+    Dir { children: Vec<NodeId> },
 }
 
 #[derive(Debug)]
-pub struct TreeNode {
-    value: ItemType,
-    children: Vec<Rc<RefCell<TreeNode>>>,
-    parent: Option<Rc<RefCell<TreeNode>>>,
+struct Node {
+    name: String,
+    parent: Option<NodeId>,
+    /// size of a file, or total size of everything inside a dir
+    size: u64,
+    kind: NodeKind,
 }
 
-impl TreeNode {
-    pub fn new() -> TreeNode {
-        TreeNode {
-            value: ItemType::Folder { name: r"\".to_string() },
-            children: vec![],
-            parent: None,
-        }
-    }
+/// Virtual filesystem keeping its nodes in an arena, referenced by their index.
+/// Dir sizes are updated whenever a file is added, so they never have to be recomputed.
+#[derive(Debug)]
+pub struct FileSystem {
+    nodes: Vec<Node>,
+}
 
-    pub fn add_child(&mut self, new_node: Rc<RefCell<TreeNode>>) {
-        self.children.push(new_node);
+impl Default for FileSystem {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-pub fn print(node: Rc<RefCell<TreeNode>>) {
-    match &node.borrow().value {
-        ItemType::File { size } => println!("file (size={size})"),
-        ItemType::Folder { name } => {
-            println!(">{name}");
-            print_children(Rc::clone(&node), 2);
+impl FileSystem {
+    pub fn new() -> FileSystem {
+        FileSystem {
+            nodes: vec![Node { name: "/".to_string(), parent: None, size: 0, kind: NodeKind::Dir { children: vec![] } }],
         }
     }
-}
 
-pub fn print_children(node: Rc<RefCell<TreeNode>>, indent: u32) {
-    for child in RefCell::borrow(&node).children.iter() {
-        let item_type: &ItemType = &RefCell::borrow(child).value;
-        let ind = " ".repeat(indent as usize);
+    pub fn root(&self) -> NodeId {
+        0
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.nodes[id].name
+    }
+
+    pub fn size(&self, id: NodeId) -> u64 {
+        self.nodes[id].size
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id].parent
+    }
 
-        if let ItemType::Folder { name } = &item_type {
-            let size = calculate_item_size(Rc::clone(child));
-            println!("{ind}>{name}  (size: {size})");
+    pub fn is_dir(&self, id: NodeId) -> bool {
+        matches!(self.nodes[id].kind, NodeKind::Dir { .. })
+    }
 
-            print_children(Rc::clone(child), indent + 2);
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        match &self.nodes[id].kind {
+            NodeKind::File => &[],
+            NodeKind::Dir { children } => children,
         }
     }
 
-    for child in RefCell::borrow(&node).children.iter() {
-        let item_type: &ItemType = &RefCell::borrow(child).value;
-        let ind = " ".repeat(indent as usize);
+    pub fn child(&self, dir: NodeId, name: &str) -> Option<NodeId> {
+        self.children(dir).iter().copied().find(|&child| self.nodes[child].name == name)
+    }
 
-        if let ItemType::File { size } = &item_type {
-            println!("{ind}file (size={size})");
+    /// Absolute path, e.g. `/a/e`.
+    pub fn path(&self, id: NodeId) -> String {
+        match self.parent(id) {
+            None => "/".to_string(),
+            Some(parent) if parent == self.root() => format!("/{}", self.name(id)),
+            Some(parent) => format!("{}/{}", self.path(parent), self.name(id)),
+        }
+    }
 
-            print_children(Rc::clone(child), indent + 2);
+    /// Adds a dir, or returns the one already having given name.
+    pub fn add_dir(&mut self, parent: NodeId, name: &str) -> NodeId {
+        if let Some(existing) = self.child(parent, name) {
+            return existing;
         }
+
+        self.add_node(parent, Node { name: name.to_string(), parent: Some(parent), size: 0, kind: NodeKind::Dir { children: vec![] } })
     }
-}
 
-fn find_child_by_name(root: Rc<RefCell<TreeNode>>, folder_name: &str) -> Option<Rc<RefCell<TreeNode>>> {
-    for node in RefCell::borrow(&root).children.iter() {
-        let item_type: &ItemType = &RefCell::borrow(node).value;
+    /// Adds a file and adds its size to all dirs above it.
+    pub fn add_file(&mut self, parent: NodeId, name: &str, size: u64) -> NodeId {
+        let id = self.add_node(parent, Node { name: name.to_string(), parent: Some(parent), size, kind: NodeKind::File });
 
-        match item_type {
-            ItemType::File { .. } => continue,
-            ItemType::Folder { name } => {
-                if *name == folder_name {
-                    return Some(Rc::clone(node));
-                }
-                continue;
-            }
+        let mut ancestor = Some(parent);
+        while let Some(dir) = ancestor {
+            self.nodes[dir].size += size;
+            ancestor = self.nodes[dir].parent;
         }
+
+        id
     }
 
-    None
-}
+    fn add_node(&mut self, parent: NodeId, node: Node) -> NodeId {
+        let id = self.nodes.len();
+        self.nodes.push(node);
+
+        match &mut self.nodes[parent].kind {
+            NodeKind::Dir { children } => children.push(id),
+            NodeKind::File => panic!("{} is not a dir", self.path(parent)),
+        }
 
-fn calculate_item_size(node: Rc<RefCell<TreeNode>>) -> i32 {
-    let mut sum = 0;
+        id
+    }
 
-    for node in RefCell::borrow(&node).children.iter() {
-        let item_type: &ItemType = &RefCell::borrow(node).value;
+    pub fn dirs(&self) -> impl Iterator<Item = NodeId> + '_ {
+        (0..self.nodes.len()).filter(|&id| self.is_dir(id))
+    }
 
-        match item_type {
-            ItemType::File { size } => {
-                sum += size;
-                continue;
-            }
-            ItemType::Folder { .. } => {
-                sum += calculate_item_size(Rc::clone(node));
-            }
+    /// Path and total size of every dir, like `du`.
+    pub fn du(&self) -> Vec<(String, u64)> {
+        self.dirs().map(|dir| (self.path(dir), self.size(dir))).collect()
+    }
+
+    /// Dirs of size within given range, like `find -type d` with `--min-size` / `--max-size`.
+    pub fn find_dirs(&self, sizes: impl RangeBounds<u64>) -> Vec<NodeId> {
+        self.dirs().filter(|&dir| sizes.contains(&self.size(dir))).collect()
+    }
+
+    /// Smallest dir, which deletion leaves at least `needed` free bytes on a disk of `disk_size`.
+    pub fn smallest_dir_to_free(&self, disk_size: u64, needed: u64) -> Option<NodeId> {
+        let free = disk_size.saturating_sub(self.size(self.root()));
+        let missing = needed.saturating_sub(free);
+
+        self.find_dirs(missing..).into_iter().min_by_key(|&dir| self.size(dir))
+    }
+
+    /// Renders the tree the way the puzzle does, dirs first.
+    pub fn tree(&self) -> String {
+        let mut rendered = String::new();
+        self.render(self.root(), 0, &mut rendered);
+        rendered
+    }
+
+    fn render(&self, id: NodeId, depth: usize, rendered: &mut String) {
+        let kind = if self.is_dir(id) { "dir" } else { "file" };
+        writeln!(rendered, "{}- {} ({kind}, size={})", "  ".repeat(depth), self.name(id), self.size(id)).unwrap();
+
+        let (dirs, files): (Vec<NodeId>, Vec<NodeId>) = self.children(id).iter().partition(|&&child| self.is_dir(child));
+        for child in dirs.into_iter().chain(files) {
+            self.render(child, depth + 1, rendered);
         }
     }
+}
 
-    sum
+/// Shell working on a [FileSystem], the way the puzzle's terminal does.
+pub struct Shell {
+    file_system: FileSystem,
+    cwd: NodeId,
 }
 
-fn calculate_all_sizes(node: Rc<RefCell<TreeNode>>) -> i32 {
-    let mut total = 0;
+impl Shell {
+    pub fn new(file_system: FileSystem) -> Shell {
+        let cwd = file_system.root();
+        Shell { file_system, cwd }
+    }
 
-    for node in RefCell::borrow(&node).children.iter() {
-        let item_type: &ItemType = &RefCell::borrow(node).value;
+    pub fn file_system(&self) -> &FileSystem {
+        &self.file_system
+    }
 
-        match item_type {
-            ItemType::File { .. } => continue,
-            ItemType::Folder { .. } => {
-                let size = calculate_item_size(Rc::clone(node));
+    pub fn cwd(&self) -> NodeId {
+        self.cwd
+    }
 
-                if size < 100_000 {
-                    total += size;
-                }
+    pub fn cd(&mut self, param: &str) -> Result<(), InconsistencyKind> {
+        self.cwd = match param {
+            "/" => self.file_system.root(),
+            ".." => self.file_system.parent(self.cwd).ok_or(InconsistencyKind::CdAboveRoot)?,
+            _ => match self.file_system.child(self.cwd, param) {
+                Some(dir) if self.file_system.is_dir(dir) => dir,
+                Some(_) => return Err(InconsistencyKind::NotADir(param.to_string())),
+                None => return Err(InconsistencyKind::UnknownDir(param.to_string())),
+            },
+        };
+
+        Ok(())
+    }
+
+    /// Listing of the current dir in the puzzle's format, sorted by name.
+    pub fn ls(&self) -> String {
+        listing(&self.file_system, self.cwd)
+    }
+
+    /// Records one line of `ls` output, which must agree with what was listed before.
+    fn record(&mut self, item_type: ItemType) -> Result<(), InconsistencyKind> {
+        let (name, listed_size) = match &item_type {
+            ItemType::Folder { name } => (name, None),
+            ItemType::File { name, size } => (name, Some(*size)),
+        };
 
-                total += calculate_all_sizes(Rc::clone(node));
+        match (self.file_system.child(self.cwd, name), listed_size) {
+            (None, None) => {
+                self.file_system.add_dir(self.cwd, name);
             }
+            (None, Some(size)) => {
+                self.file_system.add_file(self.cwd, name, size);
+            }
+            (Some(existing), None) if self.file_system.is_dir(existing) => {}
+            (Some(existing), Some(listed_size)) if !self.file_system.is_dir(existing) => {
+                let size = self.file_system.size(existing);
+                if size != listed_size {
+                    return Err(InconsistencyKind::ConflictingSize { name: name.clone(), size, listed_size });
+                }
+            }
+            (Some(_), _) => return Err(InconsistencyKind::ConflictingType(name.clone())),
         }
+
+        Ok(())
     }
+}
+
+fn listing(file_system: &FileSystem, dir: NodeId) -> String {
+    let mut children = file_system.children(dir).to_vec();
+    children.sort_by_key(|&child| file_system.name(child));
 
-    total
+    children.into_iter()
+        .map(|child| {
+            if file_system.is_dir(child) {
+                format!("dir {}\n", file_system.name(child))
+            } else {
+                format!("{} {}\n", file_system.size(child), file_system.name(child))
+            }
+        })
+        .collect()
 }
 
-fn init_tree(file_name: &str) -> Rc<RefCell<TreeNode>> {
-    let root = Rc::new(RefCell::new(TreeNode::new()));
-    let mut current_folder = Rc::clone(&root);
+/// Replays a terminal transcript, skipping every line inconsistent with what happened before.
+/// Returns the reconstructed filesystem along with the skipped lines.
+pub fn replay(input: &str) -> (FileSystem, Vec<Inconsistency>) {
+    let mut shell = Shell::new(FileSystem::new());
+    let mut inconsistencies = Vec::new();
+    let mut listing = false;
 
-    for item in read_input(file_name).skip(1) {
-        match item {
+    for (index, item) in read_input_from_str(input).enumerate() {
+        let result = item.and_then(|item| match item {
             IteratorItem::Command { command_type, param } => {
+                listing = false;
                 match command_type {
-                    CommandType::Cd => {
-                        if param == ".." {
-                            let current_clone = Rc::clone(&current_folder);
-                            current_folder = Rc::clone(RefCell::borrow(&current_clone).parent.as_ref().unwrap_or(&root));
-                        } else {
-                            current_folder = find_child_by_name(Rc::clone(&current_folder), &param).unwrap();
-                        }
-                    }
-                    CommandType::Ls => {}
-                }
-            }
-            IteratorItem::Info { item_type } => {
-                match item_type {
-                    ItemType::Folder { name } => {
-                        let node = TreeNode {
-                            value: ItemType::Folder { name },
-                            parent: Some(Rc::clone(&current_folder)),
-                            children: vec![],
-                        };
-
-                        RefCell::borrow_mut(&current_folder).add_child(Rc::new(RefCell::new(node)));
-                    }
-                    ItemType::File { size } => {
-                        let node = TreeNode {
-                            value: ItemType::File { size },
-                            parent: Some(Rc::clone(&current_folder)),
-                            children: vec![],
-                        };
-
-                        RefCell::borrow_mut(&current_folder).add_child(Rc::new(RefCell::new(node)));
+                    CommandType::Cd => shell.cd(&param),
+                    CommandType::Ls => {
+                        listing = true;
+                        Ok(())
                     }
                 }
             }
+            IteratorItem::Info { .. } if !listing => Err(InconsistencyKind::OutputWithoutLs),
+            IteratorItem::Info { item_type } => shell.record(item_type),
+        });
+
+        if let Err(kind) = result {
+            inconsistencies.push(Inconsistency { line: index + 1, kind });
         }
     }
 
-    root
+    (shell.file_system, inconsistencies)
 }
 
-fn get_all_dir_sizes(node: Rc<RefCell<TreeNode>>, dir_sizes: &mut Vec<i32>) {
-    for child in RefCell::borrow(&node).children.iter() {
-        match &RefCell::borrow(child).value {
-            ItemType::File { .. } => continue,
-            ItemType::Folder { .. } => {
-                dir_sizes.push(calculate_item_size(Rc::clone(child)));
+/// Transcript visiting every dir once in name order, which replays to the same filesystem.
+pub fn canonical_transcript(file_system: &FileSystem) -> String {
+    let mut transcript = "$ cd /\n".to_string();
+    write_dir_transcript(file_system, file_system.root(), &mut transcript);
+    transcript
+}
 
-                get_all_dir_sizes(Rc::clone(child), dir_sizes);
-            }
-        }
+fn write_dir_transcript(file_system: &FileSystem, dir: NodeId, transcript: &mut String) {
+    transcript.push_str("$ ls\n");
+    transcript.push_str(&listing(file_system, dir));
+
+    let mut subdirs = file_system.children(dir).iter()
+        .copied()
+        .filter(|&child| file_system.is_dir(child))
+        .collect::<Vec<_>>();
+    subdirs.sort_by_key(|&child| file_system.name(child));
+
+    for subdir in subdirs {
+        writeln!(transcript, "$ cd {}", file_system.name(subdir)).unwrap();
+        write_dir_transcript(file_system, subdir, transcript);
+        transcript.push_str("$ cd ..\n");
     }
 }
 
-pub fn no_space_left_on_device_part_1(file_name: &str) -> i32 {
-    let root = init_tree(file_name);
-    print(Rc::clone(&root));
+fn init_tree(input: &str) -> FileSystem {
+    let (file_system, inconsistencies) = replay(input);
+
+    if let Some(inconsistency) = inconsistencies.first() {
+        panic!("inconsistent transcript, {inconsistency}");
+    }
 
-    calculate_all_sizes(Rc::clone(&root))
+    file_system
 }
 
-pub fn no_space_left_on_device_part_2(file_name: &str) -> i32 {
-    let max_space = 70_000_000;
-    let required_space = 30_000_000;
-    let root = init_tree(file_name);
+pub fn no_space_left_on_device_part_1(file_name: &str) -> u64 {
+    no_space_left_on_device_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-    let missing_space = calculate_item_size(Rc::clone(&root)) - (max_space - required_space);
-    let mut dir_sizes: Vec<i32> = Vec::new();
+pub fn no_space_left_on_device_part_1_from_str(input: &str) -> u64 {
+    let file_system = init_tree(input);
+    print!("{}", file_system.tree());
 
-    get_all_dir_sizes(Rc::clone(&root), &mut dir_sizes);
+    file_system.find_dirs(..=100_000).into_iter()
+        .map(|dir| file_system.size(dir))
+        .sum()
+}
 
-    let mut closest_size = max_space;
-    for dir_size in dir_sizes.iter() {
-        let diff = dir_size - missing_space;
-        if diff > 0 && closest_size - missing_space > dir_size - missing_space {
-            closest_size = *dir_size;
-        }
-    }
+pub fn no_space_left_on_device_part_2(file_name: &str) -> u64 {
+    no_space_left_on_device_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-    closest_size
+pub fn no_space_left_on_device_part_2_from_str(input: &str) -> u64 {
+    let file_system = init_tree(input);
+
+    let dir = file_system.smallest_dir_to_free(DISK_SIZE, REQUIRED_SPACE).unwrap();
+    file_system.size(dir)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_input_example() {
+        let answer = no_space_left_on_device_part_1("inputs/7_input_example.txt");
+
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 95437);
+    }
+
+    #[test]
+    fn part_1_input() {
+        let answer = no_space_left_on_device_part_1("inputs/7_input.txt");
+
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 1581595);
+    }
+
+    #[test]
+    fn part_2_input_example() {
+        let answer = no_space_left_on_device_part_2("inputs/7_input_example.txt");
+
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 24933642);
+    }
+
+    #[test]
+    fn part_2_input() {
+        let answer = no_space_left_on_device_part_2("inputs/7_input.txt");
+
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 1544176);
+    }
+
+    #[test]
+    fn canonical_transcript_input_example() {
+        let input = fs::read_to_string("inputs/7_input_example.txt").unwrap();
+        let transcript = canonical_transcript(&init_tree(&input));
+
+        assert!(transcript.starts_with("$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n$ cd a\n$ ls\ndir e\n"));
+        assert_eq!(init_tree(&transcript).du(), init_tree(&input).du());
+        assert_eq!(canonical_transcript(&init_tree(&transcript)), transcript);
+    }
+
+    #[test]
+    fn canonical_transcript_input() {
+        let input = fs::read_to_string("inputs/7_input.txt").unwrap();
+        let transcript = canonical_transcript(&init_tree(&input));
+
+        assert_eq!(no_space_left_on_device_part_1_from_str(&transcript), 1581595);
+    }
+
+    #[test]
+    fn replay_reports_inconsistencies() {
+        let input = "$ cd /\n\
+            $ ls\n\
+            dir a\n\
+            10 b\n\
+            $ cd x\n\
+            $ cd ..\n\
+            $ ls\n\
+            20 b\n\
+            dir b\n\
+            $ cd b\n\
+            $ rm b\n\
+            dir\n\
+            $ cd a\n\
+            5 c\n";
+
+        let (file_system, inconsistencies) = replay(input);
+
+        assert_eq!(inconsistencies, vec![
+            Inconsistency { line: 5, kind: InconsistencyKind::UnknownDir("x".to_string()) },
+            Inconsistency { line: 6, kind: InconsistencyKind::CdAboveRoot },
+            Inconsistency { line: 8, kind: InconsistencyKind::ConflictingSize { name: "b".to_string(), size: 10, listed_size: 20 } },
+            Inconsistency { line: 9, kind: InconsistencyKind::ConflictingType("b".to_string()) },
+            Inconsistency { line: 10, kind: InconsistencyKind::NotADir("b".to_string()) },
+            Inconsistency { line: 11, kind: InconsistencyKind::UnknownCommand("rm b".to_string()) },
+            Inconsistency { line: 12, kind: InconsistencyKind::MalformedLine("dir".to_string()) },
+            Inconsistency { line: 14, kind: InconsistencyKind::OutputWithoutLs },
+        ]);
+        assert_eq!(file_system.size(file_system.root()), 10);
+        assert_eq!(inconsistencies[0].to_string(), "line 5: cd into unknown dir 'x'");
+    }
+
+    #[test]
+    fn queries_input_example() {
+        let file_system = init_tree(&fs::read_to_string("inputs/7_input_example.txt").unwrap());
+
+        let e = file_system.child(file_system.child(file_system.root(), "a").unwrap(), "e").unwrap();
+        assert_eq!(file_system.path(e), "/a/e");
+        assert_eq!(file_system.du(), vec![
+            ("/".to_string(), 48381165),
+            ("/a".to_string(), 94853),
+            ("/d".to_string(), 24933642),
+            ("/a/e".to_string(), 584),
+        ]);
+        assert_eq!(file_system.find_dirs(1_000_000..), vec![file_system.root(), file_system.child(file_system.root(), "d").unwrap()]);
+        assert!(file_system.tree().starts_with("- / (dir, size=48381165)\n  - a (dir, size=94853)\n    - e (dir, size=584)\n      - i (file, size=584)\n"));
+    }
+
+    #[test]
+    fn smallest_dir_to_free_on_overfull_disk() {
+        let file_system = init_tree(&fs::read_to_string("inputs/7_input_example.txt").unwrap());
+        let d = file_system.child(file_system.root(), "d").unwrap();
+
+        assert_eq!(file_system.smallest_dir_to_free(40_000_000, 20_000_000), Some(d));
+        assert_eq!(file_system.smallest_dir_to_free(40_000_000, 30_000_000), Some(file_system.root()));
+        assert_eq!(file_system.smallest_dir_to_free(40_000_000, 50_000_000), None);
+    }
+}
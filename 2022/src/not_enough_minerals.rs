@@ -1,411 +1,302 @@
-#![allow(dead_code)]
+use std::fs;
 
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-use itertools::Itertools;
 use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter};
 
-const TOTAL_TIME: u32 = 24;
-
 #[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq, Hash)]
-enum Mineral {
+pub enum Mineral {
     Ore,
     Clay,
     Obsidian,
     Geode,
 }
 
-type CostInfo = HashMap<Mineral, u32>;
-
 #[derive(Debug)]
-struct Blueprint {
-    id: usize,
-    costs: HashMap<Mineral, CostInfo>,
+pub struct Blueprint {
+    pub id: u32,
+    /// costs[robot][mineral]
+    costs: [[u32; 4]; 4],
 }
 
-fn read_input(file_name: &str) -> Vec<Blueprint> {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
-    let mut buf = String::new();
-
-    let mut blueprints = Vec::new();
-
-    while let Ok(n) = reader.read_line(&mut buf) {
-        if n == 0 {
-            break;
+impl Blueprint {
+    /// Most of the mineral any robot costs, more robots mining it than that cannot be used,
+    /// because only one robot is built per minute.
+    fn max_spend(&self, mineral: Mineral) -> u32 {
+        match mineral {
+            Mineral::Geode => u32::MAX,
+            _ => self.costs.iter().map(|costs| costs[mineral as usize]).max().unwrap(),
         }
+    }
+}
 
-        let mut costs = HashMap::new();
-
-        // id 1 / ore robot: 3 ore / clay robot: 3 ore / obsidian robot: 3 ore 19 clay / geode robot 3 ore 17 obsidian
-        // 1 3 3 3 19 3 17
-
-        let only_numbers = buf.trim()
-            .replace("Blueprint ", "")
-            .replace(": Each ore robot costs", "")
-            .replace(" ore. Each clay robot costs", "")
-            .replace(" ore. Each obsidian robot costs", "")
-            .replace(" ore and", "")
-            .replace(" clay. Each geode robot costs", "")
-            .replace(" ore and", "")
-            .replace(" obsidian.", "");
-
-        let data = only_numbers.split(' ')
-            .map(|s| s.parse::<u32>().unwrap())
-            .collect::<Vec<_>>();
-
-        let id = data[0] as usize;
-        let ore_robot_costs = HashMap::from([(Mineral::Ore, data[1])]);
-        let clay_robot_costs = HashMap::from([(Mineral::Ore, data[2])]);
-        let obsidian_robot_costs = HashMap::from([(Mineral::Ore, data[3]), (Mineral::Clay, data[4])]);
-        let geode_robot_costs = HashMap::from([(Mineral::Ore, data[5]), (Mineral::Obsidian, data[6])]);
+pub fn read_input(file_name: &str) -> Vec<Blueprint> {
+    parse_input(&fs::read_to_string(file_name).unwrap())
+}
 
-        costs.insert(Mineral::Ore, ore_robot_costs);
-        costs.insert(Mineral::Clay, clay_robot_costs);
-        costs.insert(Mineral::Obsidian, obsidian_robot_costs);
-        costs.insert(Mineral::Geode, geode_robot_costs);
+fn parse_input(input: &str) -> Vec<Blueprint> {
+    input.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            // id / ore robot: ore / clay robot: ore / obsidian robot: ore, clay / geode robot: ore, obsidian
+            let data = line.split(|ch: char| !ch.is_ascii_digit())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.parse::<u32>().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(data.len(), 7, "invalid blueprint: {line}");
+
+            Blueprint {
+                id: data[0],
+                costs: [
+                    [data[1], 0, 0, 0],
+                    [data[2], 0, 0, 0],
+                    [data[3], data[4], 0, 0],
+                    [data[5], 0, data[6], 0],
+                ],
+            }
+        })
+        .collect()
+}
 
-        blueprints.push(Blueprint { id, costs });
+/// Robot finished at the end of the minute, counted from 1.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Build {
+    pub minute: u32,
+    pub robot: Mineral,
+}
 
-        buf = String::new();
-    }
+#[derive(Debug)]
+pub struct Optimum {
+    pub geodes: u32,
+    pub timeline: Vec<Build>,
+}
 
-    blueprints
+#[derive(Copy, Clone)]
+struct State {
+    /// minutes passed
+    minute: u32,
+    robots: [u32; 4],
+    minerals: [u32; 4],
 }
 
+impl State {
+    /// State right after the robot is built as soon as its costs are mined, if that happens before the time limit.
+    fn build(&self, blueprint: &Blueprint, robot: Mineral, time_limit: u32) -> Option<State> {
+        let costs = &blueprint.costs[robot as usize];
 
-fn initialize_equipment() -> (HashMap<Mineral, u32>, HashMap<Mineral, u32>) {
-    (
-        HashMap::from([
-            (Mineral::Ore, 1),
-            (Mineral::Clay, 0),
-            (Mineral::Obsidian, 0),
-            (Mineral::Geode, 0),
-        ]),
-        HashMap::from([
-            (Mineral::Ore, 0),
-            (Mineral::Clay, 0),
-            (Mineral::Obsidian, 0),
-            (Mineral::Geode, 0),
-        ])
-    )
-}
+        let mut wait = 0;
+        for (mineral, &cost) in costs.iter().enumerate() {
+            if cost > self.minerals[mineral] {
+                if self.robots[mineral] == 0 {
+                    return None;
+                }
+                wait = wait.max((cost - self.minerals[mineral]).div_ceil(self.robots[mineral]));
+            }
+        }
 
-/// returns: u32 - number of robots building
-fn build_robots(robot_type: &Mineral, minerals: &mut HashMap<Mineral, u32>, costs: &Blueprint) -> u32 {
-    let robot_costs = &costs.costs[robot_type];
-    let mut affordable_number: Vec<u32> = Vec::new();
-
-    for (mineral, cost) in robot_costs.iter() {
-        if minerals[mineral] < *cost {
-            return 0;
-        } else {
-            // println!("minerals[{}] / cost <=> {} / {} = {}",mineral, minerals[mineral], cost, minerals[mineral] / cost);
-            affordable_number.push(minerals[mineral] / cost);
+        // a robot finished in the last minute mines nothing
+        let minute = self.minute + wait + 1;
+        if minute >= time_limit {
+            return None;
         }
-    }
 
-    let number_to_build = *affordable_number.iter().min().unwrap();
+        let mut next = State { minute, ..*self };
+        for (mineral, &cost) in costs.iter().enumerate() {
+            next.minerals[mineral] = self.minerals[mineral] + self.robots[mineral] * (wait + 1) - cost;
+        }
+        next.robots[robot as usize] += 1;
 
-    for (mineral, cost) in robot_costs.iter() {
-        minerals.entry(*mineral).and_modify(|qty| *qty -= cost * number_to_build);
+        Some(next)
     }
 
-    println!("build {number_to_build} robots of type {robot_type}\n");
+    /// Geodes opened until the time limit without building any more robots.
+    fn geodes(&self, time_limit: u32) -> u32 {
+        self.minerals[Mineral::Geode as usize] + self.robots[Mineral::Geode as usize] * (time_limit - self.minute)
+    }
 
-    number_to_build
+    /// Geodes opened, if a geode robot was built every minute left.
+    fn optimistic_geodes(&self, time_limit: u32) -> u32 {
+        let left = time_limit - self.minute;
+        self.geodes(time_limit) + left * left.saturating_sub(1) / 2
+    }
 }
 
-fn mine_minerals(robots: &HashMap<Mineral, u32>, minerals: &mut HashMap<Mineral, u32>) {
-    for (mineral, qty) in minerals.iter_mut() {
-        *qty += robots[mineral];
-    }
+/// Branch and bound search jumping from build to build; a robot is built as soon as it can be afforded,
+/// robots of a mineral are capped by the most of it spent per minute, and branches that cannot beat
+/// the best result even with a geode robot every minute are cut.
+pub fn max_geodes(blueprint: &Blueprint, time_limit: u32) -> Optimum {
+    let start = State { minute: 0, robots: [1, 0, 0, 0], minerals: [0; 4] };
+    let mut optimum = Optimum { geodes: 0, timeline: Vec::new() };
+
+    search(blueprint, time_limit, start, &mut Vec::new(), &mut optimum);
+
+    optimum
 }
 
-fn try_build_next_robots(
-    minute: u32,
-    costs: &HashMap<Mineral, CostInfo>,
-    robots: HashMap<Mineral, u32>,
-    minerals: HashMap<Mineral, u32>,
-    history: HashMap<Mineral, Vec<u32>>,
-    robots_to_build: Vec<Mineral>,
-) -> u32 {
-    if minute == TOTAL_TIME {
-        print_status(&robots, &minerals, &history);
-
-        return minerals[&Mineral::Geode];
+fn search(blueprint: &Blueprint, time_limit: u32, state: State, timeline: &mut Vec<Build>, optimum: &mut Optimum) {
+    let geodes = state.geodes(time_limit);
+    if geodes > optimum.geodes {
+        optimum.geodes = geodes;
+        optimum.timeline = timeline.clone();
     }
 
-    let geode_obsidian_cost = &costs[&Mineral::Geode][&Mineral::Obsidian];
-    if minute >= TOTAL_TIME - geode_obsidian_cost && !history.contains_key(&Mineral::Obsidian) {
-        // we wont be able to build any geode robots till 24 minute
-        return 0;
+    if state.optimistic_geodes(time_limit) <= optimum.geodes {
+        return;
     }
 
-    let mut answers = Vec::new();
-
-    // try to build requested robots
-    for robot_type in robots_to_build.iter() {
-        let robot_costs = &costs[robot_type];
-        let mut is_affordable = true;
+    for robot in Mineral::iter().rev() {
+        if state.robots[robot as usize] >= blueprint.max_spend(robot) {
+            continue;
+        }
 
-        // check if there is enough minerals
-        for (mineral, cost) in robot_costs.iter() {
-            if minerals[mineral] < *cost {
-                is_affordable = false;
-                break;
-            }
+        if let Some(next) = state.build(blueprint, robot, time_limit) {
+            timeline.push(Build { minute: next.minute, robot });
+            search(blueprint, time_limit, next, timeline, optimum);
+            timeline.pop();
         }
+    }
+}
 
-        if !is_affordable {
-            // postpone the same build list to next minute
-            let mut updated_minerals = minerals.clone();
-            let updated_robots = robots.clone();
-
-            mine_minerals(&updated_robots, &mut updated_minerals);
-
-            let answer = try_build_next_robots(
-                minute + 1,
-                costs,
-                updated_robots,
-                updated_minerals,
-                history.clone(),
-                robots_to_build.clone());
-
-            answers.push(answer);
-        } else { // is affordable
-            let mut updated_history = history.clone();
-            let mut updated_minerals = minerals.clone();
-            let mut updated_robots = robots.clone();
-
-            updated_history.entry(*robot_type)
-                .and_modify(|minutes| minutes.push(minute))
-                .or_insert_with(|| vec![minute]);
-
-            // spend minerals to build a robot
-            for (mineral, cost) in robot_costs.iter() {
-                updated_minerals.entry(*mineral).and_modify(|qty| *qty -= cost);
-            }
+pub fn not_enough_minerals_part_1(file_name: &str) -> u32 {
+    not_enough_minerals_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-            // mine new minerals
-            mine_minerals(&updated_robots, &mut updated_minerals);
+/// Sum of the quality levels of the blueprints in 24 minutes.
+pub fn not_enough_minerals_part_1_from_str(input: &str) -> u32 {
+    parse_input(input).iter()
+        .map(|blueprint| blueprint.id * max_geodes(blueprint, 24).geodes)
+        .sum()
+}
 
-            // finish construction of a new robot
-            updated_robots.entry(*robot_type).and_modify(|robots_number| *robots_number += 1);
+pub fn not_enough_minerals_part_2(file_name: &str) -> u32 {
+    not_enough_minerals_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-            // make new build request
-            let new_robots_to_build: Vec<Mineral> = create_build_request(*robot_type, minute, &updated_history);
+/// Product of the geodes of the first three blueprints in 32 minutes.
+pub fn not_enough_minerals_part_2_from_str(input: &str) -> u32 {
+    parse_input(input).iter()
+        .take(3)
+        .map(|blueprint| max_geodes(blueprint, 32).geodes)
+        .product()
+}
 
-            let answer = try_build_next_robots(
-                minute + 1,
-                costs,
-                updated_robots,
-                updated_minerals,
-                updated_history,
-                new_robots_to_build);
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
-            answers.push(answer);
-        }
-    }
+    use crate::input_generator::blueprints;
 
-    *answers.iter().max().unwrap()
-}
+    use super::*;
 
-fn create_build_request(robot_type: Mineral, _current_minute: u32, _history: &HashMap<Mineral, Vec<u32>>) -> Vec<Mineral> {
-    match robot_type {
-        Mineral::Ore => vec![Mineral::Ore, Mineral::Clay],
-        Mineral::Clay => vec![Mineral::Clay, Mineral::Obsidian],
-        Mineral::Obsidian => vec![Mineral::Obsidian, Mineral::Geode],
-        Mineral::Geode => vec![Mineral::Geode],
-    }
-    // match (history.contains_key(&Mineral::Ore), history.contains_key(&Mineral::Clay), history.contains_key(&Mineral::Obsidian), history.contains_key(&Mineral::Geode)) {
-    //     (true, true, true, true) => vec![Mineral::Geode],
-    //     (true, true, true, false) => {
-    //         let most_recent_clay = history[&Mineral::Clay].iter().last().unwrap();
-    //         if current_minute < most_recent_clay + 3 {
-    //             vec![Mineral::Clay, Mineral::Obsidian, Mineral::Geode]
-    //         } else {
-    //             vec![Mineral::Obsidian, Mineral::Geode]
-    //         }
-    //     }
-    //     (true, true, false, false) => {
-    //         let most_recent_ore = history[&Mineral::Ore].iter().last().unwrap();
-    //         if current_minute < most_recent_ore + 3 {
-    //             vec![Mineral::Ore, Mineral::Clay, Mineral::Obsidian]
-    //         } else {
-    //             vec![Mineral::Obsidian, Mineral::Geode]
-    //         }
-    //     }
-    //     (true, false, false, false) => {
-    //         vec![Mineral::Ore, Mineral::Clay]
-    //     }
-    //     _ => {
-    //         vec![Mineral::Ore, Mineral::Clay]
-    //     }
-    // }
-}
+    #[test]
+    fn part_1_input_example() {
+        let answer = not_enough_minerals_part_1("inputs/19_input_example.txt");
 
-fn print_status(robots: &HashMap<Mineral, u32>, minerals: &HashMap<Mineral, u32>, history: &HashMap<Mineral, Vec<u32>>) {
-    println!("FINISHED");
-    for (val, min) in history.iter()
-        .flat_map(|(key, values)| {
-            values.iter().map(move |value| (key, *value))
-        })
-        .sorted_by(|(_, min1), (_, min2)| min1.cmp(min2))
-        .collect::<Vec<_>>() {
-        println!("minute {min} - build {val} robot");
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 33);
     }
-    print_equipment(robots, minerals);
-}
 
-/// round phases:
-/// 1. spending minerals for robots construction
-/// 1. mining minerals
-/// 1. finishing robots constructions
-fn analyze_next_minute(
-    minute: u32,
-    robots: HashMap<Mineral, u32>,
-    minerals: HashMap<Mineral, u32>,
-    costs: &Blueprint,
-    history: Vec<(Option<Mineral>, u32)>,
-) -> u32 {
-    if minute == TOTAL_TIME {
-        println!("FINISHED");
-        for (mineral, min) in history {
-            match mineral {
-                None => {
-                    // println!("minute {min} - do nothing"),
-                }
-                Some(val) => println!("minute {min} - build {val} robot"),
-            }
-        }
-        print_equipment(&robots, &minerals);
+    #[test]
+    fn part_1_input() {
+        let answer = not_enough_minerals_part_1("inputs/19_input.txt");
 
-        return minerals[&Mineral::Geode];
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 1528);
     }
 
-    if minute >= TOTAL_TIME - 7 && !history.iter().any(|(mineral, _)| mineral.is_some() && mineral.unwrap() == Mineral::Obsidian) {
-        return 0;
+    #[test]
+    fn part_2_input_example() {
+        let answer = not_enough_minerals_part_2("inputs/19_input_example.txt");
+
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 3472);
     }
 
-    let mut allowed_minerals: Vec<Mineral> = vec![Mineral::Geode, Mineral::Obsidian, Mineral::Clay, Mineral::Ore];
-    let _ = allowed_minerals[0];
+    #[test]
+    fn part_2_input() {
+        let answer = not_enough_minerals_part_2("inputs/19_input.txt");
 
-    let filtered_history = history.iter()
-        .filter(|(miner, _min)| miner.is_some())
-        .map(|(miner, min)| (miner.unwrap(), min));
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 16926);
+    }
 
-    match filtered_history.clone().last() {
-        None => { // no robot was build yet
-            allowed_minerals = vec![Mineral::Clay, Mineral::Ore]
-        }
-        Some((miner, _min)) => {
-            allowed_minerals = match miner {
-                Mineral::Ore => vec![Mineral::Clay, Mineral::Ore],
-                Mineral::Clay => vec![Mineral::Obsidian, Mineral::Clay],
-                Mineral::Obsidian => vec![Mineral::Geode, Mineral::Obsidian],
-                Mineral::Geode => vec![Mineral::Geode],
-            }
+    #[test]
+    fn part_1_generated_input() {
+        let input = blueprints(&mut StdRng::seed_from_u64(19), 3);
+        let answer = not_enough_minerals_part_1_from_str(&input);
 
-            // append "lower" mineral if...
-        }
+        println!("part 1 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 53);
     }
 
-    println!("\tallowed minerals in minute {minute} are: {:?}", allowed_minerals);
+    #[test]
+    fn part_2_generated_input() {
+        let input = blueprints(&mut StdRng::seed_from_u64(19), 3);
+        let answer = not_enough_minerals_part_2_from_str(&input);
 
-    let mut answers = Vec::new();
-    let mut was_anything_build = false;
+        println!("part 2 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 47916);
+    }
 
-    // choice: try buy a robot
-    for mineral in allowed_minerals {
-        let robot_costs = &costs.costs[&mineral];
-        let mut is_affordable = true;
+    /// Geodes opened by following the timeline minute by minute.
+    fn replay(blueprint: &Blueprint, time_limit: u32, timeline: &[Build]) -> u32 {
+        let (mut robots, mut minerals) = ([1, 0, 0, 0], [0_u32; 4]);
+        let mut builds = timeline.iter().peekable();
 
-        // check if there is enough minerals
-        for (mineral, cost) in robot_costs.iter() {
-            if minerals[mineral] < *cost {
-                is_affordable = false;
-                break;
+        for minute in 1..=time_limit {
+            let build = builds.next_if(|build| build.minute == minute);
+            if let Some(build) = build {
+                for (mineral, cost) in minerals.iter_mut().zip(blueprint.costs[build.robot as usize]) {
+                    *mineral = mineral.checked_sub(cost).expect("robot not affordable");
+                }
             }
-        }
-
-        if is_affordable {
-            was_anything_build = true;
-            let mut updated_history = history.clone();
-            let mut updated_minerals = minerals.clone();
-            let mut updated_robots = robots.clone();
-
-            updated_history.push((Some(mineral), minute));
-
-            // spend minerals to build a robot
-            for (mineral, cost) in robot_costs.iter() {
-                updated_minerals.entry(*mineral).and_modify(|qty| *qty -= cost);
+            for (mineral, mining) in minerals.iter_mut().zip(robots) {
+                *mineral += mining;
             }
+            if let Some(build) = build {
+                robots[build.robot as usize] += 1;
+            }
+        }
+        assert!(builds.next().is_none());
 
-            // mine new minerals
-            mine_minerals(&updated_robots, &mut updated_minerals);
+        minerals[Mineral::Geode as usize]
+    }
 
-            // finish construction of a new robot
-            updated_robots.entry(mineral).and_modify(|robots_number| *robots_number += 1);
+    #[test]
+    fn timeline_input_example() {
+        let blueprints = parse_input(&fs::read_to_string("inputs/19_input_example.txt").unwrap());
+        let optimum = max_geodes(&blueprints[0], 24);
 
-            let answer = analyze_next_minute(minute + 1, updated_robots, updated_minerals, costs, updated_history);
-            answers.push(answer);
-        }
+        assert_eq!(optimum.geodes, 9);
+        assert_eq!(replay(&blueprints[0], 24, &optimum.timeline), 9);
+        assert_eq!(optimum.timeline.iter().filter(|build| build.robot == Mineral::Geode).count(), 2);
     }
 
-    if !was_anything_build {
-        // choice: do nothing
-        let mut updated_minerals = minerals;
-        let mut updated_history = history.clone();
-
-        updated_history.push((None, minute));
+    #[test]
+    fn timeline_generated_input() {
+        let input = blueprints(&mut StdRng::seed_from_u64(19), 3);
 
-        mine_minerals(&robots, &mut updated_minerals);
+        let geodes = parse_input(&input).iter()
+            .map(|blueprint| {
+                let optimum = max_geodes(blueprint, 32);
+                assert_eq!(replay(blueprint, 32, &optimum.timeline), optimum.geodes);
 
-        let answer = analyze_next_minute(minute + 1, robots.clone(), updated_minerals, costs, updated_history);
-        answers.push(answer);
+                optimum.geodes
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(geodes.iter().product::<u32>(), 47916);
     }
 
-    // // choice: do nothing
-    // let mut updated_minerals = minerals;
-    // let mut updated_history = history.clone();
-    //
-    // updated_history.push((None, minute));
-    //
-    //
-    // mine_minerals(&robots, &mut updated_minerals);
-    //
-    // let answer = analyze_next_minute(minute + 1, robots.clone(), updated_minerals, costs, updated_history);
-    // answers.push(answer);
-
-    *answers.iter().max().unwrap()
-}
+    #[test]
+    fn other_time_limits() {
+        let blueprints = parse_input(&fs::read_to_string("inputs/19_input_example.txt").unwrap());
 
-pub fn not_enough_minerals_part_1(file_name: &str) -> u32 {
-    let costs = read_input(file_name);
-    let (robots, minerals) = initialize_equipment();
-
-    println!("{:#?}", costs);
-
-    // let max_geode_qty = analyze_next_minute(1, robots, minerals, &costs[0], Vec::new());
-    let max_geode_qty = try_build_next_robots(
-        1,
-        &costs[0].costs,
-        robots,
-        minerals,
-        HashMap::from([(Mineral::Ore, vec![0])]),
-        Vec::from([Mineral::Clay, Mineral::Ore]));
-
-    println!("max geode qty for blueprint {} is {}", costs[0].id, max_geode_qty);
-    max_geode_qty
-}
+        let geodes = [0, 10, 20, 24, 28].map(|time_limit| max_geodes(&blueprints[1], time_limit).geodes);
+        assert_eq!(geodes[0], 0);
+        assert!(geodes.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(geodes[3], 12);
 
-fn print_equipment(robots: &HashMap<Mineral, u32>, minerals: &HashMap<Mineral, u32>) {
-    for mineral in Mineral::iter() {
-        println!("\t{:20} robots:{:3?}  minerals:{:?}", mineral, robots[&mineral], minerals[&mineral]);
+        let optimum = max_geodes(&blueprints[1], 28);
+        assert_eq!(replay(&blueprints[1], 28, &optimum.timeline), optimum.geodes);
     }
-    println!();
 }
@@ -0,0 +1,157 @@
+use std::any::Any;
+use std::panic;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::beacon_exclusion_zone::{beacon_exclusion_zone_part_1_from_str, beacon_exclusion_zone_part_2_from_str};
+use crate::blizzard_basin::{blizzard_basin_part_1_from_str, blizzard_basin_part_2_from_str};
+use crate::boiling_boulders::{boiling_boulders_part_1_from_str, boiling_boulders_part_2_from_str};
+use crate::calorie_counting::{calculate_max_calories_from_str, calculate_top_n_max_calories_from_str};
+use crate::camp_cleanup::{camp_cleanup_part_1_from_str, camp_cleanup_part_2_from_str};
+use crate::cathode_ray_tube::{cathode_ray_tube_part_1_from_str, cathode_ray_tube_part_2_from_str};
+use crate::distress_signal::{distress_signal_part_1_from_str, distress_signal_part_2_from_str};
+use crate::full_of_hot_air::full_of_hot_air_part_1_from_str;
+use crate::grove_positioning_system::{grove_positioning_system_part_1_from_str, grove_positioning_system_part_2_from_str};
+use crate::hill_climbing_algorithm::{hill_climbing_algorithm_part_1_from_str, hill_climbing_algorithm_part_2_from_str};
+use crate::monkey_in_the_middle::{DivideBy, ModuloLcm, monkey_in_the_middle_from_str};
+use crate::monkey_map::{monkey_map_part_1_from_str, monkey_map_part_2_from_str};
+use crate::monkey_math::{monkey_math_part_1_from_str, monkey_math_part_2_from_str};
+use crate::no_space_left_on_device::{no_space_left_on_device_part_1_from_str, no_space_left_on_device_part_2_from_str};
+use crate::not_enough_minerals::{not_enough_minerals_part_1_from_str, not_enough_minerals_part_2_from_str};
+use crate::proboscidea_volcanium::{proboscidea_volcanium_part_1_from_str, proboscidea_volcanium_part_2_from_str};
+use crate::pyroclastic_flow::pyroclastic_flow_from_str;
+use crate::regolith_reservoir::{regolith_reservoir_part_1_from_str, regolith_reservoir_part_2_from_str};
+use crate::rock_paper_scissors::{rock_paper_scissors_part_1_from_str, rock_paper_scissors_part_2_from_str};
+use crate::rope_bridge::{rope_bridge_part_1_from_str, rope_bridge_part_2_from_str};
+use crate::rucksack_reorganization::{rucksack_reorganization_part_1_from_str, rucksack_reorganization_part_2_from_str};
+use crate::supply_stacks::{supply_stacks_part_1_from_str, supply_stacks_part_2_from_str};
+use crate::treetop_tree_house::{treetop_tree_house_part_1_from_str, treetop_tree_house_part_2_from_str};
+use crate::tuning_trouble::{tuning_trouble_part_1_from_str, tuning_trouble_part_2_from_str};
+use crate::unstable_diffusion::{unstable_diffusion_part_1_from_str, unstable_diffusion_part_2_from_str};
+
+type Solver = fn(&str) -> String;
+
+/// Solution of given day and part, taking the puzzle input and using the puzzle's parameters.
+fn solver(day: u32, part: u32) -> Option<Solver> {
+    let solver: Solver = match (day, part) {
+        (1, 1) => |input| calculate_max_calories_from_str(input).0.to_string(),
+        (1, 2) => |input| calculate_top_n_max_calories_from_str(input, 3).iter().sum::<i32>().to_string(),
+        (2, 1) => |input| rock_paper_scissors_part_1_from_str(input).to_string(),
+        (2, 2) => |input| rock_paper_scissors_part_2_from_str(input).to_string(),
+        (3, 1) => |input| rucksack_reorganization_part_1_from_str(input).to_string(),
+        (3, 2) => |input| rucksack_reorganization_part_2_from_str(input).to_string(),
+        (4, 1) => |input| camp_cleanup_part_1_from_str(input).to_string(),
+        (4, 2) => |input| camp_cleanup_part_2_from_str(input).to_string(),
+        (5, 1) => supply_stacks_part_1_from_str,
+        (5, 2) => supply_stacks_part_2_from_str,
+        (6, 1) => |input| tuning_trouble_part_1_from_str(input).to_string(),
+        (6, 2) => |input| tuning_trouble_part_2_from_str(input).to_string(),
+        (7, 1) => |input| no_space_left_on_device_part_1_from_str(input).to_string(),
+        (7, 2) => |input| no_space_left_on_device_part_2_from_str(input).to_string(),
+        (8, 1) => |input| treetop_tree_house_part_1_from_str(input).to_string(),
+        (8, 2) => |input| treetop_tree_house_part_2_from_str(input).to_string(),
+        (9, 1) => |input| rope_bridge_part_1_from_str(input).to_string(),
+        (9, 2) => |input| rope_bridge_part_2_from_str(input, 10).to_string(),
+        (10, 1) => |input| cathode_ray_tube_part_1_from_str(input).to_string(),
+        (10, 2) => |input| cathode_ray_tube_part_2_from_str(input),
+        (11, 1) => |input| monkey_in_the_middle_from_str(input, 20, DivideBy(3)).to_string(),
+        (11, 2) => |input| monkey_in_the_middle_from_str(input, 10_000, ModuloLcm::default()).to_string(),
+        (12, 1) => |input| hill_climbing_algorithm_part_1_from_str(input).to_string(),
+        (12, 2) => |input| hill_climbing_algorithm_part_2_from_str(input).to_string(),
+        (13, 1) => |input| distress_signal_part_1_from_str(input).to_string(),
+        (13, 2) => |input| distress_signal_part_2_from_str(input).to_string(),
+        (14, 1) => |input| regolith_reservoir_part_1_from_str(input).to_string(),
+        (14, 2) => |input| regolith_reservoir_part_2_from_str(input).to_string(),
+        (15, 1) => |input| beacon_exclusion_zone_part_1_from_str(input, 2_000_000).to_string(),
+        (15, 2) => |input| beacon_exclusion_zone_part_2_from_str(input, 4_000_000).to_string(),
+        (16, 1) => |input| proboscidea_volcanium_part_1_from_str(input).to_string(),
+        (16, 2) => |input| proboscidea_volcanium_part_2_from_str(input).to_string(),
+        (17, 1) => |input| pyroclastic_flow_from_str(input, 2022).to_string(),
+        (17, 2) => |input| pyroclastic_flow_from_str(input, 1_000_000_000_000).to_string(),
+        (18, 1) => |input| boiling_boulders_part_1_from_str(input).to_string(),
+        (18, 2) => |input| boiling_boulders_part_2_from_str(input).to_string(),
+        (19, 1) => |input| not_enough_minerals_part_1_from_str(input).to_string(),
+        (19, 2) => |input| not_enough_minerals_part_2_from_str(input).to_string(),
+        (20, 1) => |input| grove_positioning_system_part_1_from_str(input).to_string(),
+        (20, 2) => |input| grove_positioning_system_part_2_from_str(input).to_string(),
+        (21, 1) => |input| monkey_math_part_1_from_str(input).to_string(),
+        (21, 2) => |input| monkey_math_part_2_from_str(input).to_string(),
+        (22, 1) => |input| monkey_map_part_1_from_str(input).to_string(),
+        (22, 2) => |input| monkey_map_part_2_from_str(input).to_string(),
+        (23, 1) => |input| unstable_diffusion_part_1_from_str(input).to_string(),
+        (23, 2) => |input| unstable_diffusion_part_2_from_str(input).to_string(),
+        (24, 1) => |input| blizzard_basin_part_1_from_str(input).to_string(),
+        (24, 2) => |input| blizzard_basin_part_2_from_str(input).to_string(),
+        (25, 1) => full_of_hot_air_part_1_from_str,
+        _ => return None,
+    };
+
+    Some(solver)
+}
+
+/// Makes panics visible in the browser console, since they abort the wasm module.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(start)]
+fn start() {
+    console_error_panic_hook::set_once();
+}
+
+/// Solves the puzzle for the input given as a string.
+/// Fails if there is no solution of given day and part.
+/// A panic of the solution is caught as an error natively only: panics can't unwind in wasm,
+/// they abort the module and reach JS as a `RuntimeError`, see ../playground/index.html.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn solve(day: u32, part: u32, input: &str) -> Result<String, String> {
+    let solver = solver(day, part).ok_or(format!("no solution of day {day} part {part}"))?;
+
+    panic::catch_unwind(|| solver(input)).map_err(panic_message)
+}
+
+/// Days having a solution of at least one part.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn solved_days() -> Vec<u32> {
+    (1..=25).filter(|&day| solver(day, 1).is_some() || solver(day, 2).is_some()).collect()
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "solution panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn solve_input_example() {
+        let input = fs::read_to_string("inputs/14_input_example.txt").unwrap();
+
+        assert_eq!(solve(14, 1, &input), Ok("24".to_string()));
+        assert_eq!(solve(14, 2, &input), Ok("93".to_string()));
+    }
+
+    #[test]
+    fn solve_missing_day() {
+        assert!(solve(25, 2, "").is_err());
+        assert!(solve(26, 1, "").is_err());
+        assert_eq!(solved_days(), (1..=25).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn solve_last_day() {
+        assert_eq!(solve(25, 1, "1=-0-2\n12111\n"), Ok("1-111=".to_string()));
+    }
+
+    #[test]
+    fn solve_invalid_input() {
+        assert!(solve(14, 1, "498,4 -> x").is_err());
+    }
+}
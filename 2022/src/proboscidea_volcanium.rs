@@ -1,278 +1,360 @@
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::iter;
-
-use itertools::Itertools;
-
-type AdjGraph = HashMap<String, HashSet<String>>;
-type MatGraph = Vec<Vec<u32>>;
-type ValveRates = HashMap<String, u32>;
-
-fn read_input(file_name: &str) -> (AdjGraph, ValveRates) {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
-    let mut input_data: Vec<(String, u32, Vec<String>)> = Vec::new();
-
-    loop {
-        let mut buf = String::new();
-        if let Ok(n) = reader.read_line(&mut buf) {
-            if n == 0 {
-                break;
-            }
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+
+/// Valves with their flow rates and the tunnels between them.
+struct Network {
+    names: Vec<String>,
+    rates: Vec<u32>,
+    tunnels: Vec<Vec<usize>>,
+}
 
-            let data = buf.trim()
+fn parse_input(input: &str) -> Network {
+    let valves = input.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let data = line.trim()
                 .replace("Valve ", "")
                 .replace(" has flow rate=", "|")
                 .replace("; tunnels lead to valves ", "|")
                 .replace("; tunnel leads to valve ", "|");
+            let data = data.split('|').collect::<Vec<_>>();
+
+            let rate = data[1].parse::<u32>().unwrap();
+            let tunnels = data[2].split(',').map(|name| name.trim().to_string()).collect::<Vec<_>>();
+
+            (data[0].to_string(), rate, tunnels)
+        })
+        .collect::<Vec<_>>();
 
-            let data = data
-                .split('|')
-                .collect::<Vec<_>>();
+    let indices = valves.iter()
+        .enumerate()
+        .map(|(index, (name, _, _))| (name.clone(), index))
+        .collect::<HashMap<_, _>>();
+
+    Network {
+        names: valves.iter().map(|(name, _, _)| name.clone()).collect(),
+        rates: valves.iter().map(|(_, rate, _)| *rate).collect(),
+        tunnels: valves.iter()
+            .map(|(_, _, tunnels)| tunnels.iter().map(|name| indices[name]).collect())
+            .collect(),
+    }
+}
 
-            let valve_name = data[0].to_string();
-            let valve_rate = data[1].parse::<u32>().unwrap();
-            let list = data[2].split(',').map(|x| x.trim().to_string()).collect::<Vec<_>>();
+impl Network {
+    fn index(&self, name: &str) -> usize {
+        self.names.iter().position(|other| other == name).unwrap_or_else(|| panic!("unknown valve {name}"))
+    }
 
-            input_data.push((valve_name, valve_rate, list))
+    /// Minutes to walk from the valve to every valve.
+    fn distances(&self, from: usize) -> Vec<u32> {
+        let mut distances = vec![u32::MAX; self.names.len()];
+        let mut queue = VecDeque::from([from]);
+        distances[from] = 0;
+
+        while let Some(valve) = queue.pop_front() {
+            for &next in self.tunnels[valve].iter() {
+                if distances[next] == u32::MAX {
+                    distances[next] = distances[valve] + 1;
+                    queue.push_back(next);
+                }
+            }
         }
+
+        distances
     }
+}
 
-    let mut network: AdjGraph = HashMap::new();
-    let mut valve_rates: ValveRates = HashMap::new();
+/// Actor walking through the tunnels and opening valves.
+#[derive(Clone, Debug)]
+pub struct Agent {
+    pub start: String,
+    pub minutes: u32,
+}
 
-    for (valve_name, valve_rate, list) in input_data.into_iter() {
-        valve_rates.insert(valve_name.clone(), valve_rate);
-        network.insert(valve_name, HashSet::from_iter(list));
+impl Agent {
+    pub fn new(start: &str, minutes: u32) -> Self {
+        Agent { start: start.to_string(), minutes }
     }
+}
 
-    (network, valve_rates)
+/// Valve opened by an agent after `minute` minutes of its time budget, from then on it releases pressure.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Opening {
+    pub valve: String,
+    pub minute: u32,
 }
 
-fn dijkstra(graph: &AdjGraph, source_name: &str) -> HashMap<String, u32> {
-    let mut dist = HashMap::new();
-    let mut prev = HashMap::new();
-    let mut queue = HashSet::new();
+#[derive(Debug)]
+pub struct Plan {
+    pub pressure: u32,
+    /// valves opened by each agent, in the order of the agents
+    pub schedules: Vec<Vec<Opening>>,
+}
 
-    for (name, _adj_list) in graph.iter() {
-        dist.insert(name.clone(), u32::MAX);
-        prev.insert(name.clone(), "".to_string());
-        queue.insert(name.clone());
-    }
-    *dist.get_mut(source_name).unwrap() = 0;
+/// Best schedule of one agent for every subset of the working valves, as a bitmask.
+struct SubsetPlans {
+    pressure: Vec<u32>,
+    /// openings as (index of the working valve, minute)
+    schedule: Vec<Vec<(usize, u32)>>,
+}
 
-    while !queue.is_empty() {
-        let vertex_name = &queue.iter()
-            .min_by(|&name1, &name2| (dist[name1]).cmp(&dist[name2]))
-            .unwrap()
-            .clone();
+/// Planner over the valves with non-zero flow rate, which are the only ones worth walking to.
+pub struct Planner {
+    network: Network,
+    /// valves with non-zero flow rate, their positions are the bits of the subsets
+    working: Vec<usize>,
+}
+
+impl Planner {
+    pub fn new(input: &str) -> Self {
+        let network = parse_input(input);
+        let working = (0..network.names.len()).filter(|&valve| network.rates[valve] > 0).collect::<Vec<_>>();
+        assert!(working.len() < usize::BITS as usize, "too many working valves");
 
-        queue.remove(vertex_name);
+        Planner { network, working }
+    }
 
-        for neighbor in graph[vertex_name].intersection(&queue) {
-            let current_dist = dist[vertex_name] + 1;
+    /// Best pressure and schedule of the agent opening only valves of each subset, found by walking all schedules.
+    fn subset_plans(&self, agent: &Agent) -> SubsetPlans {
+        let start = self.network.index(&agent.start);
+        let distances = self.working.iter().map(|&valve| self.network.distances(valve)).collect::<Vec<_>>();
+        let from_start = self.network.distances(start);
+
+        let mut plans = SubsetPlans {
+            pressure: vec![0; 1 << self.working.len()],
+            schedule: vec![Vec::new(); 1 << self.working.len()],
+        };
+
+        // (opened subset, minute, pressure, openings), the last opening is where the agent is
+        let mut stack = vec![(0_usize, 0_u32, 0_u32, Vec::<(usize, u32)>::new())];
+        while let Some((opened, minute, pressure, openings)) = stack.pop() {
+            if pressure > plans.pressure[opened] {
+                plans.pressure[opened] = pressure;
+                plans.schedule[opened] = openings.clone();
+            }
 
-            if current_dist < dist[neighbor] {
-                *dist.get_mut(neighbor).unwrap() = current_dist;
-                prev.insert(neighbor.clone(), vertex_name.clone());
+            for (bit, &valve) in self.working.iter().enumerate() {
+                let distance = match openings.last() {
+                    Some(&(last, _)) => distances[last][valve],
+                    None => from_start[valve],
+                };
+                if opened & (1 << bit) != 0 || distance == u32::MAX || minute + distance + 1 >= agent.minutes {
+                    continue;
+                }
+
+                let opened_at = minute + distance + 1;
+                let mut next_openings = openings.clone();
+                next_openings.push((bit, opened_at));
+                stack.push((
+                    opened | (1 << bit),
+                    opened_at,
+                    pressure + self.network.rates[valve] * (agent.minutes - opened_at),
+                    next_openings,
+                ));
             }
         }
+
+        plans
     }
 
-    dist
-}
+    /// Best pressure of the agents, each opening different valves, and their schedules.
+    ///
+    /// Each agent's best pressure for every subset is extended to the best one within every subset,
+    /// then the subsets are split between the agents one by one.
+    pub fn plan(&self, agents: &[Agent]) -> Plan {
+        let all = (1_usize << self.working.len()) - 1;
+
+        // best[mask] = best pressure of the agents so far within the subset, split[k][mask] = subset given to agent k
+        let mut best = vec![0_u32; all + 1];
+        let mut splits: Vec<Vec<usize>> = Vec::new();
+        let mut agent_plans = Vec::new();
+
+        for (k, agent) in agents.iter().enumerate() {
+            let plans = self.subset_plans(agent);
+            let (within, within_source) = best_within_subsets(&plans.pressure);
+            let is_last = k + 1 == agents.len();
+
+            let mut next_best = vec![0_u32; all + 1];
+            let mut split = vec![0_usize; all + 1];
+            for mask in 0..=all {
+                // only the whole set is needed from the last agent
+                if is_last && mask != all {
+                    continue;
+                }
+
+                let mut subset = mask;
+                loop {
+                    let pressure = within[subset] + if k == 0 { 0 } else { best[mask ^ subset] };
+                    if pressure > next_best[mask] || (subset == mask && k == 0) {
+                        next_best[mask] = pressure;
+                        split[mask] = subset;
+                    }
+                    if subset == 0 || k == 0 {
+                        break;
+                    }
+                    subset = (subset - 1) & mask;
+                }
+            }
 
-fn make_complete_graph(input_graph: &AdjGraph, rates: &ValveRates) -> (MatGraph, HashMap<String, usize>) {
-    let non_zero_vertices_count = input_graph.keys().filter(|&key| rates[key] != 0 || key == "AA").count();
-    let mut matrix = vec![vec![0_u32; non_zero_vertices_count]; non_zero_vertices_count];
-    let mut name_to_index_mappings = HashMap::new();
+            best = next_best;
+            splits.push(split.into_iter().map(|subset| within_source[subset]).collect());
+            agent_plans.push(plans);
+        }
 
-    let non_zero_vertices = input_graph.keys()
-        .filter(|&key| rates[key] != 0 || key == "AA")
-        .collect::<Vec<_>>();
+        // walk the splits back from the last agent
+        let mut schedules = vec![Vec::new(); agents.len()];
+        let mut remaining = all;
+        for k in (0..agents.len()).rev() {
+            let subset = splits[k][remaining];
+            schedules[k] = agent_plans[k].schedule[subset].iter()
+                .map(|&(bit, minute)| Opening { valve: self.network.names[self.working[bit]].clone(), minute })
+                .collect();
+            remaining &= !subset;
+        }
 
-    for (index, vertex) in non_zero_vertices.iter().enumerate() {
-        name_to_index_mappings.insert((*vertex).clone(), index);
+        Plan { pressure: best[all], schedules }
     }
+}
 
-    for (row_vertex, row_index) in name_to_index_mappings.iter() {
-        let shortest_paths = dijkstra(input_graph, row_vertex);
-
-        for (col_vertex, col_index) in name_to_index_mappings.iter() {
-            matrix[*row_index][*col_index] = shortest_paths[col_vertex];
+/// For every subset the best value of its subsets and the subset with that value.
+fn best_within_subsets(values: &[u32]) -> (Vec<u32>, Vec<usize>) {
+    let mut best = values.to_vec();
+    let mut source = (0..values.len()).collect::<Vec<_>>();
+
+    let bits = values.len().trailing_zeros();
+    for bit in 0..bits {
+        for mask in 0..values.len() {
+            if mask & (1 << bit) != 0 && best[mask ^ (1 << bit)] > best[mask] {
+                best[mask] = best[mask ^ (1 << bit)];
+                source[mask] = source[mask ^ (1 << bit)];
+            }
         }
     }
 
-    (matrix, name_to_index_mappings)
+    (best, source)
 }
 
-#[allow(dead_code)]
-fn bfs(
-    graph: &MatGraph,
-    starting_node: usize,
-    my_minutes_left: u32,
-    my_opened_valves: Vec<(usize, u32)>,
-    rates: &HashMap<usize, u32>,
-) -> Option<u32> {
-    let mut can_go_anywhere = false;
-    let mut inner_results = Vec::new();
-    let (my_current_node, _my_last_minute) = my_opened_valves.last().unwrap();
-
-    for (neighbor_index, dist) in graph[*my_current_node].iter().enumerate() {
-        if neighbor_index == starting_node
-            || neighbor_index == *my_current_node
-            || *dist + 1 > my_minutes_left
-            || my_opened_valves.iter().map(|(idx, _)| idx).contains(&neighbor_index) {
-            continue;
-        }
-        can_go_anywhere = true;
-
-        let new_minutes_left = my_minutes_left - dist - 1;
-        let new_opened_valves = my_opened_valves.clone()
-            .into_iter()
-            .chain(iter::once((neighbor_index, 30 - new_minutes_left)))
-            .collect::<Vec<_>>();
-
-        let result = bfs(
-            graph,
-            starting_node,
-            new_minutes_left,
-            new_opened_valves,
-            rates);
-
-        if let Some(pressure) = result {
-            inner_results.push(pressure)
-        }
-    }
+pub fn proboscidea_volcanium_part_1(file_name: &str) -> u32 {
+    proboscidea_volcanium_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-    if !can_go_anywhere {
-        let mut sum = 0;
-        for (valve_index, minute) in my_opened_valves.iter() {
-            let valve_rate = rates[valve_index];
-            sum += valve_rate * (30 - minute);
-        }
+pub fn proboscidea_volcanium_part_1_from_str(input: &str) -> u32 {
+    let plan = Planner::new(input).plan(&[Agent::new("AA", 30)]);
+    println!("{:?}", plan.schedules);
 
-        return Some(sum);
-    }
+    plan.pressure
+}
 
-    Some(*inner_results.iter().max().unwrap())
+pub fn proboscidea_volcanium_part_2(file_name: &str) -> u32 {
+    proboscidea_volcanium_part_2_from_str(&fs::read_to_string(file_name).unwrap())
 }
 
-fn traverse(
-    graph: &MatGraph,
-    minutes_left: u32,
-    mut opened_valves: Vec<(usize, u32)>,
-    rates: &HashMap<usize, u32>,
-) -> Option<u32> {
-    let mut inner_results = Vec::new();
-    let (current_node, _my_last_minute) = opened_valves.last().unwrap();
-
-    let mut possible_destinations = Vec::new();
-    for (neighbor_index, dist) in graph[*current_node].iter().enumerate() {
-        if neighbor_index == *current_node
-            || *dist + 1 > minutes_left
-            || opened_valves.iter().map(|(idx, _)| idx).contains(&neighbor_index) {
-            continue;
-        }
-        possible_destinations.push((neighbor_index, dist));
-    }
-    if !possible_destinations.is_empty() { // select best destination
-        possible_destinations.sort_by(|(node1, dist1), (node2, dist2)| {
-            ((minutes_left - *dist1 - 1) * rates[node1]).cmp(&((minutes_left - *dist2 - 1) * rates[node2]))
-        });
-
-        print!("possible destination:\t");
-        for dest in possible_destinations.iter() {
-            print!("{:?} /{}/  ", dest, (minutes_left - dest.1 - 1) * rates[&dest.0])
-        }
-        println!();
+pub fn proboscidea_volcanium_part_2_from_str(input: &str) -> u32 {
+    let plan = Planner::new(input).plan(&[Agent::new("AA", 26), Agent::new("AA", 26)]);
+    println!("{:?}", plan.schedules);
 
-        let (next_node_index, next_node_dist) = *possible_destinations.last().unwrap();
-        let minutes_after = minutes_left - *next_node_dist - 1;
+    plan.pressure
+}
 
-        opened_valves.push((next_node_index, minutes_after));
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
-        let result = traverse(graph, minutes_after, opened_valves, rates);
+    use crate::input_generator::valve_network;
 
-        if let Some(pressure) = result {
-            inner_results.push(pressure)
-        }
-    } else {
-        let mut sum = 0;
-        for (valve_index, minute) in opened_valves.iter() {
-            let valve_rate = rates[valve_index];
-            sum += valve_rate * (30 - minute);
-        }
+    use super::*;
 
-        println!("opened valves: {:?}", opened_valves);
+    #[test]
+    fn part_1_input_example() {
+        let answer = proboscidea_volcanium_part_1("inputs/16_input_example.txt");
 
-        return Some(sum);
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 1651);
     }
 
-    Some(*inner_results.iter().max().unwrap())
-}
+    #[test]
+    fn part_1_input() {
+        let answer = proboscidea_volcanium_part_1("inputs/16_input.txt");
 
-pub fn proboscidea_volcanium_part_1(file_name: &str) -> u32 {
-    let (input_graph, valve_rates) = read_input(file_name);
-    let (complete_graph, mappings) = make_complete_graph(&input_graph, &valve_rates);
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 1737);
+    }
 
-    print(&complete_graph, &mappings);
+    #[test]
+    fn part_2_input_example() {
+        let answer = proboscidea_volcanium_part_2("inputs/16_input_example.txt");
 
-    let start = mappings.get("AA").unwrap();
-    let rates_translated: HashMap<usize, u32> = translate(&valve_rates, &mappings);
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 1707);
+    }
 
-    let r = traverse(
-        &complete_graph,
-        30,
-        vec![(*start, 30)],
-        &rates_translated);
+    #[test]
+    fn part_2_input() {
+        let answer = proboscidea_volcanium_part_2("inputs/16_input.txt");
 
-    r.unwrap()
-}
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 2216);
+    }
 
-fn translate(rates: &ValveRates, mappings: &HashMap<String, usize>) -> HashMap<usize, u32> {
-    rates.iter()
-        .filter(|&(name, _)| mappings.contains_key(name))
-        .map(|(name, rate)| (mappings[name], *rate))
-        .collect::<HashMap<_, _>>()
-}
+    #[test]
+    fn part_1_generated_input() {
+        let input = valve_network(&mut StdRng::seed_from_u64(16), 30, 10);
+        let answer = proboscidea_volcanium_part_1_from_str(&input);
 
-// pub fn proboscidea_volcanium_part_1(file_name: &str) -> u32 {
-//     let (input_graph, valve_rates) = read_input(file_name);
-//     let (complete_graph, mappings) = make_complete_graph(&input_graph, &valve_rates);
-//
-//     print(&complete_graph, &mappings);
-//
-//     let start = mappings.get("AA").unwrap();
-//     let rates_translated: HashMap<usize, u32> = translate(&valve_rates, &mappings);
-//
-//     let r = bfs(
-//         &complete_graph,
-//         *start,
-//         30,
-//         vec![(*start, 30)],
-//         &rates_translated);
-//
-//     r.unwrap()
-// }
-
-fn print(matrix: &MatGraph, name_mappings: &HashMap<String, usize>) {
-    let pairs_in_order = name_mappings.iter()
-        .sorted_by(|&(_, i1), &(_, i2)| i1.cmp(i2))
-        .collect::<Vec<_>>();
+        println!("part 1 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 1962);
+    }
+
+    #[test]
+    fn part_2_generated_input() {
+        let input = valve_network(&mut StdRng::seed_from_u64(16), 30, 10);
+        let answer = proboscidea_volcanium_part_2_from_str(&input);
 
-    print!("   ");
-    for (name, _index) in pairs_in_order.iter() {
-        print!("{} ", *name)
+        println!("part 2 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 2207);
     }
-    println!();
 
-    for (row_name, row_index) in pairs_in_order.iter() {
-        print!("{} ", row_name);
-        for (_col_name, col_index) in pairs_in_order.iter() {
-            print!("{:2} ", matrix[**row_index][**col_index]);
+    /// Pressure released by the schedules, checking that they can be walked and open every valve once.
+    fn replay(input: &str, agents: &[Agent], schedules: &[Vec<Opening>]) -> u32 {
+        let network = parse_input(input);
+        let mut opened = Vec::new();
+        let mut pressure = 0;
+
+        for (agent, schedule) in agents.iter().zip(schedules) {
+            let (mut position, mut minute) = (network.index(&agent.start), 0);
+            for opening in schedule {
+                let valve = network.index(&opening.valve);
+                assert!(!opened.contains(&valve));
+                assert_eq!(opening.minute, minute + network.distances(position)[valve] + 1);
+                assert!(opening.minute < agent.minutes);
+
+                pressure += network.rates[valve] * (agent.minutes - opening.minute);
+                opened.push(valve);
+                (position, minute) = (valve, opening.minute);
+            }
         }
-        println!()
+
+        pressure
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn schedules_input_example() {
+        let input = fs::read_to_string("inputs/16_input_example.txt").unwrap();
+        let agents = [Agent::new("AA", 26), Agent::new("AA", 26)];
+        let plan = Planner::new(&input).plan(&agents);
+
+        assert_eq!(replay(&input, &agents, &plan.schedules), 1707);
+        assert_eq!(plan.schedules.iter().map(Vec::len).sum::<usize>(), 6);
+    }
+
+    #[test]
+    fn three_agents_with_own_budgets() {
+        let input = fs::read_to_string("inputs/16_input.txt").unwrap();
+        let agents = [Agent::new("AA", 20), Agent::new("AA", 16), Agent::new("EJ", 12)];
+        let plan = Planner::new(&input).plan(&agents);
+
+        assert_eq!(replay(&input, &agents, &plan.schedules), plan.pressure);
+        assert!(plan.pressure >= Planner::new(&input).plan(&agents[..2]).pressure);
+    }
+}
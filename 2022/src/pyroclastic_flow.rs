@@ -1,505 +1,330 @@
-#![allow(dead_code)]
-
-use std::collections::{HashMap, HashSet};
-use std::fmt::Debug;
+use std::collections::HashMap;
 use std::fs;
 
-fn read_input(file_name: &str) -> Vec<Direction> {
-    fs::read_to_string(file_name)
-        .unwrap()
-        .chars()
-        .map(|ch| if ch == '>' { Direction::Right } else { Direction::Left })
-        .collect::<Vec<_>>()
-}
-
-#[derive(Copy, Clone, Debug, Default)]
-struct Point {
-    col: u32,
-    row: u32,
-}
-
-#[derive(Copy, Clone, Debug, PartialEq)]
-enum Rock {
-    Dash,
-    Plus,
-    Knee,
-    Pipe,
-    Square,
-}
+/// Rocks of the puzzle in the order they fall, separated by empty lines.
+pub const ROCK_SHAPES: &str = "\
+####
 
-/// The origin of each rock is located in left-down corner of its 'box'
-/// In the case of Rock::Plus it is outside of the shape.
-/// X###
-///
-/// .#.
-/// ###
-/// X#.
-///
-/// ..#
-/// ..#
-/// X##
-///
-/// #
-/// #
-/// #
-/// X
-///
-/// ##
-/// X#
-impl Rock {
-    fn get_collision_points_from_below(self, pos: Point) -> Vec<Point> {
-        match self {
-            Rock::Dash => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row },
-                    Point { col: pos.col + 1, row: pos.row },
-                    Point { col: pos.col + 2, row: pos.row },
-                    Point { col: pos.col + 3, row: pos.row },
-                ])
-            }
-            Rock::Plus => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row + 1 },
-                    Point { col: pos.col + 1, row: pos.row },
-                    Point { col: pos.col + 2, row: pos.row + 1 },
-                ])
-            }
-            Rock::Knee => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row },
-                    Point { col: pos.col + 1, row: pos.row },
-                    Point { col: pos.col + 2, row: pos.row },
-                ])
-            }
-            Rock::Pipe => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row },
-                ])
-            }
-            Rock::Square => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row },
-                    Point { col: pos.col + 1, row: pos.row },
-                ])
-            }
-        }
-    }
+.#.
+###
+.#.
 
-    fn get_collision_points_from_right(self, pos: Point) -> Vec<Point> {
-        match self {
-            Rock::Dash => {
-                Vec::from([
-                    Point { col: pos.col + 3, row: pos.row },
-                ])
-            }
-            Rock::Plus => {
-                Vec::from([
-                    Point { col: pos.col + 1, row: pos.row },
-                    Point { col: pos.col + 2, row: pos.row + 1 },
-                    Point { col: pos.col + 1, row: pos.row + 2 },
-                ])
-            }
-            Rock::Knee => {
-                Vec::from([
-                    Point { col: pos.col + 2, row: pos.row },
-                    Point { col: pos.col + 2, row: pos.row + 1 },
-                    Point { col: pos.col + 2, row: pos.row + 2 },
-                ])
-            }
-            Rock::Pipe => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row },
-                    Point { col: pos.col, row: pos.row + 1 },
-                    Point { col: pos.col, row: pos.row + 2 },
-                    Point { col: pos.col, row: pos.row + 3 },
-                ])
-            }
-            Rock::Square => {
-                Vec::from([
-                    Point { col: pos.col + 1, row: pos.row },
-                    Point { col: pos.col + 1, row: pos.row + 1 },
-                ])
-            }
-        }
-    }
+..#
+..#
+###
 
-    fn get_collision_points_from_left(self, pos: Point) -> Vec<Point> {
-        match self {
-            Rock::Dash => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row },
-                ])
-            }
-            Rock::Plus => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row + 1 },
-                    Point { col: pos.col + 1, row: pos.row },
-                    Point { col: pos.col + 1, row: pos.row + 2 },
-                ])
-            }
-            Rock::Knee => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row },
-                    Point { col: pos.col + 2, row: pos.row + 1 },
-                    Point { col: pos.col + 2, row: pos.row + 2 },
-                ])
-            }
-            Rock::Pipe => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row },
-                    Point { col: pos.col, row: pos.row + 1 },
-                    Point { col: pos.col, row: pos.row + 2 },
-                    Point { col: pos.col, row: pos.row + 3 },
-                ])
-            }
-            Rock::Square => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row },
-                    Point { col: pos.col, row: pos.row + 1 },
-                ])
-            }
-        }
-    }
+#
+#
+#
+#
 
-    fn get_structural_points(self, pos: Point) -> Vec<Point> {
-        match self {
-            Rock::Dash => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row },
-                    Point { col: pos.col + 1, row: pos.row },
-                    Point { col: pos.col + 2, row: pos.row },
-                    Point { col: pos.col + 3, row: pos.row },
-                ])
-            }
-            Rock::Plus => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row + 1 },
-                    Point { col: pos.col + 1, row: pos.row },
-                    Point { col: pos.col + 1, row: pos.row + 1 },
-                    Point { col: pos.col + 1, row: pos.row + 2 },
-                    Point { col: pos.col + 2, row: pos.row + 1 },
-                ])
-            }
-            Rock::Knee => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row },
-                    Point { col: pos.col + 1, row: pos.row },
-                    Point { col: pos.col + 2, row: pos.row },
-                    Point { col: pos.col + 2, row: pos.row + 1 },
-                    Point { col: pos.col + 2, row: pos.row + 2 },
-                ])
-            }
-            Rock::Pipe => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row },
-                    Point { col: pos.col, row: pos.row + 1 },
-                    Point { col: pos.col, row: pos.row + 2 },
-                    Point { col: pos.col, row: pos.row + 3 },
-                ])
-            }
-            Rock::Square => {
-                Vec::from([
-                    Point { col: pos.col, row: pos.row },
-                    Point { col: pos.col, row: pos.row + 1 },
-                    Point { col: pos.col + 1, row: pos.row },
-                    Point { col: pos.col + 1, row: pos.row + 1 },
-                ])
-            }
-        }
-    }
+##
+##
+";
 
-    fn get_edge_pos(self) -> (u32, u32) {
-        match self {
-            Rock::Dash => (0, 3),
-            Rock::Plus => (0, 4),
-            Rock::Knee => (0, 4),
-            Rock::Pipe => (0, 6),
-            Rock::Square => (0, 5),
-        }
-    }
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Jet {
+    Left,
+    Right,
 }
 
-#[derive(Debug)]
-struct RocksLoopedIterator {
-    rocks: [Rock; 5],
-    current_index: usize,
-    total_index: usize,
+fn parse_input(input: &str) -> Vec<Jet> {
+    input
+        .trim()
+        .chars()
+        .map(|ch| match ch {
+            '<' => Jet::Left,
+            '>' => Jet::Right,
+            _ => panic!("invalid jet: {ch}"),
+        })
+        .collect()
 }
 
-impl Default for RocksLoopedIterator {
-    fn default() -> Self {
-        Self {
-            rocks: [Rock::Dash, Rock::Plus, Rock::Knee, Rock::Pipe, Rock::Square],
-            current_index: 0,
-            total_index: 0,
-        }
-    }
+/// Rock as bitmask rows from the bottom up, column `c` of the rock is bit `c`.
+#[derive(Clone, Debug)]
+pub struct Shape {
+    rows: Vec<u8>,
+    width: usize,
 }
 
-impl Iterator for RocksLoopedIterator {
-    type Item = Rock;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let rock = self.rocks[self.current_index];
-
-        if self.current_index == self.rocks.len() - 1 {
-            self.current_index = 0;
-        } else {
-            self.current_index += 1;
-        }
-
-        self.total_index += 1;
-        Some(rock)
+impl Shape {
+    /// Shape drawn with `#` and `.`, top row first, as in the puzzle.
+    pub fn parse(drawing: &str) -> Self {
+        let rows = drawing
+            .lines()
+            .rev()
+            .map(|line| {
+                assert!(line.len() <= 8, "rocks can be at most 8 units wide");
+                line.chars().enumerate().fold(0_u8, |row, (col, ch)| match ch {
+                    '#' => row | 1 << col,
+                    '.' => row,
+                    _ => panic!("invalid rock tile: {ch}"),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let width = 8 - rows.iter().fold(0, |all, row| all | row).leading_zeros() as usize;
+        assert!(width > 0, "empty rock");
+
+        Shape { rows, width }
     }
 }
 
-#[derive(Debug)]
-enum Direction {
-    Left,
-    Right,
+/// Shapes separated by empty lines.
+pub fn parse_shapes(drawings: &str) -> Vec<Shape> {
+    drawings
+        .split("\n\n")
+        .filter(|drawing| !drawing.trim().is_empty())
+        .map(|drawing| Shape::parse(drawing.trim_matches('\n')))
+        .collect()
 }
 
-#[derive(Debug)]
-struct FallingRock {
-    rock: Rock,
-    pos: Point,
-    falling_time: usize,
+/// Chamber of at most 8 units width as bitmask rows from the floor up, column `c` is bit `c`.
+/// Shapes and jets repeat in their order, `rock` and `jet` point at the next ones.
+pub struct Chamber {
+    width: usize,
+    rows: Vec<u8>,
+    shapes: Vec<Shape>,
+    jets: Vec<Jet>,
+    rock: usize,
+    jet: usize,
 }
 
-fn move_rock_down(rock: &mut FallingRock) {
-    rock.pos.row -= 1;
-    rock.falling_time += 1;
+impl Chamber {
+    pub fn new(width: usize, shapes: Vec<Shape>, jets: &str) -> Self {
+        assert!(width <= 8, "chamber can be at most 8 units wide");
+        assert!(shapes.iter().all(|shape| shape.width + 2 <= width), "rocks have to fit next to the left gap");
 
-    // println!("move down to position: {:?}", rock.pos);
-}
+        let jets = parse_input(jets);
+        assert!(!shapes.is_empty() && !jets.is_empty(), "no rocks or no jets");
 
-fn is_collision(columns: &HashMap<u32, HashSet<u32>>, falling_rock: &mut FallingRock) -> bool {
-    for possible_collision_point in falling_rock.rock.get_collision_points_from_below(falling_rock.pos).iter() {
-        if possible_collision_point.row == 0 {
-            return true;
-        }
+        Chamber { width, rows: Vec::new(), shapes, jets, rock: 0, jet: 0 }
+    }
 
-        if columns[&possible_collision_point.col].contains(&(possible_collision_point.row - 1)) {
-            return true;
-        }
+    pub fn height(&self) -> usize {
+        self.rows.len()
     }
 
-    false
-}
+    /// Whether the shape with its bottom left corner at given column and row overlaps rock or the floor.
+    fn collides(&self, shape: &Shape, col: usize, row: usize) -> bool {
+        shape.rows.iter()
+            .enumerate()
+            .any(|(i, &mask)| self.rows.get(row + i).is_some_and(|&rock| rock & mask << col != 0))
+    }
 
-fn push_rock(direction: &Direction, columns: &HashMap<u32, HashSet<u32>>, falling_rock: &mut FallingRock) {
-    let (left_edge_pos, right_edge_pos) = falling_rock.rock.get_edge_pos();
+    /// Lets the next rock fall until it comes to rest, two units from the left wall and three above the highest rock.
+    pub fn drop_rock(&mut self) {
+        let shape = &self.shapes[self.rock];
+        let (mut col, mut row) = (2_usize, self.rows.len() + 3);
 
-    match direction {
-        Direction::Left => {
-            if falling_rock.pos.col == left_edge_pos {
-                return;
-            }
+        loop {
+            let pushed = match self.jets[self.jet] {
+                Jet::Left => col.checked_sub(1),
+                Jet::Right => Some(col + 1).filter(|&col| col + shape.width <= self.width),
+            };
+            self.jet = (self.jet + 1) % self.jets.len();
 
-            let possible_collision_points = falling_rock.rock.get_collision_points_from_left(falling_rock.pos);
-            for p in possible_collision_points.iter() {
-                if columns[&(p.col - 1)].contains(&p.row) {
-                    return;
-                }
+            if let Some(pushed) = pushed.filter(|&pushed| !self.collides(shape, pushed, row)) {
+                col = pushed;
             }
-
-            falling_rock.pos.col -= 1;
-            // println!("push rock to the left to position: {:?}", falling_rock.pos);
+            if row == 0 || self.collides(shape, col, row - 1) {
+                break;
+            }
+            row -= 1;
         }
-        Direction::Right => {
-            if falling_rock.pos.col == right_edge_pos {
-                return;
+
+        for (i, &mask) in shape.rows.iter().enumerate() {
+            if self.rows.len() <= row + i {
+                self.rows.push(0);
             }
+            self.rows[row + i] |= mask << col;
+        }
+        self.rock = (self.rock + 1) % self.shapes.len();
+    }
 
-            let possible_collision_points = falling_rock.rock.get_collision_points_from_right(falling_rock.pos);
-            for p in possible_collision_points.iter() {
-                if columns[&(p.col + 1)].contains(&p.row) {
-                    return;
+    /// Cells that falling rocks can still reach, row by row from the top down to the last reachable row.
+    /// Rocks only move sideways and down, so a cell is reachable if it is empty and next to or below
+    /// a reachable one. The rest of the chamber cannot influence any rock anymore.
+    fn surface(&self) -> Vec<u8> {
+        let all = ((1_u16 << self.width) - 1) as u8;
+        let mut surface = Vec::new();
+        let mut above = all;
+
+        for &rock in self.rows.iter().rev() {
+            let free = all & !rock;
+            let mut reachable = above & free;
+            loop {
+                let spread = (reachable | reachable << 1 | reachable >> 1) & free;
+                if spread == reachable {
+                    break;
                 }
+                reachable = spread;
             }
 
-            falling_rock.pos.col += 1;
-            // println!("push rock to the right to position: {:?}", falling_rock.pos);
+            if reachable == 0 {
+                break;
+            }
+            surface.push(reachable);
+            above = reachable;
         }
-    }
-}
-
 
-#[derive(Debug)]
-struct HistoryInfo {
-    height: usize,
-    rocks_count: usize,
-}
+        surface
+    }
 
-pub fn pyroclastic_flow(file_name: &str, rocks_number: usize) -> usize {
-    let input = read_input(file_name);
+    /// Height of the tower after given number of rocks fell, counted from the start of the chamber.
+    ///
+    /// Once the next rock, the next jet and the surface repeat, so does everything after, which lets the
+    /// simulation skip the whole cycles between then and the last rock.
+    pub fn tower_height(&mut self, rocks: u64) -> u64 {
+        // (rock, jet, surface) -> (rocks dropped, height)
+        let mut seen: HashMap<(usize, usize, Vec<u8>), (u64, u64)> = HashMap::new();
+        let mut skipped_height = 0;
+        let mut dropped = 0;
+
+        while dropped < rocks {
+            if skipped_height == 0 {
+                let state = (self.rock, self.jet, self.surface());
+                if let Some(&(cycle_start, start_height)) = seen.get(&state) {
+                    let (cycle_rocks, cycle_height) = (dropped - cycle_start, self.height() as u64 - start_height);
+                    let cycles = (rocks - dropped) / cycle_rocks;
+
+                    dropped += cycles * cycle_rocks;
+                    skipped_height = cycles * cycle_height;
+                    if dropped == rocks {
+                        break;
+                    }
+                } else {
+                    seen.insert(state, (dropped, self.height() as u64));
+                }
+            }
 
-    let mut columns: HashMap<u32, HashSet<u32>> = HashMap::new();
+            self.drop_rock();
+            dropped += 1;
+        }
 
-    for x in 0..=6 {
-        columns.insert(x, HashSet::from([0]));
+        self.height() as u64 + skipped_height
     }
 
-    // simulation variables
-    let mut rocks_iter = RocksLoopedIterator::default();
-    let mut direction_counter: usize = 0;
-    let mut rocks_left = rocks_number;
-    let mut falling_rock_op: Option<FallingRock> = None;
-
-    // repetition detection variables
-    let mut repetition_detected = false;
-    let mut iteration_history: Vec<HistoryInfo> = vec![HistoryInfo { height: 0, rocks_count: 0 }];
-    let mut tower_height_to_add = 0_usize;
-
-    loop {
-        // looking for pattern repetition
-        if !repetition_detected && direction_counter > 0 && direction_counter % input.len() == 0 {
-            let new_height = *columns.values()
-                .map(|set| set.iter().max().unwrap())
-                .max()
-                .unwrap();
-
-            iteration_history.push(HistoryInfo {
-                height: new_height as usize,
-                rocks_count: rocks_iter.total_index,
-            });
-
-            if let Some((height_in_pattern, rocks_in_pattern)) = analyze_iteration_history(&iteration_history) {
-                repetition_detected = true;
-                let pattern_repetition_numer = rocks_left / rocks_in_pattern;
-
-                tower_height_to_add = pattern_repetition_numer * height_in_pattern;
-                rocks_left -= pattern_repetition_numer * rocks_in_pattern - 1;
-            }
-        }
+    /// Chamber with its walls and floor, as in the puzzle.
+    pub fn render(&self) -> String {
+        let mut lines = self.rows.iter()
+            .rev()
+            .map(|&row| {
+                let tiles = (0..self.width).map(|col| if row & 1 << col != 0 { '#' } else { '.' }).collect::<String>();
+                format!("|{tiles}|")
+            })
+            .collect::<Vec<_>>();
+        lines.push(format!("+{}+", "-".repeat(self.width)));
+
+        lines.join("\n") + "\n"
+    }
+}
 
-        // spawning new rock
-        if falling_rock_op.is_none() {
-            let max_row = *columns.values()
-                .map(|set| set.iter().max().unwrap())
-                .max()
-                .unwrap() + 4;
+pub fn pyroclastic_flow(file_name: &str, rocks_number: u64) -> u64 {
+    pyroclastic_flow_from_str(&fs::read_to_string(file_name).unwrap(), rocks_number)
+}
 
-            let spawn_pos = Point {
-                col: 2,
-                row: max_row,
-            };
+pub fn pyroclastic_flow_from_str(input: &str, rocks_number: u64) -> u64 {
+    Chamber::new(7, parse_shapes(ROCK_SHAPES), input).tower_height(rocks_number)
+}
 
-            let new_falling_rock = FallingRock {
-                rock: rocks_iter.next().unwrap(),
-                falling_time: 0,
-                pos: spawn_pos,
-            };
-            // println!("spawned {} - {:?}", rocks_counter, new_falling_rock);
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
-            falling_rock_op = Some(new_falling_rock);
-            rocks_left -= 1;
-        }
+    use crate::input_generator::jet_pattern;
 
-        let falling_rock = falling_rock_op.as_mut().unwrap();
-        let direction = &input[direction_counter % input.len()];
+    use super::*;
 
-        push_rock(direction, &columns, falling_rock);
+    #[test]
+    fn part_1_input_example() {
+        let answer = pyroclastic_flow("inputs/17_input_example.txt", 2022);
 
-        let mut collision_occurred = false;
-        if falling_rock.falling_time >= 3 && is_collision(&columns, falling_rock) {
-            collision_occurred = true;
-            // println!("collision detected! rock's position: {:?}", falling_rock.pos);
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 3068);
+    }
 
-            for structural_pos in falling_rock.rock.get_structural_points(falling_rock.pos) {
-                columns.get_mut(&structural_pos.col).map(|val| val.insert(structural_pos.row));
-            }
-        }
+    #[test]
+    fn part_1_input() {
+        let answer = pyroclastic_flow("inputs/17_input.txt", 2022);
 
-        if collision_occurred {
-            falling_rock_op = None;
-        } else {
-            move_rock_down(falling_rock);
-        }
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 3179);
+    }
 
-        if rocks_left == 0 {
-            break;
-        }
+    #[test]
+    fn part_2_input_example() {
+        let answer = pyroclastic_flow("inputs/17_input_example.txt", 1_000_000_000_000);
 
-        direction_counter += 1;
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 1514285714288);
     }
 
-    *columns.values()
-        .map(|set| set.iter().max().unwrap())
-        .max()
-        .unwrap() as usize + tower_height_to_add
-}
+    #[test]
+    fn part_2_input() {
+        let answer = pyroclastic_flow("inputs/17_input.txt", 1_000_000_000_000);
 
-fn print_tower(columns: &mut HashMap<u32, HashSet<u32>>, height: usize) {
-    for i in (0..height as u32).rev() {
-        print!("{}\t", i);
-        for col in 0..7 {
-            if columns[&col].contains(&i) {
-                print!("#");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 1567723342929);
     }
-}
 
-fn analyze_iteration_history(iteration_history: &[HistoryInfo]) -> Option<(usize, usize)> {
-    let diff_vec = iteration_history.windows(2)
-        .map(|infos| {
-            let first_info = &infos[0];
-            let second_info = &infos[1];
-            let height_diff = second_info.height - first_info.height;
-            let rocks_count_diff = second_info.rocks_count - first_info.rocks_count;
+    #[test]
+    fn part_1_generated_input() {
+        let input = jet_pattern(&mut StdRng::seed_from_u64(17), 2000);
+        let answer = pyroclastic_flow_from_str(&input, 2022);
 
-            (height_diff, rocks_count_diff)
-        })
-        .collect::<Vec<_>>();
+        println!("part 1 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 3099);
+    }
 
-    // println!("diff_vec: {:#?}", diff_vec);
+    #[test]
+    fn part_2_generated_input() {
+        let input = jet_pattern(&mut StdRng::seed_from_u64(17), 2000);
+        let answer = pyroclastic_flow_from_str(&input, 1_000_000_000_000);
 
-    let last_entry = diff_vec.last().unwrap();
-    let last_entry_pos = diff_vec.len() - 1;
+        println!("part 2 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 1531343283577);
+    }
 
-    return match diff_vec.iter().rev().skip(1).position(|x| x == last_entry) {
-        None => None,
-        Some(equal_entry_pos_rev) => {
-            let equal_entry_pos = diff_vec.len() - 1 - (equal_entry_pos_rev + 1);
+    #[test]
+    fn render_input_example() {
+        let input = fs::read_to_string("inputs/17_input_example.txt").unwrap();
+        let mut chamber = Chamber::new(7, parse_shapes(ROCK_SHAPES), &input);
 
-            // there must be at least 2x elements (double the pattern length)
-            let offset = last_entry_pos - equal_entry_pos;
-            if diff_vec.len() < 2 * offset {
-                return None;
-            }
+        chamber.drop_rock();
+        assert_eq!(chamber.render(), "|..####.|\n+-------+\n");
 
-            for i in 1..offset {
-                // println!("last_entry_pos: {} equal_entry_pos {}", last_entry_pos, equal_entry_pos);
-                // println!("comparing {:?} with {:?}", diff_vec[last_entry_pos - i], diff_vec[equal_entry_pos - i]);
-                if diff_vec[last_entry_pos - i] != diff_vec[equal_entry_pos - i] {
-                    return None;
-                }
-            }
+        chamber.drop_rock();
+        assert_eq!(chamber.render(), "|...#...|\n|..###..|\n|...#...|\n|..####.|\n+-------+\n");
+    }
 
-            let height_of_pattern = diff_vec.iter().rev().take(offset).map(|(height, _)| *height).sum();
-            let rocks_in_pattern = diff_vec.iter().rev().take(offset).map(|(_, rocks)| *rocks).sum();
+    #[test]
+    fn cycles_match_simulation() {
+        let input = fs::read_to_string("inputs/17_input_example.txt").unwrap();
+        let mut simulated = Chamber::new(7, parse_shapes(ROCK_SHAPES), &input);
 
-            println!("repetition detected - height_of_pattern: {height_of_pattern} and rocks_in_pattern: {rocks_in_pattern}");
-            Some((height_of_pattern, rocks_in_pattern))
+        for rocks in 1..=3000 {
+            simulated.drop_rock();
+            if rocks % 97 == 0 {
+                let height = Chamber::new(7, parse_shapes(ROCK_SHAPES), &input).tower_height(rocks);
+                assert_eq!(height, simulated.height() as u64, "after {rocks} rocks");
+            }
         }
-    };
-}
+    }
 
-fn trim_columns(columns: &mut HashMap<u32, HashSet<u32>>) {
-    let max_row = *columns.values()
-        .map(|set| set.iter().max().unwrap())
-        .max()
-        .unwrap() + 4;
+    #[test]
+    fn custom_shapes_and_width() {
+        let shapes = parse_shapes("#.#\n###\n\n#\n#\n");
+        assert_eq!(shapes[0].width, 3);
+        assert_eq!(shapes[0].rows, vec![0b111, 0b101]);
 
-    for column in columns.values_mut() {
-        for row in column.clone() {
-            if row < max_row - 30 {
-                column.remove(&row);
-            }
+        let input = fs::read_to_string("inputs/17_input_example.txt").unwrap();
+        let mut simulated = Chamber::new(5, shapes.clone(), &input);
+        for _ in 0..1234 {
+            simulated.drop_rock();
         }
+
+        assert_eq!(Chamber::new(5, shapes, &input).tower_height(1234), simulated.height() as u64);
     }
 }
@@ -1,233 +1,323 @@
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 
-use itertools::{Itertools, MinMaxResult};
+use cellular_automaton::{Automaton, DenseGrid, Grid, Point};
+use itertools::Itertools;
 
-fn read_input(file_name: &str) -> Vec<Vec<(u32, u32)>> {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
+const SAND_SOURCE: (i32, i32) = (500, 0);
 
-    let mut input_data = Vec::new();
-
-    loop {
-        let mut buf = String::new();
-        if let Ok(n) = reader.read_line(&mut buf) {
-            if n == 0 {
-                break;
-            }
-
-            let rocks_path = buf.trim()
-                .split(" -> ")
-                .map(|pair| pair.split(',')
-                    .map(|number| number.parse::<u32>().unwrap())
-                    .collect_tuple::<(_, _)>()
-                    .unwrap())
-                .collect::<Vec<_>>();
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default)]
+enum Tile {
+    #[default]
+    Air,
+    Rock,
+    Sand,
+}
 
-            input_data.push(rocks_path)
-        }
-    }
+/// What is below the lowest rock.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Bottom {
+    /// sand falling below the lowest rock is lost
+    Abyss,
+    /// infinite floor given number of rows below the lowest rock
+    Floor(i32),
+}
 
-    input_data
+/// Cave with columns shifted, so the leftmost column fits at index 0.
+/// It is wide enough to hold sand heaped up from every source down to the floor, so the sand never leaves it.
+pub struct Cave {
+    tiles: DenseGrid<Tile>,
+    lowest_rock: i32,
+    bottom: Bottom,
+    sources: Vec<Point>,
 }
 
-/// col_number -> (set of number representing row with rock)
-/// for example data we will have the following mappings:
-/// 494 -> [9]
-/// 495 -> [9]
-/// 496 -> [6,9]
-/// 497 -> [6,9]
-/// 498 -> [4,5,6,9]
-/// 499 -> [9]
-/// 500 -> [9]
-/// 501 -> [9]
-/// 502 -> [4,5,6,7,8,9]
-/// 503 -> [4]
-fn process_input(input: Vec<Vec<(u32, u32)>>) -> HashMap<u32, HashSet<u32>> {
-    let mut map: HashMap<u32, HashSet<u32>> = HashMap::new();
-
-    for line in input.iter() {
-        for (first_pair, second_pair) in line.iter().tuple_windows::<(_, _)>() {
-            process_input_inner(*first_pair, *second_pair, &mut map)
-        }
-    }
+fn parse_input(input: &str) -> Vec<Vec<(i32, i32)>> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.trim()
+            .split(" -> ")
+            .map(|pair| pair.split(',')
+                .map(|number| number.parse::<i32>().unwrap())
+                .collect_tuple::<(_, _)>()
+                .unwrap())
+            .collect::<Vec<_>>())
+        .collect()
+}
 
+impl Cave {
+    /// Cave of the rock paths, with the sources given as (x, y) like the paths.
+    pub fn new(input: &str, sources: &[(i32, i32)], bottom: Bottom) -> Self {
+        let paths = parse_input(input);
+        let lowest_rock = paths.iter().flatten().map(|(_col, row)| *row).max().unwrap();
+        let floor = match bottom {
+            Bottom::Abyss => lowest_rock + 2,
+            Bottom::Floor(depth) => {
+                assert!(depth >= 1, "the floor has to be below the lowest rock");
+                lowest_rock + depth
+            }
+        };
+        assert!(sources.iter().all(|&(_col, row)| (0..floor).contains(&row)), "sources have to be between row 0 and the floor");
 
-    let (min, max) = match map.keys().minmax() {
-        MinMaxResult::MinMax(min, max) => (*min, *max),
-        _ => panic!(),
-    };
+        let (min_col, max_col) = paths.iter().flatten()
+            .map(|(col, _row)| *col)
+            .chain(sources.iter().flat_map(|&(col, row)| [col - (floor - row) - 1, col + (floor - row) + 1]))
+            .minmax()
+            .into_option()
+            .unwrap();
 
-    // add empty edge columns for convenience
-    map.insert(min - 1, HashSet::new());
-    map.insert(max + 1, HashSet::new());
-    map
-}
+        let mut tiles = DenseGrid::new((max_col - min_col + 1) as usize, floor as usize + 1);
 
-fn process_input_inner(first_pair: (u32, u32), second_pair: (u32, u32), data: &mut HashMap<u32, HashSet<u32>>) {
-    if first_pair.0 == second_pair.0 { // vertical path
-        let col_number = first_pair.0;
-        let (min, max) = sort(first_pair.1, second_pair.1);
+        for (&(first_col, first_row), &(second_col, second_row)) in paths.iter().flat_map(|path| path.iter().tuple_windows()) {
+            for row in first_row.min(second_row)..=first_row.max(second_row) {
+                for col in first_col.min(second_col)..=first_col.max(second_col) {
+                    tiles.set(Point::new(row, col - min_col), Tile::Rock);
+                }
+            }
+        }
 
-        for row in min..=max {
-            data.entry(col_number)
-                .and_modify(|set| { set.insert(row); })
-                .or_insert(HashSet::from([row]));
+        if let Bottom::Floor(_) = bottom {
+            for col in 0..tiles.width() as i32 {
+                tiles.set(Point::new(floor, col), Tile::Rock);
+            }
         }
-    } else { // horizontal path
-        let row_number = first_pair.1;
-        let (min, max) = sort(first_pair.0, second_pair.0);
-
-        for col in min..=max {
-            data.entry(col)
-                .and_modify(|set| { set.insert(row_number); })
-                .or_insert(HashSet::from([row_number]));
+
+        Cave {
+            tiles,
+            lowest_rock,
+            bottom,
+            sources: sources.iter().map(|&(col, row)| Point::new(row, col - min_col)).collect(),
         }
     }
-}
 
-fn sort(first: u32, second: u32) -> (u32, u32) {
-    if first < second {
-        (first, second)
-    } else {
-        (second, first)
+    /// Pours sand from the sources one after another, each until it is blocked or its sand falls into the abyss.
+    /// Returns the units of sand that came to rest.
+    pub fn fill(&mut self) -> u32 {
+        match self.bottom {
+            Bottom::Abyss => self.pour(),
+            Bottom::Floor(_) => self.heap_up(),
+        }
     }
-}
 
-fn check_tile((tile_col, tile_row): (u32, u32), obstacles_map: &mut HashMap<u32, HashSet<u32>>, sum: &mut u32) -> bool {
-    println!("checking tile: {:?}", (tile_col, tile_row));
+    /// Pours grain after grain, since which grains fall into the abyss depends on the order they come in.
+    ///
+    /// The path of the last grain is kept as a stack, the next grain follows it up to the tile above
+    /// where the last one came to rest, so it does not have to fall from the source again.
+    fn pour(&mut self) -> u32 {
+        let mut resting = 0;
 
-    let next_row = tile_row + 1;
-
-    if obstacles_map[&(tile_col)].contains(&next_row) { // check tile below
-        let col_to_left = tile_col - 1;
-        let col_to_right = tile_col + 1;
-        let is_left_down_occupied = obstacles_map[&col_to_left].contains(&next_row);
-        let is_right_down_occupied = obstacles_map[&col_to_right].contains(&next_row);
-
-        return match (is_left_down_occupied, is_right_down_occupied) {
-            (true, true) => {
-                fill_and_add(tile_col, tile_row, obstacles_map, sum);
-                true
+        for source in self.sources.clone() {
+            if !self.tiles.is_empty_at(source) {
+                continue;
             }
-            (false, true) => {
-                print!("going left!\t");
-                let result = check_tile((col_to_left, next_row), obstacles_map, sum);
-                if result {
-                    fill_and_add(tile_col, tile_row, obstacles_map, sum);
-                }
 
-                result
-            }
-            (true, false) => {
-                print!("going right!\t");
-                let result = check_tile((col_to_right, next_row), obstacles_map, sum);
-                if result {
-                    fill_and_add(tile_col, tile_row, obstacles_map, sum);
+            let mut path = vec![source];
+            while let Some(&grain) = path.last() {
+                let below = [0, -1, 1].iter()
+                    .map(|d| Point::new(grain.row + 1, grain.col + d))
+                    .find(|below| self.tiles.is_empty_at(*below));
+
+                match below {
+                    Some(below) if below.row > self.lowest_rock => break,
+                    Some(below) => path.push(below),
+                    None => {
+                        self.tiles.set(grain, Tile::Sand);
+                        resting += 1;
+                        path.pop();
+                    }
                 }
-
-                result
             }
-            (false, false) => {
-                print!("going left!\t");
-                let result_left = check_tile((col_to_left, next_row), obstacles_map, sum);
-                if result_left {
-                    print!("going right!\t");
-                    let result_right = check_tile((col_to_right, next_row), obstacles_map, sum);
-                    if result_right {
-                        fill_and_add(tile_col, tile_row, obstacles_map, sum);
-                    }
+        }
 
-                    return result_right;
-                }
+        resting
+    }
 
-                result_left
-            }
-        };
-    } else { // no tile one below
-        match obstacles_map[&tile_col].iter().find(|&rock_row| *rock_row > tile_row) {
-            None => false, // column has no rocks below current tile
-            Some(_) => {
-                print!("going down!\t");
-                if check_tile((tile_col, tile_row + 1), obstacles_map, sum) {
-                    // re-check tile above
-                    print!("going up!\t");
-                    check_tile((tile_col, tile_row), obstacles_map, sum)
-                } else {
-                    false
-                }
+    /// With a floor every source gets blocked, and sand comes to rest on every tile it can fall to,
+    /// so the heap grows row by row: air with sand in any of the 3 tiles above becomes sand.
+    fn heap_up(&mut self) -> u32 {
+        let before = self.tiles.count(&Tile::Sand);
+
+        for &source in &self.sources {
+            if self.tiles.is_empty_at(source) {
+                self.tiles.set(source, Tile::Sand);
             }
         }
+
+        Automaton::synchronous(|tiles: &DenseGrid<Tile>, p, _| match tiles.get(p) {
+            Tile::Air if [-1, 0, 1].iter().any(|d| tiles.get(Point::new(p.row - 1, p.col + d)) == Tile::Sand) => Tile::Sand,
+            tile => tile,
+        }).run_until_stable(&mut self.tiles, usize::MAX);
+
+        (self.tiles.count(&Tile::Sand) - before) as u32
     }
+
+    /// Drawing of the part of the cave holding rocks, sand and sources, in the puzzle's notation.
+    pub fn render(&self) -> String {
+        let (min_col, max_col) = self.tiles.occupied().iter()
+            .chain(self.sources.iter())
+            .filter(|point| self.bottom == Bottom::Abyss || point.row < self.tiles.height() as i32 - 1)
+            .map(|point| point.col)
+            .minmax()
+            .into_option()
+            .unwrap();
+
+        let last_row = match self.bottom {
+            Bottom::Abyss => self.lowest_rock,
+            Bottom::Floor(_) => self.tiles.height() as i32 - 1,
+        };
+
+        (0..=last_row)
+            .map(|row| (min_col..=max_col)
+                .map(|col| match self.tiles.get(Point::new(row, col)) {
+                    Tile::Rock => '#',
+                    Tile::Sand => 'o',
+                    Tile::Air if self.sources.contains(&Point::new(row, col)) => '+',
+                    Tile::Air => '.',
+                })
+                .collect::<String>() + "\n")
+            .collect()
+    }
+
 }
 
-fn fill_and_add(tile_col: u32, tile_row: u32, obstacles_map: &mut HashMap<u32, HashSet<u32>>, sum: &mut u32) {
-    // fill current tile with sand
-    println!("\tfilling tile at: {:?}", (tile_col, tile_row));
-    obstacles_map.get_mut(&tile_col).map(|val| val.insert(tile_row));
-    *sum += 1;
+pub fn regolith_reservoir_part_1(file_name: &str) -> u32 {
+    regolith_reservoir_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn regolith_reservoir_part_1_from_str(input: &str) -> u32 {
+    Cave::new(input, &[SAND_SOURCE], Bottom::Abyss).fill()
 }
 
+pub fn regolith_reservoir_part_2(file_name: &str) -> u32 {
+    regolith_reservoir_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-fn process_input_2(input: Vec<Vec<(u32, u32)>>) -> HashMap<u32, HashSet<u32>> {
-    let mut map: HashMap<u32, HashSet<u32>> = HashMap::new();
+pub fn regolith_reservoir_part_2_from_str(input: &str) -> u32 {
+    Cave::new(input, &[SAND_SOURCE], Bottom::Floor(2)).fill()
+}
 
-    for line in input.iter() {
-        for (first_pair, second_pair) in line.iter().tuple_windows::<(_, _)>() {
-            process_input_inner(*first_pair, *second_pair, &mut map)
-        }
-    }
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
+    use crate::input_generator::rock_paths;
 
-    let (min, max) = match map.keys().minmax() {
-        MinMaxResult::MinMax(min, max) => (*min, *max),
-        _ => panic!(),
-    };
+    use super::*;
 
-    // add empty edge columns for convenience
-    for i in 1..=250 {
-        map.insert(min - i, HashSet::new());
-        map.insert(max + i, HashSet::new());
+    #[test]
+    fn part_1_input_example_1() {
+        let answer = regolith_reservoir_part_1("inputs/14_input_example.txt");
+
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 24);
     }
 
-    let min_row = map.iter()
-        .filter_map(|(_col, set)| set.iter().max())
-        .max()
-        .unwrap();
+    #[test]
+    fn part_1_input() {
+        let answer = regolith_reservoir_part_1("inputs/14_input.txt");
+
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 1406);
+    }
 
-    let new_min_rock = min_row + 2;
+    #[test]
+    fn part_2_input_example_1() {
+        let answer = regolith_reservoir_part_2("inputs/14_input_example.txt");
 
-    for (_, set) in map.iter_mut()
-    {
-        set.insert(new_min_rock);
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 93);
     }
 
-    map
-}
+    #[test]
+    fn part_2_input() {
+        let answer = regolith_reservoir_part_2("inputs/14_input.txt");
 
-pub fn regolith_reservoir_part_1(file_name: &str) -> u32 {
-    let input = read_input(file_name);
-    let mut rocks_map = process_input(input);
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 20870);
+    }
 
-    println!("{:?}", rocks_map);
+    #[test]
+    fn part_1_generated_input() {
+        let input = rock_paths(&mut StdRng::seed_from_u64(14), 60, 4);
+        let answer = regolith_reservoir_part_1_from_str(&input);
 
-    let mut sum = 0;
+        println!("part 1 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 457);
+    }
 
-    check_tile((500, 0), &mut rocks_map, &mut sum);
+    #[test]
+    fn part_2_generated_input() {
+        let input = rock_paths(&mut StdRng::seed_from_u64(14), 60, 4);
+        let answer = regolith_reservoir_part_2_from_str(&input);
 
-    sum
-}
+        println!("part 2 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 4739);
+    }
 
-pub fn regolith_reservoir_part_2(file_name: &str) -> u32 {
-    let input = read_input(file_name);
-    let mut rocks_map = process_input_2(input);
+    #[test]
+    fn render_input_example() {
+        let input = fs::read_to_string("inputs/14_input_example.txt").unwrap();
+
+        let mut cave = Cave::new(&input, &[SAND_SOURCE], Bottom::Abyss);
+        cave.fill();
+        assert_eq!(cave.render(), "\
+            ......+...\n\
+            ..........\n\
+            ......o...\n\
+            .....ooo..\n\
+            ....#ooo##\n\
+            ...o#ooo#.\n\
+            ..###ooo#.\n\
+            ....oooo#.\n\
+            .o.ooooo#.\n\
+            #########.\n");
+
+        let mut cave = Cave::new(&input, &[SAND_SOURCE], Bottom::Floor(2));
+        cave.fill();
+        let render = cave.render();
+        assert_eq!(render.lines().next(), Some("..........o.........."));
+        assert_eq!(render.lines().last(), Some("#####################"));
+        assert_eq!(render.matches('o').count(), 93);
+    }
+
+    #[test]
+    fn multiple_sources() {
+        let input = fs::read_to_string("inputs/14_input_example.txt").unwrap();
+
+        // the second source is buried under the heap of the first one
+        let mut cave = Cave::new(&input, &[SAND_SOURCE, (500, 5)], Bottom::Floor(2));
+        assert_eq!(cave.fill(), 93);
+
+        // sand of the second source lands on the heap of the first one and falls off it
+        let mut cave = Cave::new(&input, &[SAND_SOURCE, (497, 0)], Bottom::Abyss);
+        assert_eq!(cave.fill(), 24);
+        assert_eq!(cave.render(), "\
+            ...+..+...\n\
+            ..........\n\
+            ......o...\n\
+            .....ooo..\n\
+            ....#ooo##\n\
+            ...o#ooo#.\n\
+            ..###ooo#.\n\
+            ....oooo#.\n\
+            .o.ooooo#.\n\
+            #########.\n");
+    }
 
-    println!("{:?}", rocks_map);
+    #[test]
+    #[should_panic(expected = "sources have to be between row 0 and the floor")]
+    fn source_below_floor() {
+        let input = fs::read_to_string("inputs/14_input_example.txt").unwrap();
 
-    let mut sum = 0;
+        Cave::new(&input, &[(500, 20)], Bottom::Floor(2));
+    }
 
-    check_tile((500, 0), &mut rocks_map, &mut sum);
+    #[test]
+    #[should_panic(expected = "the floor has to be below the lowest rock")]
+    fn floor_at_lowest_rock() {
+        let input = fs::read_to_string("inputs/14_input_example.txt").unwrap();
 
-    sum
-}
\ No newline at end of file
+        Cave::new(&input, &[SAND_SOURCE], Bottom::Floor(0));
+    }
+}
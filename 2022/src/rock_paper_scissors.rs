@@ -1,147 +1,260 @@
-use std::fs::File;
-use std::io;
-use std::io::{BufRead, BufReader};
-
-/// | opp \ me | Rock | Paper | Scissors |
-/// | Rock     |   0  |   1   |   -1     |
-/// | Paper    |  -1  |   0   |    1     |
-/// | Scissors |   1  |  -1   |    0     |
-pub const BATTLE_RESULT: [[i32; 3]; 3] = [
-    [0, 1, -1],
-    [-1, 0, 1],
-    [1, -1, 0],
-];
+use std::collections::BTreeMap;
+use std::fs;
 
-#[derive(Copy, Clone, Debug)]
-enum AttackType {
-    Rock = 0,
-    Paper = 1,
-    Scissors = 2,
+use itertools::Itertools;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
 }
 
-impl From<i32> for AttackType {
-    fn from(value: i32) -> Self {
-        match value {
-            0 => AttackType::Rock,
-            1 => AttackType::Paper,
-            2 => AttackType::Scissors,
-            _ => panic!()
+impl Outcome {
+    fn points(&self) -> u32 {
+        match self {
+            Outcome::Lose => 0,
+            Outcome::Draw => 3,
+            Outcome::Win => 6,
         }
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-enum BattleResult {
-    Win = 1,
-    Draw = 0,
-    Lose = -1,
+/// Game of any number of weapons, the i-th weapon is worth `i + 1` points and is played by the opponent as the i-th letter from `A`.
+pub struct Game {
+    weapons: Vec<String>,
+    /// beats[a][b] - weapon a beats weapon b
+    beats: Vec<Vec<bool>>,
 }
 
-impl From<i32> for BattleResult {
-    fn from(value: i32) -> Self {
-        match value {
-            -1 => BattleResult::Lose,
-            0 => BattleResult::Draw,
-            1 => BattleResult::Win,
-            _ => panic!()
+impl Game {
+    /// Game of the weapons, with the pairs (winner, loser) of the beats relation; pairs not listed are draws.
+    pub fn new(weapons: &[&str], beats: &[(&str, &str)]) -> Self {
+        let index = |weapon: &str| weapons.iter().position(|&other| other == weapon).unwrap_or_else(|| panic!("unknown weapon {weapon}"));
+
+        let mut game = Game {
+            weapons: weapons.iter().map(|weapon| weapon.to_string()).collect(),
+            beats: vec![vec![false; weapons.len()]; weapons.len()],
+        };
+        for &(winner, loser) in beats {
+            let (winner, loser) = (index(winner), index(loser));
+            assert!(winner != loser && !game.beats[loser][winner], "{} and {} cannot beat each other", weapons[winner], weapons[loser]);
+            game.beats[winner][loser] = true;
         }
+
+        game
     }
-}
 
-fn opp_mapping(letter: char) -> AttackType {
-    match letter {
-        'A' => AttackType::Rock,
-        'B' => AttackType::Paper,
-        'C' => AttackType::Scissors,
-        _ => panic!()
+    pub fn classic() -> Self {
+        Game::new(&["Rock", "Paper", "Scissors"], &[("Rock", "Scissors"), ("Paper", "Rock"), ("Scissors", "Paper")])
     }
-}
 
-fn me_mapping(letter: char) -> AttackType {
-    match letter {
-        'X' => AttackType::Rock,
-        'Y' => AttackType::Paper,
-        'Z' => AttackType::Scissors,
-        _ => panic!()
+    pub fn lizard_spock() -> Self {
+        Game::new(
+            &["Rock", "Paper", "Scissors", "Lizard", "Spock"],
+            &[
+                ("Scissors", "Paper"), ("Paper", "Rock"), ("Rock", "Lizard"), ("Lizard", "Spock"), ("Spock", "Scissors"),
+                ("Scissors", "Lizard"), ("Lizard", "Paper"), ("Paper", "Spock"), ("Spock", "Rock"), ("Rock", "Scissors"),
+            ],
+        )
+    }
+
+    pub fn weapon(&self, index: usize) -> &str {
+        &self.weapons[index]
+    }
+
+    fn outcome(&self, me: usize, opponent: usize) -> Outcome {
+        if self.beats[me][opponent] {
+            Outcome::Win
+        } else if self.beats[opponent][me] {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    fn score(&self, me: usize, opponent: usize) -> u32 {
+        me as u32 + 1 + self.outcome(me, opponent).points()
+    }
+
+    /// Total score of the guide, reading the second column as my weapons by the mapping.
+    pub fn score_moves(&self, guide: &[Round], mapping: &BTreeMap<char, usize>) -> u32 {
+        guide.iter()
+            .map(|round| {
+                let me = *mapping.get(&round.response).unwrap_or_else(|| panic!("no weapon for {}", round.response));
+                self.score(me, self.opponent(round))
+            })
+            .sum()
+    }
+
+    /// Total score of the guide, reading the second column as the outcome, `X` to lose, `Y` to draw and `Z` to win.
+    /// When more weapons lead to the outcome, the one worth most is played.
+    pub fn score_outcomes(&self, guide: &[Round]) -> u32 {
+        guide.iter()
+            .map(|round| {
+                let outcome = match round.response {
+                    'X' => Outcome::Lose,
+                    'Y' => Outcome::Draw,
+                    'Z' => Outcome::Win,
+                    letter => panic!("invalid outcome: {letter}"),
+                };
+                let opponent = self.opponent(round);
+
+                (0..self.weapons.len())
+                    .filter(|&me| self.outcome(me, opponent) == outcome)
+                    .map(|me| self.score(me, opponent))
+                    .max()
+                    .unwrap_or_else(|| panic!("no weapon can {outcome:?} against {}", self.weapons[opponent]))
+            })
+            .sum()
+    }
+
+    /// Mapping of the letters in the second column to different weapons with the highest or lowest total score,
+    /// found by trying all of them.
+    pub fn solve_mapping(&self, guide: &[Round], objective: Objective) -> (BTreeMap<char, usize>, u32) {
+        let letters = guide.iter().map(|round| round.response).unique().sorted().collect::<Vec<_>>();
+        assert!(letters.len() <= self.weapons.len(), "more letters than weapons");
+
+        let scored = (0..self.weapons.len())
+            .permutations(letters.len())
+            .map(|weapons| {
+                let mapping = letters.iter().copied().zip(weapons).collect::<BTreeMap<_, _>>();
+                let score = self.score_moves(guide, &mapping);
+                (mapping, score)
+            });
+
+        match objective {
+            Objective::Max => scored.max_by_key(|(_, score)| *score),
+            Objective::Min => scored.min_by_key(|(_, score)| *score),
+        }.unwrap()
+    }
+
+    fn opponent(&self, round: &Round) -> usize {
+        let opponent = (round.opponent as u8).wrapping_sub(b'A') as usize;
+        assert!(opponent < self.weapons.len(), "invalid opponent weapon: {}", round.opponent);
+
+        opponent
     }
 }
 
-fn calculate_point_for_round(result: BattleResult, my_attack: AttackType) -> i32 {
-    let battle_points = match result {
-        BattleResult::Win => 6,
-        BattleResult::Draw => 3,
-        BattleResult::Lose => 0,
-    };
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Objective {
+    Max,
+    Min,
+}
 
-    let attack_points = match my_attack {
-        AttackType::Rock => 1,
-        AttackType::Paper => 2,
-        AttackType::Scissors => 3,
-    };
+/// Line of the strategy guide.
+#[derive(Copy, Clone, Debug)]
+pub struct Round {
+    pub opponent: char,
+    pub response: char,
+}
 
-    battle_points + attack_points
+pub fn parse_guide(input: &str) -> Vec<Round> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| match line.split_whitespace().collect::<Vec<_>>()[..] {
+            [opponent, response] if opponent.len() == 1 && response.len() == 1 => Round {
+                opponent: opponent.chars().next().unwrap(),
+                response: response.chars().next().unwrap(),
+            },
+            _ => panic!("invalid round: {line}"),
+        })
+        .collect()
 }
 
-pub fn rock_paper_scissors_part_1(file_name: &str) -> Result<(), io::Error> {
-    let file = File::open(file_name)?;
-    let reader = BufReader::new(file);
+/// Mapping of the puzzle, `X`, `Y` and `Z` are rock, paper and scissors.
+fn puzzle_mapping() -> BTreeMap<char, usize> {
+    BTreeMap::from([('X', 0), ('Y', 1), ('Z', 2)])
+}
 
-    let mut sum = 0;
+pub fn rock_paper_scissors_part_1(file_name: &str) -> u32 {
+    rock_paper_scissors_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-    for result in reader.lines() {
-        let line = result.unwrap();
-        // let line = match result {
-        //     Ok(line) => line,
-        //     Err(_) => break
-        // };
+pub fn rock_paper_scissors_part_1_from_str(input: &str) -> u32 {
+    Game::classic().score_moves(&parse_guide(input), &puzzle_mapping())
+}
+
+pub fn rock_paper_scissors_part_2(file_name: &str) -> u32 {
+    rock_paper_scissors_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-        let mut iter = line.chars();
+pub fn rock_paper_scissors_part_2_from_str(input: &str) -> u32 {
+    Game::classic().score_outcomes(&parse_guide(input))
+}
 
-        let opponent = opp_mapping(iter.next().unwrap());
-        let me = me_mapping(iter.skip(1).next().unwrap());
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let battle_result = BattleResult::from(BATTLE_RESULT[opponent as usize][me as usize]);
+    #[test]
+    fn part_1_input_example() {
+        let answer = rock_paper_scissors_part_1("inputs/2_input_example.txt");
 
-        sum += calculate_point_for_round(battle_result, me);
-        println!("opponent: {:?} \t\t me: {:?} \t\t\t result: {:?}", opponent, me, battle_result);
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 15);
     }
 
-    println!("total points: {}", sum);
-    Ok(())
-}
+    #[test]
+    fn part_1_input() {
+        let answer = rock_paper_scissors_part_1("inputs/2_input.txt");
 
-fn expectation_mapping(letter: char) -> BattleResult {
-    match letter {
-        'X' => BattleResult::Lose,
-        'Y' => BattleResult::Draw,
-        'Z' => BattleResult::Win,
-        _ => panic!()
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 13682);
     }
-}
 
-pub fn rock_paper_scissors_part_2(file_name: &str) -> Result<(), io::Error> {
-    let file = File::open(file_name)?;
-    let reader = BufReader::new(file);
+    #[test]
+    fn part_2_input_example() {
+        let answer = rock_paper_scissors_part_2("inputs/2_input_example.txt");
 
-    let mut sum = 0;
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 12);
+    }
+
+    #[test]
+    fn part_2_input() {
+        let answer = rock_paper_scissors_part_2("inputs/2_input.txt");
 
-    for result in reader.lines() {
-        let line = result.unwrap();
-        let mut iter = line.chars();
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 12881);
+    }
 
-        let opponent = opp_mapping(iter.next().unwrap());
-        let expected_result = expectation_mapping(iter.skip(1).next().unwrap());
+    #[test]
+    fn solve_mapping_input_example() {
+        let guide = parse_guide(&fs::read_to_string("inputs/2_input_example.txt").unwrap());
+        let game = Game::classic();
 
-        let my_attack: AttackType = BATTLE_RESULT[opponent as usize]
-            .iter()
-            .position(|&x| x == (expected_result as i32))
-            .map(|x| AttackType::from(x as i32))
-            .unwrap();
+        // always answering with the weapon beating the opponent's: A -> paper, B -> scissors, C -> rock
+        let (mapping, score) = game.solve_mapping(&guide, Objective::Max);
+        assert_eq!(mapping, BTreeMap::from([('X', 2), ('Y', 1), ('Z', 0)]));
+        assert_eq!(score, 24);
 
-        sum += calculate_point_for_round(expected_result, my_attack);
-        println!("opponent: {:?} \t\t me: {:?} \t\t\t result: {:?}", opponent, my_attack, expected_result);
+        // always losing with the weapon beaten by the opponent's: A -> scissors, B -> rock, C -> paper
+        let (mapping, score) = game.solve_mapping(&guide, Objective::Min);
+        assert_eq!(mapping, BTreeMap::from([('X', 0), ('Y', 2), ('Z', 1)]));
+        assert_eq!(score, 6);
     }
 
-    println!("total points: {}", sum);
-    Ok(())
+    #[test]
+    fn lizard_spock() {
+        let game = Game::lizard_spock();
+        let guide = parse_guide("E X\nD Y\nA Z\n");
+
+        // each weapon beats two others and loses to two others
+        for weapon in 0..5 {
+            let wins = (0..5).filter(|&other| game.outcome(weapon, other) == Outcome::Win).count();
+            let losses = (0..5).filter(|&other| game.outcome(weapon, other) == Outcome::Lose).count();
+            assert_eq!((wins, losses), (2, 2), "{}", game.weapon(weapon));
+        }
+
+        // lose to Spock with Scissors (3) or Rock (1), draw Lizard with Lizard (4), beat Rock with Spock (5) or Paper (2)
+        assert_eq!(game.score_outcomes(&guide), 3 + (4 + 3) + (5 + 6));
+
+        // beat Spock with Lizard, Lizard with Scissors and Rock with Spock
+        let (mapping, score) = game.solve_mapping(&guide, Objective::Max);
+        assert_eq!(mapping, BTreeMap::from([('X', 3), ('Y', 2), ('Z', 4)]));
+        assert_eq!(score, (4 + 6) + (3 + 6) + (5 + 6));
+    }
 }
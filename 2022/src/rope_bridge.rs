@@ -1,9 +1,9 @@
-use std::borrow::BorrowMut;
-use std::f32::consts::SQRT_2;
-use std::fs::File;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Direction {
     Left,
     Right,
@@ -13,18 +13,25 @@ pub enum Direction {
 
 pub fn read_input(file_name: &str) -> InstructionsIterator {
     let file = File::open(file_name).unwrap();
-    let reader = BufReader::new(file);
+
+    InstructionsIterator {
+        buf_reader: BufReader::new(file),
+    }
+}
+
+pub fn read_input_from_str(input: &str) -> InstructionsIterator<&[u8]> {
+    let reader = input.as_bytes();
 
     InstructionsIterator {
         buf_reader: reader,
     }
 }
 
-pub struct InstructionsIterator {
-    buf_reader: BufReader<File>,
+pub struct InstructionsIterator<R = BufReader<File>> {
+    buf_reader: R,
 }
 
-impl Iterator for InstructionsIterator {
+impl<R: BufRead> Iterator for InstructionsIterator<R> {
     type Item = (Direction, usize);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -57,183 +64,231 @@ fn parse_direction(direction: &str) -> Direction {
 }
 
 
-fn expand_map(map: &mut Vec<Vec<char>>, direction: Direction) {
-    match direction {
-        Direction::Left => {
-            for row in map {
-                row.insert(0, '.');
-            }
-        }
-        Direction::Right => {
-            for row in map {
-                row.push('.');
-            }
-        }
-        Direction::Up => {
-            let row_length = map[0].len();
-            map.insert(0, vec!['.'; row_length])
-        }
-        Direction::Down => {
-            let row_length = map[0].len();
-            map.push(vec!['.'; row_length])
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    fn moved(self, direction: Direction) -> Point {
+        match direction {
+            Direction::Left => Point { x: self.x - 1, ..self },
+            Direction::Right => Point { x: self.x + 1, ..self },
+            Direction::Up => Point { y: self.y + 1, ..self },
+            Direction::Down => Point { y: self.y - 1, ..self },
         }
-    };
-}
-
-fn extend_maps_if_needed(
-    head_map: &mut Vec<Vec<char>>,
-    tail_map: &mut Vec<Vec<char>>,
-    knots: &mut [Knot],
-) {
-    if knots[0].row < 0 {
-        expand_map(head_map, Direction::Up);
-        expand_map(tail_map, Direction::Up);
-        for knot in knots.iter_mut() {
-            knot.row += 1
+    }
+}
+
+/// How a knot follows the knot ahead of it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FollowRule {
+    /// Puzzle's rule: once not touching, moves one step towards the knot ahead, diagonally if not in line.
+    Standard,
+    /// Moves only diagonally, so a knot in line with the knot ahead waits until that one leaves the line.
+    DiagonalOnly,
+    /// Like [FollowRule::Standard], but `slack` cells may stay between the knots before it moves.
+    Elastic { slack: u32 },
+}
+
+impl FollowRule {
+    fn follow(self, leader: Point, follower: Point) -> Point {
+        let (dx, dy) = (leader.x - follower.x, leader.y - follower.y);
+        let distance = dx.abs().max(dy.abs()) as u32;
+
+        let moves = match self {
+            FollowRule::Standard => distance > 1,
+            FollowRule::DiagonalOnly => distance > 1 && dx != 0 && dy != 0,
+            FollowRule::Elastic { slack } => distance > 1 + slack,
+        };
+
+        if moves {
+            Point { x: follower.x + dx.signum(), y: follower.y + dy.signum() }
+        } else {
+            follower
         }
     }
+}
+
+/// Rope of knots on an unbounded plane, remembering the cells visited by every knot.
+pub struct Rope {
+    knots: Vec<Point>,
+    rule: FollowRule,
+    visited: Vec<HashSet<Point>>,
+    trajectory: Option<Vec<Vec<Point>>>,
+}
+
+impl Rope {
+    pub fn new(knots_num: usize, rule: FollowRule) -> Rope {
+        assert!(knots_num > 0, "rope needs at least a head");
+        let start = Point::default();
 
-    if knots[0].col < 0 {
-        expand_map(head_map, Direction::Left);
-        expand_map(tail_map, Direction::Left);
-        for knot in knots.iter_mut() {
-            knot.col += 1
+        Rope {
+            knots: vec![start; knots_num],
+            rule,
+            visited: vec![HashSet::from([start]); knots_num],
+            trajectory: None,
         }
     }
 
-    if knots[0].row >= head_map.len() as i32 {
-        expand_map(head_map, Direction::Down);
-        expand_map(tail_map, Direction::Down);
+    /// Records positions of all knots after every step, see [Rope::export_trajectory].
+    pub fn with_trajectory(mut self) -> Rope {
+        self.trajectory = Some(vec![self.knots.clone()]);
+        self
     }
 
-    if knots[0].col >= head_map[0].len() as i32 {
-        expand_map(head_map, Direction::Right);
-        expand_map(tail_map, Direction::Right);
+    pub fn knots(&self) -> &[Point] {
+        &self.knots
     }
-}
 
-#[derive(Clone, Copy)]
-struct Knot {
-    row: i32,
-    col: i32,
-}
+    pub fn visited(&self, knot: usize) -> &HashSet<Point> {
+        &self.visited[knot]
+    }
+
+    pub fn tail_visited(&self) -> &HashSet<Point> {
+        self.visited.last().unwrap()
+    }
+
+    /// Positions of all knots at the start and after every step.
+    pub fn trajectory(&self) -> Option<&[Vec<Point>]> {
+        self.trajectory.as_deref()
+    }
 
-fn update_tail_cords_new(knot1: Knot, knot2: &mut Knot) {
-    let square_dist = (knot2.row - knot1.row).pow(2) + (knot2.col - knot1.col).pow(2);
-    let dist = (square_dist as f32).powf(1. / 2.);
+    pub fn step(&mut self, direction: Direction) {
+        self.knots[0] = self.knots[0].moved(direction);
 
-    if dist <= SQRT_2 + 0.001 {
-        return;
+        for i in 1..self.knots.len() {
+            self.knots[i] = self.rule.follow(self.knots[i - 1], self.knots[i]);
+        }
+
+        for (visited, knot) in self.visited.iter_mut().zip(self.knots.iter()) {
+            visited.insert(*knot);
+        }
+
+        if let Some(trajectory) = self.trajectory.as_mut() {
+            trajectory.push(self.knots.clone());
+        }
     }
 
-    let vector = (knot2.row - knot1.row, knot2.col - knot1.col);
-    let new_pos: (i32, i32) = match vector {
-        (0, 2) => (0, 1),
-        (0, -2) => (0, -1),
-        (-2, 0) => (-1, 0),
-        (2, 0) => (1, 0),
+    pub fn apply(&mut self, direction: Direction, distance: usize) {
+        for _ in 0..distance {
+            self.step(direction);
+        }
+    }
 
-        (2, 2) => (1, 1),
-        (2, -2) => (1, -1),
-        (-2, 2) => (-1, 1),
-        (-2, -2) => (-1, -1),
+    /// Trajectory as CSV with columns `step,knot,x,y`, where knot 0 is the head.
+    pub fn export_trajectory(&self) -> Option<String> {
+        let trajectory = self.trajectory()?;
+        let mut csv = "step,knot,x,y\n".to_string();
 
-        (2, 1) => (1, 0),
-        (2, -1) => (1, 0),
-        (-2, 1) => (-1, 0),
-        (-2, -1) => (-1, 0),
+        for (step, knots) in trajectory.iter().enumerate() {
+            for (knot, point) in knots.iter().enumerate() {
+                writeln!(csv, "{step},{knot},{},{}", point.x, point.y).unwrap();
+            }
+        }
 
-        (1, 2) => (0, 1),
-        (1, -2) => (0, -1),
-        (-1, 2) => (0, 1),
-        (-1, -2) => (0, -1),
-        _ => panic!()
-    };
+        Some(csv)
+    }
+}
 
-    knot2.row = knot1.row + new_pos.0;
-    knot2.col = knot1.col + new_pos.1;
+pub fn rope_bridge_part_1(file_name: &str) -> usize {
+    rope_bridge_part_2(file_name, 2)
 }
 
-fn count_marked(map: &[Vec<char>]) -> i32 {
-    map.iter()
-        .map(|row| row.iter()
-            .filter(|&char| *char == '#')
-            .map(|_| 1)
-            .sum::<i32>())
-        .sum()
+pub fn rope_bridge_part_1_from_str(input: &str) -> usize {
+    rope_bridge_part_2_from_str(input, 2)
 }
 
-pub fn rope_bridge_part_1(file_name: &str) -> i32 {
-    rope_bridge_part_2(file_name, 2)
+pub fn rope_bridge_part_2(file_name: &str, knots_num: usize) -> usize {
+    rope_bridge_part_2_from_str(&fs::read_to_string(file_name).unwrap(), knots_num)
 }
 
-pub fn rope_bridge_part_2(file_name: &str, knots_num: usize) -> i32 {
-    let initial_size = 2;
-    let half = (initial_size / 2) as i32;
+pub fn rope_bridge_part_2_from_str(input: &str, knots_num: usize) -> usize {
+    let mut rope = Rope::new(knots_num, FollowRule::Standard);
 
-    let mut head_map = vec![vec!['.'; initial_size]; initial_size];
-    let mut tail_map = vec![vec!['.'; initial_size]; initial_size];
+    for (direction, distance) in read_input_from_str(input) {
+        rope.apply(direction, distance);
+    }
 
-    let mut knots = vec![Knot { row: half, col: half }; knots_num];
+    rope.tail_visited().len()
+}
 
-    for (direction, dist) in read_input(file_name) {
-        for _ in 0..dist {
-            match direction {
-                Direction::Left => knots[0].row -= 1,
-                Direction::Right => knots[0].row += 1,
-                Direction::Up => knots[0].col += 1,
-                Direction::Down => knots[0].col -= 1,
-            };
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            extend_maps_if_needed(&mut head_map, &mut tail_map, &mut knots);
+    #[test]
+    fn part_1_input_example() {
+        let answer = rope_bridge_part_1("inputs/9_input_example.txt");
 
-            for i in 0..knots.len() - 1 {
-                let first = knots[i];
-                let second = knots[i + 1].borrow_mut();
-                update_tail_cords_new(first, second);
-            }
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 13);
+    }
 
-            head_map[knots[0].row as usize][knots[0].col as usize] = '$';
-            tail_map[knots.last().unwrap().row as usize][knots.last().unwrap().col as usize] = '#';
+    #[test]
+    fn part_1_input() {
+        let answer = rope_bridge_part_1("inputs/9_input.txt");
 
-            // print_knots(&knots, head_map.len(), head_map[0].len());
-        }
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 6018);
     }
 
-    print(&head_map);
-    print(&tail_map);
+    #[test]
+    fn part_2_input_example() {
+        let answer = rope_bridge_part_2("inputs/9_input_example.txt", 10);
 
-    count_marked(&tail_map)
-}
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 1);
+    }
 
-#[allow(dead_code)]
-fn print_knots(knots: &[Knot], length: usize, width: usize) {
-    let mut array = vec![vec!['.'; width]; length];
+    #[test]
+    fn part_2_input_example_2() {
+        let answer = rope_bridge_part_2("inputs/9_input_example_2.txt", 10);
 
-    for (i, knot) in knots.iter().enumerate() {
-        array[knot.row as usize][knot.col as usize] = format!("{i}").parse().unwrap();
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 36);
     }
 
-    print(&array);
-}
+    #[test]
+    fn part_2_input() {
+        let answer = rope_bridge_part_2("inputs/9_input.txt", 10);
+
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 2619);
+    }
+
+    #[test]
+    fn visited_per_knot() {
+        let mut rope = Rope::new(3, FollowRule::Standard);
+        rope.apply(Direction::Right, 4);
+
+        assert_eq!(rope.visited(0).len(), 5);
+        assert_eq!(rope.visited(1).len(), 4);
+        assert_eq!(rope.visited(2).len(), 3);
+        assert_eq!(rope.knots(), &[Point { x: 4, y: 0 }, Point { x: 3, y: 0 }, Point { x: 2, y: 0 }]);
+    }
 
-#[allow(dead_code)]
-fn print(array2d: &[Vec<char>]) {
-    for row in array2d.iter() {
-        let row_str = row
-            .iter()
-            .map(|elem| elem.to_string())
-            .collect::<Vec<String>>()
-            .join(" ");
-        println!("{}", row_str);
+    #[test]
+    fn follow_rules() {
+        let leader = Point { x: 2, y: 1 };
+        let in_line_leader = Point { x: 2, y: 0 };
+        let follower = Point::default();
+
+        assert_eq!(FollowRule::Standard.follow(leader, follower), Point { x: 1, y: 1 });
+        assert_eq!(FollowRule::Standard.follow(in_line_leader, follower), Point { x: 1, y: 0 });
+        assert_eq!(FollowRule::DiagonalOnly.follow(leader, follower), Point { x: 1, y: 1 });
+        assert_eq!(FollowRule::DiagonalOnly.follow(in_line_leader, follower), follower);
+        assert_eq!(FollowRule::Elastic { slack: 1 }.follow(leader, follower), follower);
+        assert_eq!(FollowRule::Elastic { slack: 1 }.follow(Point { x: 3, y: 1 }, follower), Point { x: 1, y: 1 });
     }
-    println!()
 
-    // for j in (0..array2d.len() - 1).rev() {
-    //     for i in 0..array2d[0].len() {
-    //         print!("{}", array2d[i][j]);
-    //     }
-    //     println!();
-    // }
-    // println!()
-}
\ No newline at end of file
+    #[test]
+    fn export_trajectory() {
+        let mut rope = Rope::new(2, FollowRule::Standard).with_trajectory();
+        rope.apply(Direction::Up, 2);
+
+        assert_eq!(rope.export_trajectory().unwrap(), "step,knot,x,y\n0,0,0,0\n0,1,0,0\n1,0,0,1\n1,1,0,0\n2,0,0,2\n2,1,0,1\n");
+        assert_eq!(Rope::new(2, FollowRule::Standard).export_trajectory(), None);
+    }
+}
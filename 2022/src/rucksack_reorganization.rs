@@ -1,12 +1,12 @@
 use std::collections::HashSet;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
 
-pub struct InputIterator {
-    buf_reader: BufReader<File>,
+pub struct InputIterator<R = BufReader<File>> {
+    buf_reader: R,
 }
 
-impl Iterator for InputIterator {
+impl<R: BufRead> Iterator for InputIterator<R> {
     type Item = (Vec<char>, Vec<char>);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -28,7 +28,14 @@ impl Iterator for InputIterator {
 
 pub fn read_input(file_name: &str) -> InputIterator {
     let file = File::open(file_name).unwrap();
-    let reader = BufReader::new(file);
+
+    InputIterator {
+        buf_reader: BufReader::new(file),
+    }
+}
+
+pub fn read_input_from_str(input: &str) -> InputIterator<&[u8]> {
+    let reader = input.as_bytes();
 
     InputIterator {
         buf_reader: reader,
@@ -48,9 +55,13 @@ fn sum_chars(chars: Vec<&char>) -> i32 {
 }
 
 pub fn rucksack_reorganization_part_1(file_name: &str) -> i32 {
+    rucksack_reorganization_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn rucksack_reorganization_part_1_from_str(input: &str) -> i32 {
     let mut total = 0;
 
-    for (first_half, second_half) in read_input(file_name) {
+    for (first_half, second_half) in read_input_from_str(input) {
         let first_set: HashSet<char> = first_half.into_iter().collect();
         let second_set: HashSet<char> = second_half.into_iter().collect();
 
@@ -62,11 +73,11 @@ pub fn rucksack_reorganization_part_1(file_name: &str) -> i32 {
     total
 }
 
-pub struct ThreeLineInputIterator {
-    buf_reader: BufReader<File>,
+pub struct ThreeLineInputIterator<R = BufReader<File>> {
+    buf_reader: R,
 }
 
-impl Iterator for ThreeLineInputIterator {
+impl<R: BufRead> Iterator for ThreeLineInputIterator<R> {
     type Item = (Vec<char>, Vec<char>, Vec<char>);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -96,7 +107,14 @@ impl Iterator for ThreeLineInputIterator {
 
 pub fn read_input_2(file_name: &str) -> ThreeLineInputIterator {
     let file = File::open(file_name).unwrap();
-    let reader = BufReader::new(file);
+
+    ThreeLineInputIterator {
+        buf_reader: BufReader::new(file),
+    }
+}
+
+pub fn read_input_2_from_str(input: &str) -> ThreeLineInputIterator<&[u8]> {
+    let reader = input.as_bytes();
 
     ThreeLineInputIterator {
         buf_reader: reader,
@@ -104,9 +122,13 @@ pub fn read_input_2(file_name: &str) -> ThreeLineInputIterator {
 }
 
 pub fn rucksack_reorganization_part_2(file_name: &str) -> i32 {
+    rucksack_reorganization_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn rucksack_reorganization_part_2_from_str(input: &str) -> i32 {
     let mut total = 0;
 
-    for (first, second, third) in read_input_2(file_name) {
+    for (first, second, third) in read_input_2_from_str(input) {
         let first_set: HashSet<char> = first.into_iter().collect();
         let second_set: HashSet<char> = second.into_iter().collect();
         let third_set: HashSet<char> = third.into_iter().collect();
@@ -1,117 +1,275 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 
-type Stacks = Vec<Vec<char>>;
+/// Moves crates between stacks, lifting some of the top crates at once and keeping their order.
+pub trait Crane {
+    /// Crates lifted at once while `remaining` crates of the move are left on the stack.
+    fn lift(&self, remaining: usize) -> usize;
+}
 
-#[derive(Debug)]
-struct Move {
-    qty: usize,
-    start: usize,
-    end: usize,
+/// Lifts one crate at a time, so the moved crates end up reversed.
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn lift(&self, _remaining: usize) -> usize {
+        1
+    }
 }
 
+/// Lifts all crates of a move at once, so they keep their order.
+pub struct CrateMover9001;
 
-fn read_input(file_name: &str) -> (Stacks, Vec<Move>) {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
+impl Crane for CrateMover9001 {
+    fn lift(&self, remaining: usize) -> usize {
+        remaining
+    }
+}
 
-    let mut lines: Vec<String> = Vec::new();
+/// Lifts at most `capacity` crates at once.
+pub struct LimitedCrane {
+    pub capacity: usize,
+}
 
-    loop {
-        let mut buf = String::new();
-        let _ = reader.read_line(&mut buf).unwrap();
+impl Crane for LimitedCrane {
+    fn lift(&self, remaining: usize) -> usize {
+        remaining.min(self.capacity)
+    }
+}
 
-        if buf.starts_with(" 1   2") {
-            break;
-        }
+#[derive(Debug)]
+pub struct Move {
+    pub quantity: usize,
+    /// indices of the stacks
+    pub from: usize,
+    pub to: usize,
+}
 
-        lines.push(buf.clone());
-    };
+/// Stacks of crates from the bottom up, named by their labels.
+#[derive(Clone, Debug)]
+pub struct Cargo {
+    labels: Vec<String>,
+    stacks: Vec<Vec<char>>,
+}
 
-    let num_of_stacks = (lines.last().unwrap().len() + 1) / 4;
-    let mut stacks: Stacks = vec![Vec::new(); num_of_stacks];
+/// Drawing of the stacks and the moves, separated by an empty line.
+///
+/// Stacks are counted from the labels in the last row of the drawing, so there can be any number of them
+/// with labels of any length. Crates of a stack are drawn under the middle character of its label,
+/// the right one of the two middle characters for labels of even length.
+pub fn parse_input(input: &str) -> (Cargo, Vec<Move>) {
+    let input = input.replace("\r\n", "\n");
+    let (drawing, moves) = input.split_once("\n\n").expect("no empty line after the drawing");
 
-    // parse stacks
-    for line in lines.iter().rev() {
-        for (i, stack) in stacks.iter_mut().enumerate() {
-            let segment = &line.chars().nth((i * 4) + 1).unwrap();
+    let mut rows = drawing.lines().filter(|line| !line.trim().is_empty()).collect::<Vec<_>>();
+    let label_row = rows.pop().expect("no labels").chars().collect::<Vec<_>>();
 
-            if segment.is_alphabetic() {
-                stack.push(*segment);
-            }
+    // labels with the column of their crates
+    let mut labels = Vec::new();
+    let mut columns = Vec::new();
+    let mut start = 0;
+    while start < label_row.len() {
+        if label_row[start].is_whitespace() {
+            start += 1;
+            continue;
         }
+        let end = label_row[start..].iter().position(|ch| ch.is_whitespace()).map_or(label_row.len(), |len| start + len);
+        labels.push(label_row[start..end].iter().collect::<String>());
+        columns.push(start + (end - start) / 2);
+        start = end;
     }
 
-    // skip one line
-    let _ = reader.read_line(&mut String::new());
-
-    // parse moves
-    let mut moves: Vec<Move> = Vec::new();
-    loop {
-        let mut buf = String::new();
-        match reader.read_line(&mut buf) {
-            Ok(0) => break,
-            Err(_) => panic!(),
-            Ok(_n) => {
-                let info: Vec<i32> = buf.split(' ')
-                    .filter_map(|s| s.trim().parse().ok())
-                    .collect();
-
-                moves.push(
-                    Move {
-                        qty: info[0] as usize,
-                        start: (info[1] - 1) as usize,
-                        end: (info[2] - 1) as usize,
-                    }
-                );
+    let mut stacks = vec![Vec::new(); labels.len()];
+    for row in rows.iter().rev() {
+        let row = row.chars().collect::<Vec<_>>();
+        for (stack, &column) in stacks.iter_mut().zip(&columns) {
+            match row.get(column) {
+                Some(&ch) if ch.is_alphanumeric() => stack.push(ch),
+                _ => {}
             }
         }
-    };
+    }
+
+    let index = |label: &str| labels.iter().position(|other| other == label).unwrap_or_else(|| panic!("unknown stack {label}"));
+    let moves = moves
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            match words[..] {
+                ["move", quantity, "from", from, "to", to] => Move {
+                    quantity: quantity.parse().unwrap(),
+                    from: index(from),
+                    to: index(to),
+                },
+                _ => panic!("invalid move: {line}"),
+            }
+        })
+        .collect();
 
-    // println!("{:?}", stacks);
-    // println!("{:?}", moves);
-    (stacks, moves)
+    (Cargo { labels, stacks }, moves)
 }
 
+impl Cargo {
+    pub fn apply(&mut self, crane: &impl Crane, step: &Move) {
+        assert!(self.stacks[step.from].len() >= step.quantity, "not enough crates on stack {}", self.labels[step.from]);
 
-pub fn supply_stacks_part_1(file_name: &str) -> String {
-    let (mut stacks, moves) = read_input(file_name);
+        let mut remaining = step.quantity;
+        while remaining > 0 {
+            let lifted = crane.lift(remaining).clamp(1, remaining);
+            let from = &mut self.stacks[step.from];
+            let crates = from.split_off(from.len() - lifted);
 
-    for move_ in moves.iter() {
-        for _ in 0..move_.qty {
-            let elem = stacks[move_.start].pop().unwrap();
-            stacks[move_.end].push(elem);
+            self.stacks[step.to].extend(crates);
+            remaining -= lifted;
         }
     }
 
+    pub fn label(&self, stack: usize) -> &str {
+        &self.labels[stack]
+    }
+
+    /// Crates on top of the stacks, empty stacks are skipped.
+    pub fn top_crates(&self) -> String {
+        self.stacks.iter().filter_map(|stack| stack.last()).collect()
+    }
+
+    /// Drawing of the stacks as in the puzzle input, stacks with labels longer than 3 characters are drawn wider.
+    pub fn render(&self) -> String {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let widths = self.labels.iter().map(|label| label.chars().count().max(3)).collect::<Vec<_>>();
+
+        let mut lines = (0..height)
+            .rev()
+            .map(|level| self.stacks.iter()
+                .zip(&widths)
+                .map(|(stack, &width)| match stack.get(level) {
+                    // crate under the middle of the label, see parse_input
+                    Some(ch) => format!("{}[{ch}]{}", " ".repeat((width - 2) / 2), " ".repeat(width - 3 - (width - 2) / 2)),
+                    None => " ".repeat(width),
+                })
+                .collect::<Vec<_>>()
+                .join(" "))
+            .collect::<Vec<_>>();
+        lines.push(self.labels.iter().zip(&widths).map(|(label, &width)| format!("{label:^width$}")).collect::<Vec<_>>().join(" "));
 
-    let mut result = Vec::new();
-    for stack in stacks.iter() {
-        result.push(stack.last().copied().unwrap())
+        lines.join("\n") + "\n"
     }
+}
+
+/// Crates on top of the stacks after all moves.
+pub fn rearrange(input: &str, crane: &impl Crane) -> String {
+    let (mut cargo, moves) = parse_input(input);
+    for step in moves.iter() {
+        cargo.apply(crane, step);
+    }
+
+    println!("{}", cargo.render());
+
+    cargo.top_crates()
+}
 
-    println!("{:?}", stacks);
+pub fn supply_stacks_part_1(file_name: &str) -> String {
+    supply_stacks_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-    String::from_iter(result)
+pub fn supply_stacks_part_1_from_str(input: &str) -> String {
+    rearrange(input, &CrateMover9000)
 }
 
 pub fn supply_stacks_part_2(file_name: &str) -> String {
-    let (mut stacks, moves) = read_input(file_name);
+    supply_stacks_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn supply_stacks_part_2_from_str(input: &str) -> String {
+    rearrange(input, &CrateMover9001)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_input_example() {
+        let answer = supply_stacks_part_1("inputs/5_input_example.txt");
+
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, "CMZ");
+    }
+
+    #[test]
+    fn part_1_input() {
+        let answer = supply_stacks_part_1("inputs/5_input.txt");
+
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, "LJSVLTWQM");
+    }
+
+    #[test]
+    fn part_2_input_example() {
+        let answer = supply_stacks_part_2("inputs/5_input_example.txt");
 
-    for move_ in moves.iter() {
-        let start_stack_len = stacks[move_.start].len();
-        let elements = stacks[move_.start].split_off(start_stack_len - move_.qty);
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, "MCD");
+    }
+
+    #[test]
+    fn part_2_input() {
+        let answer = supply_stacks_part_2("inputs/5_input.txt");
+
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, "BRQWDBBJM");
+    }
+
+    #[test]
+    fn render_input_example() {
+        let input = fs::read_to_string("inputs/5_input_example.txt").unwrap();
+        let (mut cargo, moves) = parse_input(&input);
+
+        assert!(input.starts_with(&cargo.render()));
+
+        cargo.apply(&CrateMover9000, &moves[0]);
+        assert_eq!(cargo.render(), "[D]        \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n");
+    }
+
+    #[test]
+    fn limited_crane() {
+        let input = fs::read_to_string("inputs/5_input_example.txt").unwrap();
 
-        stacks[move_.end].extend(elements);
+        assert_eq!(rearrange(&input, &LimitedCrane { capacity: 1 }), "CMZ");
+        assert_eq!(rearrange(&input, &LimitedCrane { capacity: 3 }), "MCD");
+        assert_eq!(rearrange(&input, &LimitedCrane { capacity: 2 }), "MCZ");
     }
 
-    let mut result = Vec::new();
+    #[test]
+    fn multi_digit_labels() {
+        let labels = (1..=11).map(|label| format!("{label:^3}")).collect::<Vec<_>>().join(" ");
+        let input = format!("[A]{}[B]\n{labels}\n\nmove 1 from 11 to 1\nmove 2 from 1 to 10\n", " ".repeat(37));
+        let (mut cargo, moves) = parse_input(&input);
 
-    for stack in stacks.iter() {
-        result.push(stack.last().copied().unwrap())
+        assert!(input.starts_with(&cargo.render()));
+        for step in moves.iter() {
+            cargo.apply(&CrateMover9001, step);
+        }
+        assert_eq!(cargo.stacks[9], vec!['A', 'B']);
+        assert_eq!(cargo.top_crates(), "B");
     }
 
-    println!("{:?}", stacks);
+    #[test]
+    fn long_labels() {
+        let cargo = Cargo {
+            labels: ["1", "north", "10", "west", "east-2"].map(str::to_string).to_vec(),
+            stacks: vec![vec!['A'], vec!['B', 'C'], vec![], vec!['D'], vec!['E', 'F', 'G']],
+        };
+        let drawing = cargo.render();
+        assert_eq!(drawing, "                     [G] \n     [C]             [F] \n[A]  [B]       [D]   [E] \n 1  north 10  west east-2\n");
+
+        let (mut parsed, moves) = parse_input(&format!("{drawing}\nmove 2 from north to 10\nmove 1 from east-2 to west\n"));
+        assert_eq!(parsed.labels, cargo.labels);
+        assert_eq!(parsed.stacks, cargo.stacks);
+        assert_eq!(parsed.render(), drawing);
 
-    String::from_iter(result)
-}
\ No newline at end of file
+        for step in moves.iter() {
+            parsed.apply(&CrateMover9001, step);
+        }
+        assert_eq!(parsed.top_crates(), "ACGF");
+    }
+}
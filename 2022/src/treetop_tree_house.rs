@@ -1,280 +1,198 @@
-use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-fn read_input(file_name: &str) -> Vec<Vec<i32>> {
-    let file = File::open(file_name).unwrap();
-    let reader = BufReader::new(file);
-
-    reader.lines()
-        .map(|result| result.unwrap())
-        .map(|line| line.chars()
-            .map(|char| char.to_string().parse::<i32>().unwrap())
+use std::fs;
+
+fn parse_input(input: &str) -> Vec<Vec<u8>> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.trim()
+            .chars()
+            .map(|ch| ch.to_digit(10).unwrap_or_else(|| panic!("invalid height: {ch}")) as u8)
             .collect::<Vec<_>>())
-        .collect::<Vec<_>>()
+        .collect()
 }
 
-pub fn treetop_tree_house_part_1(file_name: &str) -> usize {
-    let rows = read_input(file_name);
-    // println!("{}",rows[1][0]);
-    let length = rows.len();
-    let width = rows[0].len();
-
-    let mut visible_cords = HashSet::new();
-
-    // from left to right
-    let mut row_index = 1;
-
-    while row_index < length - 1 {
-        let mut max_height = rows[row_index][0];
-        let mut col_index = 1;
-
-        while col_index < width - 1 {
-            let current_height = rows[row_index][col_index];
-
-            analyze_visibility("->", &mut visible_cords, row_index, col_index, &mut max_height, current_height);
-
-            if current_height == 9 {
-                break;
-            }
+/// Directions looked in from every tree.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Directions {
+    /// up, down, left and right, as in the puzzle
+    Four,
+    /// also along the diagonals
+    Eight,
+}
 
-            col_index += 1;
+impl Directions {
+    /// (row, col) steps
+    fn steps(&self) -> &'static [(isize, isize)] {
+        match self {
+            Directions::Four => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            Directions::Eight => &[(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (-1, 1), (1, -1), (1, 1)],
         }
-        row_index += 1;
     }
+}
 
-    // from right to left
-    let mut row_index = 1;
-
-    while row_index < length - 1 {
-        let mut max_height = rows[row_index][width - 1];
-        let mut col_index = width - 1;
+/// Visibility from the edge and scenic score of every tree.
+pub struct Analysis {
+    visible: Vec<Vec<bool>>,
+    scores: Vec<Vec<u64>>,
+}
 
-        while col_index > 0 {
-            let current_height = rows[row_index][col_index];
+/// Looks from every tree in every direction.
+///
+/// Each line of trees along a direction is walked once starting from the edge the trees look at, with a stack of
+/// the trees seen so far that are not hidden behind a later tree at least as tall. The first tree left on the stack
+/// after popping the smaller ones is where the view ends, and a tree is visible from the edge if nothing is left.
+pub fn analyse(heights: &[Vec<u8>], directions: Directions) -> Analysis {
+    let (rows, cols) = (heights.len(), heights.first().map_or(0, |row| row.len()));
+    let inside = |row: isize, col: isize| (0..rows as isize).contains(&row) && (0..cols as isize).contains(&col);
+
+    let mut analysis = Analysis { visible: vec![vec![false; cols]; rows], scores: vec![vec![1; cols]; rows] };
+
+    for &(d_row, d_col) in directions.steps() {
+        // trees whose view in the direction ends at the edge right away start the lines
+        let starts = (0..rows as isize)
+            .flat_map(|row| (0..cols as isize).map(move |col| (row, col)))
+            .filter(|&(row, col)| !inside(row + d_row, col + d_col));
+
+        for (start_row, start_col) in starts {
+            // (position in the line, height)
+            let mut stack: Vec<(usize, u8)> = Vec::new();
+            let (mut row, mut col, mut index) = (start_row, start_col, 0);
+
+            while inside(row, col) {
+                let height = heights[row as usize][col as usize];
+                while stack.last().is_some_and(|&(_, other)| other < height) {
+                    stack.pop();
+                }
 
-            analyze_visibility("<-", &mut visible_cords, row_index, col_index, &mut max_height, current_height);
+                let distance = match stack.last() {
+                    Some(&(blocking, _)) => index - blocking,
+                    None => index,
+                };
+                analysis.scores[row as usize][col as usize] *= distance as u64;
+                analysis.visible[row as usize][col as usize] |= stack.is_empty();
 
-            if current_height == 9 {
-                break;
+                stack.push((index, height));
+                (row, col, index) = (row - d_row, col - d_col, index + 1);
             }
-
-            col_index -= 1;
         }
-        row_index += 1;
     }
 
-    // from top to bottom
-    let mut col_index = 1;
-
-    while col_index < width - 1 {
-        let mut max_height = rows[0][col_index];
-        let mut row_index = 1;
-
-        while row_index < length - 1 {
-            let current_height = rows[row_index][col_index];
-
-            analyze_visibility("↓", &mut visible_cords, row_index, col_index, &mut max_height, current_height);
+    analysis
+}
 
-            if current_height == 9 {
-                break;
-            }
-            row_index += 1;
-        }
-        col_index += 1;
+impl Analysis {
+    pub fn visible_count(&self) -> usize {
+        self.visible.iter().flatten().filter(|&&visible| visible).count()
     }
 
-    // from bottom to top
-    let mut col_index = 1;
-
-    while col_index < width - 1 {
-        let mut max_height = rows[length - 1][col_index];
-        let mut row_index = length - 1;
-
-        while row_index > 0 {
-            let current_height = rows[row_index][col_index];
-
-            analyze_visibility("↑", &mut visible_cords, row_index, col_index, &mut max_height, current_height);
-
-            if current_height == 9 {
-                break;
-            }
-            row_index -= 1;
-        }
-        col_index += 1;
+    pub fn max_score(&self) -> u64 {
+        self.scores.iter().flatten().copied().max().unwrap_or(0)
     }
 
-    let visible_generalized = visible_cords.iter().map(|(&_, r, c)| (r, c)).collect::<HashSet<_>>();
+    /// (row, col) and scenic score of the k trees with the highest scores, ties broken by position.
+    pub fn top_trees(&self, k: usize) -> Vec<((usize, usize), u64)> {
+        let mut trees = self.scores.iter()
+            .enumerate()
+            .flat_map(|(row, scores)| scores.iter().enumerate().map(move |(col, &score)| ((row, col), score)))
+            .collect::<Vec<_>>();
+        trees.sort_by_key(|&(position, score)| (u64::MAX - score, position));
+        trees.truncate(k);
 
-    println!("{:?}", visible_cords);
-    println!("{:?}", visible_generalized);
+        trees
+    }
 
-    (width + length) * 2 - 4 + visible_generalized.len()
-}
+    /// Scenic scores drawn with characters from ` ` for zero to `@` for the highest score.
+    pub fn heat_map(&self) -> String {
+        const SHADES: &[u8] = b" .:-=+*#%@";
+        let max = self.max_score().max(1);
 
-fn analyze_visibility<'a>(direction: &'a str, visible_cords: &mut HashSet<(&'a str, usize, usize)>, row_index: usize, col_index: usize, max_height: &mut i32, current_height: i32) {
-    if current_height > *max_height {
-        visible_cords.insert((direction, row_index, col_index));
-        *max_height = current_height;
+        self.scores.iter()
+            .map(|row| row.iter()
+                .map(|&score| SHADES[(score * (SHADES.len() as u64 - 1)).div_ceil(max) as usize] as char)
+                .collect::<String>() + "\n")
+            .collect()
     }
 }
 
-pub fn treetop_tree_house_part_2(file_name: &str) -> usize {
-    let rows = read_input(file_name);
-    let length = rows.len();
-    let width = rows[0].len();
-
-    let mut lr_scenic_score = vec![vec![0; width]; length];
-    let mut rl_scenic_score = vec![vec![0; width]; length];
-    let mut tb_scenic_score = vec![vec![0; width]; length];
-    let mut bt_scenic_score = vec![vec![0; width]; length];
+pub fn treetop_tree_house_part_1(file_name: &str) -> usize {
+    treetop_tree_house_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-    let mut row_index = 1;
+pub fn treetop_tree_house_part_1_from_str(input: &str) -> usize {
+    analyse(&parse_input(input), Directions::Four).visible_count()
+}
 
-    // from left to right
-    while row_index < length - 1 {
-        let mut col_index = 1;
+pub fn treetop_tree_house_part_2(file_name: &str) -> u64 {
+    treetop_tree_house_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
 
-        while col_index < width - 1 {
-            let current_height = rows[row_index][col_index];
+pub fn treetop_tree_house_part_2_from_str(input: &str) -> u64 {
+    let analysis = analyse(&parse_input(input), Directions::Four);
+    println!("{}", analysis.heat_map());
 
-            let mut dist = 1;
-            while col_index as i32 - dist as i32 >= 0 {
-                let previous_height = rows[row_index][col_index - dist];
+    analysis.max_score()
+}
 
-                dist += 1;
-                if previous_height >= current_height {
-                    break;
-                }
-            }
-            dist -= 1;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            lr_scenic_score[row_index][col_index] = dist;
+    #[test]
+    fn part_1_input_example() {
+        let answer = treetop_tree_house_part_1("inputs/8_input_example.txt");
 
-            col_index += 1;
-        }
-        row_index += 1;
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 21);
     }
 
-    // from right to left
-    let mut row_index = 1;
-
-    while row_index < length - 1 {
-        let mut col_index = width - 2;
-
-        while col_index > 0 {
-            let current_height = rows[row_index][col_index];
+    #[test]
+    fn part_1_input() {
+        let answer = treetop_tree_house_part_1("inputs/8_input.txt");
 
-            let mut dist = 1;
-            while col_index + dist < width {
-                let next_height = rows[row_index][col_index + dist];
-
-                dist += 1;
-                if next_height >= current_height {
-                    break;
-                }
-            }
-            dist -= 1;
-
-            rl_scenic_score[row_index][col_index] = dist;
-
-            col_index -= 1;
-        }
-        row_index += 1;
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 1676);
     }
 
-    // from top to bottom
-    let mut col_index = 1;
+    #[test]
+    fn part_2_input_example() {
+        let answer = treetop_tree_house_part_2("inputs/8_input_example.txt");
 
-    while col_index < width - 1 {
-        let mut row_index = 1;
-
-        while row_index < length - 1 {
-            let current_height = rows[row_index][col_index];
-
-            let mut dist = 1;
-            while row_index + dist < length {
-                let next_height = rows[row_index + dist][col_index];
-
-                dist += 1;
-                if next_height >= current_height {
-                    break;
-                }
-            }
-            dist -= 1;
-
-            tb_scenic_score[row_index][col_index] = dist;
-
-            row_index += 1;
-        }
-        col_index += 1;
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 8);
     }
 
-    // from bottom to top
-    let mut col_index = 1;
-
-    while col_index < width - 1 {
-        let mut row_index = length - 2;
+    #[test]
+    fn part_2_input() {
+        let answer = treetop_tree_house_part_2("inputs/8_input.txt");
 
-        while row_index > 0 {
-            let current_height = rows[row_index][col_index];
-
-            let mut dist = 1;
-            while row_index as i32 - dist as i32 >= 0 {
-                let next_height = rows[row_index - dist][col_index];
-
-                dist += 1;
-                if next_height >= current_height {
-                    break;
-                }
-            }
-            dist -= 1;
-
-            bt_scenic_score[row_index][col_index] = dist;
-
-            row_index -= 1;
-        }
-        col_index += 1;
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 313200);
     }
 
-    println!("->");
-    print(&lr_scenic_score);
-
-    println!("<-");
-    print(&rl_scenic_score);
-
-    println!("↓");
-    print(&tb_scenic_score);
+    #[test]
+    fn top_trees_and_heat_map() {
+        let heights = parse_input(&fs::read_to_string("inputs/8_input_example.txt").unwrap());
+        let analysis = analyse(&heights, Directions::Four);
 
-    println!("↑");
-    print(&bt_scenic_score);
-
-    let mut max_scenic_score = 0;
-    let mut total_scenic_score = vec![vec![0; width]; length];
-    for (i, row) in total_scenic_score.iter_mut().enumerate() {
-        for (j, elem) in row.iter_mut().enumerate() {
-            *elem = lr_scenic_score[i][j] * rl_scenic_score[i][j] * tb_scenic_score[i][j] * bt_scenic_score[i][j];
-
-            if *elem > max_scenic_score {
-                max_scenic_score = *elem;
-            }
-        }
+        assert_eq!(analysis.top_trees(2), vec![((3, 2), 8), ((2, 1), 6)]);
+        assert_eq!(analysis.heat_map(), "     \n :+: \n #:- \n :@= \n     \n");
     }
 
-    println!("total scenic score:");
-    print(&total_scenic_score);
-
-    max_scenic_score
-}
-
-fn print(rl_scenic_score: &[Vec<usize>]) {
-    for row in rl_scenic_score.iter() {
-        let row_str = row
-            .iter()
-            .map(|elem| format!("{:>5}", elem))
-            .collect::<Vec<String>>()
-            .join(" ");
-        println!("{}", row_str);
+    #[test]
+    fn eight_directions() {
+        let heights = parse_input("111\n121\n111\n");
+
+        let four = analyse(&heights, Directions::Four);
+        let eight = analyse(&heights, Directions::Eight);
+        assert_eq!((four.visible_count(), four.max_score()), (9, 1));
+        assert_eq!((eight.visible_count(), eight.max_score()), (9, 1));
+
+        let heights = parse_input("0000\n0300\n0020\n0000\n");
+        let four = analyse(&heights, Directions::Four);
+        let eight = analyse(&heights, Directions::Eight);
+        assert_eq!((four.scores[1][1], four.scores[2][2]), (4, 4));
+        // the 3 sees over the 2 along the diagonal, the 2 is blocked by it
+        assert_eq!((eight.scores[1][1], eight.scores[2][2]), (8, 4));
+        assert_eq!(eight.top_trees(1), vec![((1, 1), 8)]);
     }
-    println!()
-}
\ No newline at end of file
+}
@@ -1,9 +1,11 @@
 use std::fs;
 
 pub fn tuning_trouble_part_1(file_name: &str) -> usize {
-    let contents = fs::read_to_string(file_name).expect("Should have been able to read the file");
+    tuning_trouble_part_1_from_str(&fs::read_to_string(file_name).expect("Should have been able to read the file"))
+}
 
-    let chars = contents.chars().collect::<Vec<_>>();
+pub fn tuning_trouble_part_1_from_str(input: &str) -> usize {
+    let chars = input.chars().collect::<Vec<_>>();
     let mut index = 0;
 
     loop {
@@ -29,9 +31,11 @@ fn contains_repetition(chars: &[char]) -> bool {
 }
 
 pub fn tuning_trouble_part_2(file_name: &str) -> usize {
-    let contents = fs::read_to_string(file_name).expect("Should have been able to read the file");
+    tuning_trouble_part_2_from_str(&fs::read_to_string(file_name).expect("Should have been able to read the file"))
+}
 
-    let chars = contents.chars().collect::<Vec<_>>();
+pub fn tuning_trouble_part_2_from_str(input: &str) -> usize {
+    let chars = input.chars().collect::<Vec<_>>();
     let mut index = 0;
 
     loop {
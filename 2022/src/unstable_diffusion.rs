@@ -1,224 +1,244 @@
-use std::collections::{HashSet, VecDeque};
-use std::fmt::{Debug, Formatter};
 use std::fs;
-use std::hash::Hash;
-use std::ops::Add;
 
-use itertools::{Itertools, MinMaxResult};
+use cellular_automaton::{Automaton, Grid, GrowingGrid, Point};
 
-#[derive(Eq, PartialEq, Hash, Copy, Clone, Ord, PartialOrd)]
-struct Point {
-    row: i32,
-    col: i32,
+/// Direction an elf can propose to move in.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Direction {
+    North,
+    South,
+    West,
+    East,
 }
 
-impl Point {
-    fn dist(&self, other: &Point) -> f32 {
-        let v = (self.col - other.col) * (self.col - other.col) + (self.row - other.row) * (self.row - other.row);
-        (v as f32).sqrt()
+impl Direction {
+    /// Tiles checked before moving in the direction; the middle one is the destination.
+    fn checked(&self) -> [Point; 3] {
+        match self {
+            Direction::North => [Point::new(-1, -1), Point::new(-1, 0), Point::new(-1, 1)],
+            Direction::South => [Point::new(1, -1), Point::new(1, 0), Point::new(1, 1)],
+            Direction::West => [Point::new(-1, -1), Point::new(0, -1), Point::new(1, -1)],
+            Direction::East => [Point::new(-1, 1), Point::new(0, 1), Point::new(1, 1)],
+        }
     }
 }
 
-impl Debug for Point {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.row, self.col)
-    }
+/// Which direction is considered first in each round.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Rotation {
+    /// the first direction moves to the end of the list after every round
+    Cycle,
+    /// directions are always considered in the order of the list
+    Fixed,
 }
 
-impl Add for Point {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            row: self.row + rhs.row,
-            col: self.col + rhs.col,
+#[derive(Clone, Debug)]
+pub struct Rules {
+    pub directions: Vec<Direction>,
+    pub rotation: Rotation,
+}
 
+impl Default for Rules {
+    /// Rules of the puzzle.
+    fn default() -> Self {
+        Rules {
+            directions: vec![Direction::North, Direction::South, Direction::West, Direction::East],
+            rotation: Rotation::Cycle,
         }
     }
 }
 
-fn read_input(file_name: &str) -> HashSet<Point> {
-    fs::read_to_string(file_name)
-        .unwrap()
-        .split("\r\n")
+impl Rules {
+    /// Directions in the order they are considered in given round, counted from 1.
+    fn directions_in(&self, round: usize) -> impl Iterator<Item = &Direction> {
+        let first = match self.rotation {
+            Rotation::Cycle => (round - 1) % self.directions.len(),
+            Rotation::Fixed => 0,
+        };
+
+        self.directions[first..].iter().chain(&self.directions[..first])
+    }
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct RoundStats {
+    /// counted from 1
+    pub round: usize,
+    pub moved: usize,
+    /// top-left and bottom-right elf corners of the smallest rectangle holding all elves
+    pub bounding_box: (Point, Point),
+    /// tiles in the bounding box without an elf
+    pub empty_tiles: usize,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub rounds: Vec<RoundStats>,
+    /// first round in which no elf moved, if it was reached
+    pub settled: Option<usize>,
+}
+
+pub struct Diffusion {
+    elves: GrowingGrid<bool>,
+    automaton: Automaton<'static, GrowingGrid<bool>>,
+}
+
+fn parse_input(input: &str) -> GrowingGrid<bool> {
+    input
+        .lines()
         .enumerate()
         .flat_map(|(row, line)| line.chars()
             .enumerate()
             .filter(|(_col, ch)| *ch == '#')
-            .map(move |(col, _ch)| Point { row: row as i32, col: col as i32 }))
-        .collect::<HashSet<_>>()
+            .map(move |(col, _ch)| (Point::new(row as i32, col as i32), true)))
+        .collect()
 }
 
-pub fn unstable_diffusion_part_1(filename: &str) -> usize {
-    let set = unstable_diffusion(filename, 10).1;
+/// Elf stays in place when there is nobody around, otherwise it proposes the first free direction.
+/// Elves proposing the same tile stay in place.
+fn diffusion(rules: Rules) -> Automaton<'static, GrowingGrid<bool>> {
+    Automaton::proposal(move |elves: &GrowingGrid<bool>, elf, round| {
+        if elf.neighbours_8().iter().all(|p| !elves.get(*p)) {
+            return None;
+        }
 
-    let min_max_col = set.iter().map(|p| p.col).minmax();
-    let min_max_row = set.iter().map(|p| p.row).minmax();
+        rules.directions_in(round)
+            .map(Direction::checked)
+            .find(|checked| checked.iter().all(|p| !elves.get(elf + *p)))
+            .map(|checked| elf + checked[1])
+    })
+}
 
-    if let (MinMaxResult::MinMax(min_row, max_row), MinMaxResult::MinMax(min_col, max_col)) = (min_max_row, min_max_col) {
-        let width = (min_col - max_col).unsigned_abs() as usize + 1;
-        let length = (min_row - max_row).unsigned_abs() as usize + 1;
+impl Diffusion {
+    pub fn new(input: &str, rules: Rules) -> Self {
+        let elves = parse_input(input);
+        assert!(!elves.is_empty(), "no elves");
+        assert!(!rules.directions.is_empty(), "no directions");
 
-        width * length - set.len()
-    } else { panic!() }
-}
+        Diffusion { elves, automaton: diffusion(rules) }
+    }
 
-pub fn unstable_diffusion_part_2(filename: &str) -> usize {
-    unstable_diffusion(filename, usize::MAX).0
-}
-
-fn unstable_diffusion(filename: &str, rounds: usize) -> (usize, HashSet<Point>) {
-    let mut set = read_input(filename);
-    // println!("== Initial State ==");
-    // print_input(&set);
-    // println!("{set:?}");
-
-    let mut direction_points = VecDeque::from(
-        [
-            [Point { row: -1, col: -1 }, Point { row: -1, col: 0 }, Point { row: -1, col: 1 }], // NW, N, NE
-            [Point { row: 1, col: -1 }, Point { row: 1, col: 0 }, Point { row: 1, col: 1 }], // SW, S, SE
-            [Point { row: -1, col: -1 }, Point { row: 0, col: -1 }, Point { row: 1, col: -1 }], // NW, W, SW
-            [Point { row: -1, col: 1 }, Point { row: 0, col: 1 }, Point { row: 1, col: 1 }] // NE, E, SE
-        ]);
-
-    let mut far_points = VecDeque::from(
-        [
-            Point { row: -2, col: 0 }, // N
-            Point { row: 2, col: 0 }, // S
-            Point { row: 0, col: -2 }, // W
-            Point { row: 0, col: 2 } // E
-        ]);
-
-    for round_no in 1..=rounds {
-        let mut no_elf_moved = true;
-        let set_cloned = set.clone();
-
-        for elf_point in set_cloned.iter() {
-            let neighbors = set_cloned.iter()
-                .filter(|&p| p.dist(elf_point) <= 2f32)
-                .collect::<HashSet<_>>();
-
-            // check if elf can go in any direction
-            for (points, far_point) in direction_points.iter().zip(far_points.iter()) {
-                if neighbors.iter().any(|&&p| points.iter().any(|dir_p| *elf_point + *dir_p == p)) {
-                    // cannot go in this direction, there is an elf in one of three points
-                    continue;
-                }
-
-                let has_adjacent_elf = direction_points.iter()
-                    .flatten()
-                    .unique()
-                    .map(|p| *p + *elf_point)
-                    .any(|p| neighbors.contains(&p));
-
-                if !has_adjacent_elf {
-                    break;
-                }
-
-                // check if there is an elf with which the current elf might have a collision
-                let collision_elf_point = *elf_point + *far_point;
-                let new_tile_point = *elf_point + points[1];
-
-                // there might be collision with an elf
-                if neighbors.contains(&collision_elf_point) {
-                    let neighbors_of_collision_elf = set_cloned.iter()
-                        .filter(|&p| p.dist(&collision_elf_point) < 2f32)
-                        .collect::<HashSet<_>>();
-
-                    let wanted_tile = check_elf_dir(&collision_elf_point, &neighbors_of_collision_elf, &direction_points);
-                    let is_collision = match wanted_tile {
-                        None => false,
-                        Some(p) => p == new_tile_point
-                    };
-
-                    if is_collision {
-                        break;
-                    }
-                }
-
-                update_elf_pos(&mut set, elf_point, new_tile_point);
-                no_elf_moved = false;
+    pub fn bounding_box(&self) -> (Point, Point) {
+        self.elves.bounding_box().unwrap()
+    }
+
+    pub fn empty_tiles(&self) -> usize {
+        let (min, max) = self.bounding_box();
+
+        ((max.row - min.row + 1) * (max.col - min.col + 1)) as usize - self.elves.len()
+    }
+
+    /// Plays one round.
+    pub fn step(&mut self) -> RoundStats {
+        let moved = self.automaton.step(&mut self.elves);
+
+        RoundStats { round: self.automaton.rounds(), moved, bounding_box: self.bounding_box(), empty_tiles: self.empty_tiles() }
+    }
+
+    /// Plays rounds until no elf moves or the number of rounds is reached.
+    pub fn run(&mut self, max_rounds: usize) -> Report {
+        let mut report = Report { rounds: Vec::new(), settled: None };
+
+        for _ in 0..max_rounds {
+            let stats = self.step();
+            report.rounds.push(stats);
+
+            if stats.moved == 0 {
+                report.settled = Some(stats.round);
                 break;
             }
         }
 
-        move_first_to_last(&mut direction_points, &mut far_points);
+        report
+    }
+}
 
-        if round_no % 10 == 0 {
-            // println!("\n== End of Round {} ==", round_no);
-            // print_input(&set);
-        }
+pub fn unstable_diffusion_part_1(filename: &str) -> usize {
+    unstable_diffusion_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
 
-        if no_elf_moved {
-            println!("no elf moved!!");
-            return (round_no, set.clone());
-        }
-    }
+pub fn unstable_diffusion_part_1_from_str(input: &str) -> usize {
+    let mut diffusion = Diffusion::new(input, Rules::default());
+    diffusion.run(10);
 
-    (rounds, set.clone())
+    diffusion.empty_tiles()
 }
 
-fn move_first_to_last(direction_points: &mut VecDeque<[Point; 3]>, far_points: &mut VecDeque<Point>) {
-    let t1 = direction_points.pop_front().unwrap();
-    direction_points.push_back(t1);
-    let t2 = far_points.pop_front().unwrap();
-    far_points.push_back(t2);
+pub fn unstable_diffusion_part_2(filename: &str) -> usize {
+    unstable_diffusion_part_2_from_str(&fs::read_to_string(filename).unwrap())
 }
 
-fn update_elf_pos(set: &mut HashSet<Point>, current_point: &Point, new_point: Point) {
-    let is_removed = set.remove(current_point);
-    if !is_removed {
-        panic!()
-    }
-    let is_inserted = set.insert(new_point);
-    if !is_inserted {
-        panic!()
-    }
+pub fn unstable_diffusion_part_2_from_str(input: &str) -> usize {
+    Diffusion::new(input, Rules::default()).run(usize::MAX).settled.unwrap()
 }
 
-fn check_elf_dir(elf_point: &Point, set: &HashSet<&Point>, directions_points: &VecDeque<[Point; 3]>) -> Option<Point> {
-    let has_adjacent_elf = directions_points.iter()
-        .flatten()
-        .unique()
-        .map(|p| *p + *elf_point)
-        .any(|p| set.contains(&p));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_input_example_1() {
+        let answer = unstable_diffusion_part_1("inputs/23_input_example.txt");
 
-    if !has_adjacent_elf {
-        return None;
+        println!("part 1 - example - answer: {:?}", answer);
+        assert_eq!(answer, 110);
     }
 
-    for points in directions_points {
-        if set.iter().any(|&&p| p == *elf_point + points[0] || p == *elf_point + points[1] || p == *elf_point + points[2]) {
-            // cannot go in this direction
-            continue;
-        }
+    #[test]
+    fn part_1_input() {
+        let answer = unstable_diffusion_part_1("inputs/23_input.txt");
 
-        return Some(*elf_point + points[1]);
+        println!("part 1 - original - answer: {:?}", answer);
+        assert_eq!(answer, 4302);
     }
 
-    panic!();
-}
+    #[test]
+    fn part_2_input_example_1() {
+        let answer = unstable_diffusion_part_2("inputs/23_input_example.txt");
+
+        println!("part 2 - example - answer: {:?}", answer);
+        assert_eq!(answer, 20);
+    }
 
-#[allow(dead_code)]
-fn print_input(set: &HashSet<Point>) {
-    let min_max_col = set.iter().map(|p| p.col).minmax();
-    let min_max_row = set.iter().map(|p| p.row).minmax();
+    #[test]
+    fn part_2_input() {
+        let answer = unstable_diffusion_part_2("inputs/23_input.txt");
 
-    if let (MinMaxResult::MinMax(min_row, max_row), MinMaxResult::MinMax(min_col, max_col)) = (min_max_row, min_max_col) {
-        print_tiles(set, min_row, max_row, min_col, max_col);
+        println!("part 2 - original - answer: {:?}", answer);
+        assert_eq!(answer, 1025);
     }
-}
 
-#[allow(dead_code)]
-fn print_input_padded(set: &HashSet<Point>, min_row: i32, max_row: i32, min_col: i32, max_col: i32) {
-    print_tiles(set, min_row, max_row, min_col, max_col);
-}
+    #[test]
+    fn statistics_small_example() {
+        let input = fs::read_to_string("inputs/23_input_example_1.txt").unwrap();
+        let report = Diffusion::new(&input, Rules::default()).run(10);
 
-fn print_tiles(set: &HashSet<Point>, min_row: i32, max_row: i32, min_col: i32, max_col: i32) {
-    for row in min_row..=max_row {
-        for col in min_col..=max_col {
-            print!("{}", if set.contains(&Point { row, col }) { "#" } else { "." });
-        }
-        println!();
+        assert_eq!(report.settled, Some(4));
+        assert_eq!(report.rounds.iter().map(|stats| stats.moved).collect::<Vec<_>>(), vec![3, 5, 3, 0]);
+        assert_eq!(report.rounds[2].bounding_box, (Point::new(-1, -2), Point::new(4, 2)));
+        assert_eq!(report.rounds[2].empty_tiles, 25);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn custom_rules() {
+        let rules = Rules { directions: vec![Direction::East, Direction::West], rotation: Rotation::Fixed };
+        let report = Diffusion::new("##", rules).run(10);
+
+        assert_eq!(report.settled, Some(2));
+        assert_eq!(report.rounds[0].bounding_box, (Point::new(0, -1), Point::new(0, 2)));
+        assert_eq!(report.rounds[0].empty_tiles, 2);
+    }
+
+    #[test]
+    fn grid_grows() {
+        let input = fs::read_to_string("inputs/23_input.txt").unwrap();
+        let mut diffusion = Diffusion::new(&input, Rules::default());
+        let elves = diffusion.elves.len();
+
+        diffusion.run(usize::MAX);
+        let (min, _max) = diffusion.bounding_box();
+        assert!(min.row < 0 && min.col < 0);
+        assert_eq!(diffusion.elves.len(), elves);
+        assert_eq!(diffusion.elves.occupied().len(), elves);
+    }
+}
@@ -0,0 +1,21 @@
+//! Runs in a headless wasm runner, see ../playground/build.sh
+
+#![cfg(target_arch = "wasm32")]
+
+use advent_of_code_2022::playground::{solve, solved_days};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn solve_input_example() {
+    let input = include_str!("../inputs/14_input_example.txt");
+
+    assert_eq!(solve(14, 1, input), Ok("24".to_string()));
+    assert_eq!(solve(14, 2, input), Ok("93".to_string()));
+}
+
+#[wasm_bindgen_test]
+fn solve_missing_day() {
+    assert!(solve(25, 2, "").is_err());
+    assert!(solve(26, 1, "").is_err());
+    assert_eq!(solved_days().len(), 25);
+}
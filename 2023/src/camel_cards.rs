@@ -3,8 +3,8 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
+use std::io::BufRead;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum HandType {
@@ -167,8 +167,11 @@ fn get_hand_type_no_jokers(cards_occurrences: HashMap<char, u32>) -> HandType {
 }
 
 pub fn read_input(file_name: &str, deck: impl Deck) -> Vec<PlayerHand> {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
+    read_input_from_str(&fs::read_to_string(file_name).unwrap(), deck)
+}
+
+pub fn read_input_from_str(input: &str, deck: impl Deck) -> Vec<PlayerHand> {
+    let mut reader = input.as_bytes();
     let mut buf = String::new();
     let mut poker_hands = Vec::new();
 
@@ -194,13 +197,21 @@ pub fn read_input(file_name: &str, deck: impl Deck) -> Vec<PlayerHand> {
 }
 
 fn camel_cards_part_1(filename: &str) -> u32 {
-    let mut player_hands = read_input(filename, SimpleDeck);
+    camel_cards_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn camel_cards_part_1_from_str(input: &str) -> u32 {
+    let mut player_hands = read_input_from_str(input, SimpleDeck);
 
     solve(&mut player_hands)
 }
 
 fn camel_cards_part_2(filename: &str) -> u32 {
-    let mut player_hands = read_input(filename, DeckWithJokers);
+    camel_cards_part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn camel_cards_part_2_from_str(input: &str) -> u32 {
+    let mut player_hands = read_input_from_str(input, DeckWithJokers);
 
     solve(&mut player_hands)
 }
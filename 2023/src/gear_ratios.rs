@@ -1,7 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
+use std::io::BufRead;
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
 struct Coord {
@@ -24,8 +24,11 @@ pub struct TaskData {
 }
 
 pub fn read_input_part_1(file_name: &str) -> TaskData {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
+    read_input_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn read_input_part_1_from_str(input: &str) -> TaskData {
+    let mut reader = input.as_bytes();
     let mut buf = String::new();
 
     let mut numbers = Vec::new();
@@ -97,7 +100,11 @@ fn get_neighbors(c: &Coord) -> [Coord; 8] {
 }
 
 fn gear_ratios_part_1(filename: &str) -> u32 {
-    let task_data = read_input_part_1(filename);
+    gear_ratios_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn gear_ratios_part_1_from_str(input: &str) -> u32 {
+    let task_data = read_input_part_1_from_str(input);
     let mut numbers = Vec::new();
     let mut visited_number_indices = Vec::new();
 
@@ -121,7 +128,11 @@ fn gear_ratios_part_1(filename: &str) -> u32 {
 }
 
 fn gear_ratios_part_2(filename: &str) -> u32 {
-    let data = read_input_part_1(filename);
+    gear_ratios_part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn gear_ratios_part_2_from_str(input: &str) -> u32 {
+    let data = read_input_part_1_from_str(input);
     let mut sum = 0;
 
     for (ch, coord) in data.symbols.iter() {
@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet};
+use std::fs;
 use std::io::BufRead;
 
 pub enum Choice {
@@ -13,8 +14,11 @@ pub struct NodeConnections {
 }
 
 pub fn read_input(file_name: &str) -> (Vec<Choice>, Vec<NodeConnections>) {
-    let file = std::fs::File::open(file_name).unwrap();
-    let mut reader = std::io::BufReader::new(file);
+    read_input_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn read_input_from_str(input: &str) -> (Vec<Choice>, Vec<NodeConnections>) {
+    let mut reader = input.as_bytes();
 
     let mut buf = String::new();
     _ = reader.read_line(&mut buf).unwrap();
@@ -80,7 +84,11 @@ fn make_jump_array(node_connections: Vec<NodeConnections>) -> Vec<[usize; 2]> {
 }
 
 fn haunted_wasteland_part_1(filename: &str) -> usize {
-    let (choices, node_connections) = read_input(filename);
+    haunted_wasteland_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn haunted_wasteland_part_1_from_str(input: &str) -> usize {
+    let (choices, node_connections) = read_input_from_str(input);
 
     let nodes_number = node_connections.len();
     let jump_array = make_jump_array(node_connections);
@@ -107,7 +115,11 @@ fn haunted_wasteland_part_1(filename: &str) -> usize {
 
 
 fn haunted_wasteland_part_2(filename: &str) -> usize {
-    let _ = read_input(filename);
+    haunted_wasteland_part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn haunted_wasteland_part_2_from_str(input: &str) -> usize {
+    let _ = read_input_from_str(input);
 
     todo!()
 }
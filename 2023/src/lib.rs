@@ -5,3 +5,4 @@ mod seed_fertilizer;
 mod camel_cards;
 mod wait_for_it;
 mod haunted_wasteland;
+pub mod playground;
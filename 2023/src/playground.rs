@@ -0,0 +1,98 @@
+use std::any::Any;
+use std::panic;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::camel_cards::{camel_cards_part_1_from_str, camel_cards_part_2_from_str};
+use crate::gear_ratios::{gear_ratios_part_1_from_str, gear_ratios_part_2_from_str};
+use crate::scratchcards::{scratchcards_part_1_from_str, scratchcards_part_2_from_str};
+use crate::seed_fertilizer::seed_fertilizer_part_1_from_str;
+use crate::trebuchet::{read_input_part_1_from_str, read_input_part_2_from_str};
+use crate::wait_for_it::{wait_for_it_part_1_from_str, wait_for_it_part_2_from_str};
+
+type Solver = fn(&str) -> String;
+
+/// Solution of given day and part, taking the puzzle input.
+fn solver(day: u32, part: u32) -> Option<Solver> {
+    let solver: Solver = match (day, part) {
+        (1, 1) => |input| read_input_part_1_from_str(input).iter().sum::<u32>().to_string(),
+        (1, 2) => |input| read_input_part_2_from_str(input).iter().sum::<u32>().to_string(),
+        (3, 1) => |input| gear_ratios_part_1_from_str(input).to_string(),
+        (3, 2) => |input| gear_ratios_part_2_from_str(input).to_string(),
+        (4, 1) => |input| scratchcards_part_1_from_str(input).to_string(),
+        (4, 2) => |input| scratchcards_part_2_from_str(input).to_string(),
+        (5, 1) => |input| seed_fertilizer_part_1_from_str(input).to_string(),
+        (6, 1) => |input| wait_for_it_part_1_from_str(input).to_string(),
+        (6, 2) => |input| wait_for_it_part_2_from_str(input).to_string(),
+        (7, 1) => |input| camel_cards_part_1_from_str(input).to_string(),
+        (7, 2) => |input| camel_cards_part_2_from_str(input).to_string(),
+        _ => return None,
+    };
+
+    Some(solver)
+}
+
+/// Makes panics visible in the browser console, since they abort the wasm module.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(start)]
+fn start() {
+    console_error_panic_hook::set_once();
+}
+
+/// Solves the puzzle for the input given as a string.
+/// Fails if there is no solution of given day and part.
+/// A panic of the solution is caught as an error natively only: panics can't unwind in wasm,
+/// they abort the module and reach JS as a `RuntimeError`, see ../playground/index.html.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn solve(day: u32, part: u32, input: &str) -> Result<String, String> {
+    let solver = solver(day, part).ok_or(format!("no solution of day {day} part {part}"))?;
+
+    panic::catch_unwind(|| solver(input)).map_err(panic_message)
+}
+
+/// Days having a solution of at least one part.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn solved_days() -> Vec<u32> {
+    (1..=25)
+        .filter(|&day| solver(day, 1).is_some() || solver(day, 2).is_some())
+        .collect()
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "solution panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn solve_input_example() {
+        let input = fs::read_to_string("inputs/6_input_example.txt").unwrap();
+
+        assert_eq!(solve(6, 1, &input), Ok("288".to_string()));
+        assert_eq!(solve(6, 2, &input), Ok("71503".to_string()));
+    }
+
+    #[test]
+    fn solve_missing_day() {
+        assert!(solve(2, 1, "").is_err());
+        assert!(solve(5, 2, "").is_err());
+        assert!(solve(8, 1, "").is_err());
+        assert_eq!(solved_days(), vec![1, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn solve_invalid_input() {
+        assert!(solve(7, 1, "32").is_err());
+    }
+}
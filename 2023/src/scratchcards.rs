@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
+use std::io::BufRead;
 
 #[derive(Debug)]
 pub struct Card {
@@ -16,8 +16,11 @@ impl Card {
 }
 
 pub fn read_input(file_name: &str) -> Vec<Card> {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
+    read_input_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn read_input_from_str(input: &str) -> Vec<Card> {
+    let mut reader = input.as_bytes();
     let mut buf = String::new();
 
     let mut cards = Vec::new();
@@ -60,13 +63,21 @@ pub fn read_input(file_name: &str) -> Vec<Card> {
 }
 
 fn scratchcards_part_1(filename: &str) -> u32 {
-    let cards = read_input(filename);
+    scratchcards_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn scratchcards_part_1_from_str(input: &str) -> u32 {
+    let cards = read_input_from_str(input);
 
     cards.iter().map(|card| card.calc_win()).sum()
 }
 
 fn scratchcards_part_2(filename: &str) -> u32 {
-    let cards = read_input(filename);
+    scratchcards_part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn scratchcards_part_2_from_str(input: &str) -> u32 {
+    let cards = read_input_from_str(input);
     let mut cards_qty = HashMap::<usize, u32>::from_iter(cards.iter().map(|c| (c.number, 1)));
 
     // cards are sorted by number already
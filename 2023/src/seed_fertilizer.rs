@@ -1,9 +1,9 @@
 #![ allow(warnings)]
 use std::collections::hash_map::Keys;
 use std::collections::HashMap;
-use std::fs::File;
+use std::fs;
 use std::hash::Hash;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 use std::ops::Range;
 
 #[derive(Debug)]
@@ -53,8 +53,11 @@ const RESOURCE_CHAIN: [ResourcePair; 7] = [
 type Seeds = Vec<usize>;
 
 pub fn read_input(file_name: &str) -> (Seeds, HashMap<ResourcePair, Vec<Mapping>>) {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
+    read_input_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn read_input_from_str(input: &str) -> (Seeds, HashMap<ResourcePair, Vec<Mapping>>) {
+    let mut reader = input.as_bytes();
     let mut buf = String::new();
 
     // read seeds
@@ -75,7 +78,7 @@ pub fn read_input(file_name: &str) -> (Seeds, HashMap<ResourcePair, Vec<Mapping>
         // skip an empty line and the line with description
         _ = reader.read_line(&mut String::new()).unwrap();
         while let Ok(n) = reader.read_line(&mut buf) {
-            if n == 0 || buf == "\r\n" {
+            if n == 0 || buf.trim().is_empty() {
                 break;
             }
 
@@ -114,14 +117,22 @@ fn calc_location(mut value: usize, resource_mappings: &HashMap<ResourcePair, Vec
 }
 
 fn seed_fertilizer_part_1(filename: &str) -> usize {
-    let (seeds, resource_mappings) = read_input(filename);
+    seed_fertilizer_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn seed_fertilizer_part_1_from_str(input: &str) -> usize {
+    let (seeds, resource_mappings) = read_input_from_str(input);
 
     seeds.iter().map(|value| calc_location(*value, &resource_mappings)).min().unwrap()
 }
 
 
 fn seed_fertilizer_part_2(filename: &str) -> usize {
-    let (seeds, resource_mappings) = read_input(filename);
+    seed_fertilizer_part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn seed_fertilizer_part_2_from_str(input: &str) -> usize {
+    let (seeds, resource_mappings) = read_input_from_str(input);
 
     let mut master_map = HashMap::new();
 
@@ -1,12 +1,15 @@
 #![allow(dead_code)]
 
 use std::collections::VecDeque;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
+use std::io::BufRead;
 
 pub fn read_input_part_1(file_name: &str) -> Vec<u32> {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
+    read_input_part_1_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn read_input_part_1_from_str(input: &str) -> Vec<u32> {
+    let mut reader = input.as_bytes();
     let mut buf = String::new();
     let mut numbers = Vec::new();
 
@@ -198,8 +201,11 @@ impl<'a> AhoCorasick<'a> {
 
 
 pub fn read_input_part_2(file_name: &str) -> Vec<u32> {
-    let file = File::open(file_name).unwrap();
-    let mut reader = BufReader::new(file);
+    read_input_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn read_input_part_2_from_str(input: &str) -> Vec<u32> {
+    let mut reader = input.as_bytes();
     let mut buf = String::new();
     let mut numbers = Vec::new();
 
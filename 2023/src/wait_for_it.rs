@@ -1,8 +1,12 @@
+use std::fs;
 use std::io::BufRead;
 
 pub fn read_input(file_name: &str) -> Vec<(usize, usize)> {
-    let file = std::fs::File::open(file_name).unwrap();
-    let mut reader = std::io::BufReader::new(file);
+    read_input_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn read_input_from_str(input: &str) -> Vec<(usize, usize)> {
+    let mut reader = input.as_bytes();
 
     let mut buf = String::new();
     _ = reader.read_line(&mut buf).unwrap();
@@ -57,7 +61,11 @@ fn count_integers_in_interval(start: f64, end: f64) -> usize {
 }
 
 fn wait_for_it_part_1(filename: &str) -> usize {
-    let input = read_input(filename);
+    wait_for_it_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn wait_for_it_part_1_from_str(input: &str) -> usize {
+    let input = read_input_from_str(input);
 
     input.iter()
         .map(|(t, s)| calc_quadratic_solutions(*t as f64, *s as f64))
@@ -67,7 +75,11 @@ fn wait_for_it_part_1(filename: &str) -> usize {
 
 
 fn wait_for_it_part_2(filename: &str) -> usize {
-    let input = read_input(filename);
+    wait_for_it_part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn wait_for_it_part_2_from_str(input: &str) -> usize {
+    let input = read_input_from_str(input);
 
     let time = input.iter()
         .map(|(t, _)| t.to_string())
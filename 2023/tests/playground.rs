@@ -0,0 +1,22 @@
+//! Runs in a headless wasm runner, see ../playground/build.sh
+
+#![cfg(target_arch = "wasm32")]
+
+use advent_of_code_2023::playground::{solve, solved_days};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn solve_input_example() {
+    let input = include_str!("../inputs/6_input_example.txt");
+
+    assert_eq!(solve(6, 1, input), Ok("288".to_string()));
+    assert_eq!(solve(6, 2, input), Ok("71503".to_string()));
+}
+
+#[wasm_bindgen_test]
+fn solve_missing_day() {
+    assert!(solve(2, 1, "").is_err());
+    assert!(solve(5, 2, "").is_err());
+    assert!(solve(8, 1, "").is_err());
+    assert_eq!(solved_days(), vec![1, 3, 4, 5, 6, 7]);
+}
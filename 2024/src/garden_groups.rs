@@ -23,10 +23,8 @@ enum Side {
     B,
 }
 
-fn read_input(file_name: &str) -> (Vec<Vec<usize>>, Vec<char>) {
-    let file_content = read_to_string(file_name).unwrap();
-
-    let number_to_flower_map = file_content
+fn parse_input(input: &str) -> (Vec<Vec<usize>>, Vec<char>) {
+    let number_to_flower_map = input
         .lines()
         .map(|l| l.chars())
         .flatten()
@@ -35,7 +33,7 @@ fn read_input(file_name: &str) -> (Vec<Vec<usize>>, Vec<char>) {
         .copied()
         .collect::<Vec<_>>();
 
-    let matrix = file_content
+    let matrix = input
         .lines()
         .map(|l| {
             l.chars()
@@ -139,7 +137,11 @@ fn traverse_garden(matrix: Vec<Vec<usize>>) -> Vec<VertexData> {
 }
 
 fn garden_groups_part_1(filename: &str) -> u32 {
-    let (matrix, _) = read_input(filename);
+    garden_groups_part_1_from_str(&read_to_string(filename).unwrap())
+}
+
+pub(crate) fn garden_groups_part_1_from_str(input: &str) -> u32 {
+    let (matrix, _) = parse_input(input);
 
     let vertex_summaries = traverse_garden(matrix);
 
@@ -161,7 +163,11 @@ fn garden_groups_part_1(filename: &str) -> u32 {
 }
 
 fn garden_groups_part_2(filename: &str) -> u32 {
-    let (matrix, _) = read_input(filename);
+    garden_groups_part_2_from_str(&read_to_string(filename).unwrap())
+}
+
+pub(crate) fn garden_groups_part_2_from_str(input: &str) -> u32 {
+    let (matrix, _) = parse_input(input);
 
     let vertex_summaries = traverse_garden(matrix);
 
@@ -2,49 +2,55 @@ use std::collections::{BinaryHeap, HashMap};
 use std::fs;
 
 pub fn read_input(file_name: &str) -> [BinaryHeap<u32>; 2] {
+    read_input_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn read_input_from_str(input: &str) -> [BinaryHeap<u32>; 2] {
     let mut left_column = BinaryHeap::new();
     let mut right_column = BinaryHeap::new();
 
-    fs::read_to_string(file_name)
-        .unwrap()
-        .lines()
-        .for_each(|line| {
-            let mut parts = line.split("   ");
-            left_column.push(parts.next().unwrap().parse::<u32>().unwrap());
-            right_column.push(parts.last().unwrap().parse::<u32>().unwrap());
-        });
+    input.lines().for_each(|line| {
+        let mut parts = line.split("   ");
+        left_column.push(parts.next().unwrap().parse::<u32>().unwrap());
+        right_column.push(parts.last().unwrap().parse::<u32>().unwrap());
+    });
 
     [left_column, right_column]
 }
 
 pub fn read_input_part_2(file_name: &str) -> [HashMap<u32, usize>; 2] {
+    read_input_part_2_from_str(&fs::read_to_string(file_name).unwrap())
+}
+
+pub fn read_input_part_2_from_str(input: &str) -> [HashMap<u32, usize>; 2] {
     let mut left_column = HashMap::new();
     let mut right_column = HashMap::new();
 
-    fs::read_to_string(file_name)
-        .unwrap()
-        .lines()
-        .for_each(|line| {
-            let mut parts = line.split("   ");
-            let left = parts.next().unwrap().parse::<u32>().unwrap();
-            let right = parts.last().unwrap().parse::<u32>().unwrap();
-
-            left_column
-                .entry(left)
-                .and_modify(move |x| *x += 1)
-                .or_insert(1);
-
-            right_column
-                .entry(right)
-                .and_modify(move |x| *x += 1)
-                .or_insert(1);
-        });
+    input.lines().for_each(|line| {
+        let mut parts = line.split("   ");
+        let left = parts.next().unwrap().parse::<u32>().unwrap();
+        let right = parts.last().unwrap().parse::<u32>().unwrap();
+
+        left_column
+            .entry(left)
+            .and_modify(move |x| *x += 1)
+            .or_insert(1);
+
+        right_column
+            .entry(right)
+            .and_modify(move |x| *x += 1)
+            .or_insert(1);
+    });
 
     [left_column, right_column]
 }
 
 fn historian_hysteria_part_1(filename: &str) -> u32 {
-    let [left, right] = read_input(filename);
+    historian_hysteria_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn historian_hysteria_part_1_from_str(input: &str) -> u32 {
+    let [left, right] = read_input_from_str(input);
 
     left.into_sorted_vec()
         .iter()
@@ -54,7 +60,11 @@ fn historian_hysteria_part_1(filename: &str) -> u32 {
 }
 
 fn historian_hysteria_part_2(filename: &str) -> usize {
-    let [left, right] = read_input_part_2(filename);
+    historian_hysteria_part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn historian_hysteria_part_2_from_str(input: &str) -> usize {
+    let [left, right] = read_input_part_2_from_str(input);
 
     left.iter()
         .into_iter()
@@ -64,6 +74,11 @@ fn historian_hysteria_part_2(filename: &str) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::input_generator::location_lists;
+
     use super::*;
 
     #[test]
@@ -97,4 +112,22 @@ mod tests {
         println!("part 2 - original - answer: {:?}", answer);
         assert_eq!(answer, 23082277);
     }
+
+    #[test]
+    fn part_1_generated_input() {
+        let input = location_lists(&mut StdRng::seed_from_u64(1), 1000, 300);
+        let answer = historian_hysteria_part_1_from_str(&input);
+
+        println!("part 1 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 648334);
+    }
+
+    #[test]
+    fn part_2_generated_input() {
+        let input = location_lists(&mut StdRng::seed_from_u64(1), 1000, 300);
+        let answer = historian_hysteria_part_2_from_str(&input);
+
+        println!("part 2 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 174742194);
+    }
 }
@@ -5,9 +5,8 @@ type VertexValues = HashMap<usize, u32>;
 
 type AdjMatrix = Vec<Vec<usize>>;
 
-fn read_input(file_name: &str) -> (AdjMatrix, VertexValues, Vec<usize>) {
-    let map = fs::read_to_string(file_name)
-        .unwrap()
+fn parse_input(input: &str) -> (AdjMatrix, VertexValues, Vec<usize>) {
+    let map = input
         .lines()
         .map(|line| {
             line.chars()
@@ -116,7 +115,11 @@ fn find_accessible_ends_all_trials(
 }
 
 fn hoof_it_part_1(filename: &str) -> usize {
-    let (adj_matrix, vertex_values, start_vertices) = read_input(filename);
+    hoof_it_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn hoof_it_part_1_from_str(input: &str) -> usize {
+    let (adj_matrix, vertex_values, start_vertices) = parse_input(input);
 
     start_vertices
         .iter()
@@ -125,7 +128,11 @@ fn hoof_it_part_1(filename: &str) -> usize {
 }
 
 fn hoof_it_part_2(filename: &str) -> usize {
-    let (adj_matrix, vertex_values, start_vertices) = read_input(filename);
+    hoof_it_part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn hoof_it_part_2_from_str(input: &str) -> usize {
+    let (adj_matrix, vertex_values, start_vertices) = parse_input(input);
 
     start_vertices
         .iter()
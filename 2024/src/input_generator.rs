@@ -0,0 +1,135 @@
+//! Seeded generators of shareable inputs, for the puzzles whose solutions are worth testing
+//! on other data or at other sizes: days 1, 2 and 15.
+
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const MOVES_PER_LINE: usize = 1000;
+
+/// Random warehouse map and robot moves in the format of `warehouse_woes`.
+/// The map is `width` x `height` including its surrounding wall, `walls` and `boxes` are placed inside.
+pub fn warehouse(
+    rng: &mut impl Rng,
+    width: usize,
+    height: usize,
+    walls: usize,
+    boxes: usize,
+    moves: usize,
+) -> String {
+    assert!(width >= 3 && height >= 3, "warehouse has no inside");
+    let inside = (width - 2) * (height - 2);
+    assert!(walls + boxes < inside, "no place left for the robot");
+
+    let mut tiles = vec!['#'; walls];
+    tiles.extend(vec!['O'; boxes]);
+    tiles.push('@');
+    tiles.extend(vec!['.'; inside - tiles.len()]);
+    tiles.shuffle(rng);
+
+    let wall_row = "#".repeat(width);
+    let map = tiles
+        .chunks(width - 2)
+        .map(|row| format!("#{}#", row.iter().collect::<String>()));
+
+    let moves = (0..moves)
+        .map(|_| *['<', '>', '^', 'v'].choose(rng).unwrap())
+        .chunks(MOVES_PER_LINE)
+        .into_iter()
+        .map(|line| line.collect::<String>())
+        .collect::<Vec<_>>();
+
+    [wall_row.clone()]
+        .into_iter()
+        .chain(map)
+        .chain([wall_row, String::new()])
+        .chain(moves)
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Random pairs of location ids in the format of `historian_hysteria`, drawn from `ids` different ids
+/// so that the right list repeats some of the left ones.
+pub fn location_lists(rng: &mut impl Rng, pairs: usize, ids: usize) -> String {
+    assert!(ids >= 1, "lists need an id to hold");
+
+    let ids = (0..ids)
+        .map(|_| rng.gen_range(10_000..100_000))
+        .collect::<Vec<u32>>();
+
+    (0..pairs)
+        .map(|_| {
+            let left = ids.choose(rng).unwrap();
+            let right = ids.choose(rng).unwrap();
+
+            format!("{left}   {right}\n")
+        })
+        .collect()
+}
+
+/// Random reports in the format of `red_nosed_reports`, mostly safe ones with an occasional bad level.
+pub fn reports(rng: &mut impl Rng, count: usize) -> String {
+    (0..count)
+        .map(|_| {
+            let sign = if rng.gen_bool(0.5) { 1 } else { -1 };
+            let mut level: i32 = rng.gen_range(20..=80);
+            let mut levels = vec![level];
+            for _ in 0..rng.gen_range(4..=7) {
+                let step = if rng.gen_bool(0.1) {
+                    *[0, 4, -2].choose(rng).unwrap()
+                } else {
+                    rng.gen_range(1..=3)
+                };
+                level += sign * step;
+                levels.push(level);
+            }
+
+            levels.iter().join(" ") + "\n"
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::warehouse_woes::{warehouse_woes_part_1_from_str, warehouse_woes_part_2_from_str};
+
+    use super::*;
+
+    #[test]
+    fn same_seed_same_input() {
+        let first = warehouse(&mut StdRng::seed_from_u64(15), 20, 10, 15, 30, 100);
+        let second = warehouse(&mut StdRng::seed_from_u64(15), 20, 10, 15, 30, 100);
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn warehouse_is_solvable() {
+        let input = warehouse(&mut StdRng::seed_from_u64(15), 20, 10, 15, 30, 2500);
+
+        assert_eq!(input.lines().count(), 10 + 1 + 3);
+        assert!(warehouse_woes_part_1_from_str(&input) > 0);
+        assert!(warehouse_woes_part_2_from_str(&input) > 0);
+    }
+
+    #[test]
+    fn location_lists_repeat_ids() {
+        let input = location_lists(&mut StdRng::seed_from_u64(1), 100, 20);
+
+        assert_eq!(input.lines().count(), 100);
+        assert!(input.split_whitespace().unique().count() <= 20);
+    }
+
+    #[test]
+    fn reports_have_levels() {
+        let input = reports(&mut StdRng::seed_from_u64(2), 100);
+
+        assert_eq!(input.lines().count(), 100);
+        assert!(input
+            .lines()
+            .all(|line| (5..=8).contains(&line.split(' ').count())));
+    }
+}
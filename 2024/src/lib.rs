@@ -8,3 +8,5 @@ mod warehouse_woes;
 mod restroom_redoubt;
 mod claw_contraption;
 mod t16_reindeer_maze;
+pub mod playground;
+pub mod input_generator;
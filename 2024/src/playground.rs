@@ -0,0 +1,111 @@
+use std::any::Any;
+use std::panic;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::garden_groups::{garden_groups_part_1_from_str, garden_groups_part_2_from_str};
+use crate::historian_hysteria::{
+    historian_hysteria_part_1_from_str, historian_hysteria_part_2_from_str,
+};
+use crate::hoof_it::{hoof_it_part_1_from_str, hoof_it_part_2_from_str};
+use crate::plutonian_pebbles::plutonian_pebbles_part_1_from_str;
+use crate::red_nosed_reports::{
+    red_nosed_reports_part_1_from_str, red_nosed_reports_part_2_from_str,
+};
+use crate::restroom_redoubt::{
+    restroom_redoubt_part_1_from_str, restroom_redoubt_part_2_from_str, Floor,
+};
+use crate::warehouse_woes::{warehouse_woes_part_1_from_str, warehouse_woes_part_2_from_str};
+
+type Solver = fn(&str) -> String;
+
+const RESTROOM_FLOOR: Floor = Floor {
+    width: 101,
+    height: 103,
+};
+
+/// Solution of given day and part, taking the puzzle input and using the puzzle's parameters.
+fn solver(day: u32, part: u32) -> Option<Solver> {
+    let solver: Solver = match (day, part) {
+        (1, 1) => |input| historian_hysteria_part_1_from_str(input).to_string(),
+        (1, 2) => |input| historian_hysteria_part_2_from_str(input).to_string(),
+        (2, 1) => |input| red_nosed_reports_part_1_from_str(input).to_string(),
+        (2, 2) => |input| red_nosed_reports_part_2_from_str(input).to_string(),
+        (10, 1) => |input| hoof_it_part_1_from_str(input).to_string(),
+        (10, 2) => |input| hoof_it_part_2_from_str(input).to_string(),
+        (11, 1) => |input| plutonian_pebbles_part_1_from_str(input).to_string(),
+        (12, 1) => |input| garden_groups_part_1_from_str(input).to_string(),
+        (12, 2) => |input| garden_groups_part_2_from_str(input).to_string(),
+        (14, 1) => |input| restroom_redoubt_part_1_from_str(input, &RESTROOM_FLOOR).to_string(),
+        (14, 2) => |input| restroom_redoubt_part_2_from_str(input, &RESTROOM_FLOOR).to_string(),
+        (15, 1) => |input| warehouse_woes_part_1_from_str(input).to_string(),
+        (15, 2) => |input| warehouse_woes_part_2_from_str(input).to_string(),
+        _ => return None,
+    };
+
+    Some(solver)
+}
+
+/// Makes panics visible in the browser console, since they abort the wasm module.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(start)]
+fn start() {
+    console_error_panic_hook::set_once();
+}
+
+/// Solves the puzzle for the input given as a string.
+/// Fails if there is no solution of given day and part.
+/// A panic of the solution is caught as an error natively only: panics can't unwind in wasm,
+/// they abort the module and reach JS as a `RuntimeError`, see ../playground/index.html.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn solve(day: u32, part: u32, input: &str) -> Result<String, String> {
+    let solver = solver(day, part).ok_or(format!("no solution of day {day} part {part}"))?;
+
+    panic::catch_unwind(|| solver(input)).map_err(panic_message)
+}
+
+/// Days having a solution of at least one part.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn solved_days() -> Vec<u32> {
+    (1..=25)
+        .filter(|&day| solver(day, 1).is_some() || solver(day, 2).is_some())
+        .collect()
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "solution panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn solve_input_example() {
+        let input = fs::read_to_string("inputs/1_input_example.txt").unwrap();
+
+        assert_eq!(solve(1, 1, &input), Ok("11".to_string()));
+        assert_eq!(solve(1, 2, &input), Ok("31".to_string()));
+    }
+
+    #[test]
+    fn solve_missing_day() {
+        assert!(solve(3, 1, "").is_err());
+        assert!(solve(16, 1, "").is_err());
+        assert_eq!(solved_days(), vec![1, 2, 10, 11, 12, 14, 15]);
+    }
+
+    #[test]
+    fn solve_invalid_input() {
+        assert!(solve(1, 1, "1   x").is_err());
+    }
+}
@@ -24,9 +24,8 @@ enum Result {
     TwoNumbers(u64, u64),
 }
 
-fn read_input(file_name: &str) -> Vec<u64> {
-    fs::read_to_string(file_name)
-        .unwrap()
+fn parse_input(input: &str) -> Vec<u64> {
+    input
         .lines()
         .next()
         .unwrap()
@@ -97,7 +96,11 @@ fn solution(numbers: Vec<u64>, rounds_count: usize) -> usize {
 }
 
 fn plutonian_pebbles_part_1(filename: &str) -> usize {
-    let numbers = read_input(filename);
+    plutonian_pebbles_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn plutonian_pebbles_part_1_from_str(input: &str) -> usize {
+    let numbers = parse_input(input);
 
     solution(numbers, 25)
 }
@@ -108,7 +111,11 @@ enum VertexSum {
 }
 
 fn plutonian_pebbles_part_2(filename: &str, rounds_count: usize) -> u32 {
-    let numbers = read_input(filename);
+    plutonian_pebbles_part_2_from_str(&fs::read_to_string(filename).unwrap(), rounds_count)
+}
+
+pub(crate) fn plutonian_pebbles_part_2_from_str(input: &str, rounds_count: usize) -> u32 {
+    let numbers = parse_input(input);
 
     let mut adj_matrix = Vec::<Vec<usize>>::new();
     let mut vertex_values = Vec::<Node>::new();
@@ -283,7 +290,7 @@ mod tests {
 
     #[test]
     fn brute_force_test() {
-        let numbers = read_input("inputs/11_input_example.txt");
+        let numbers = parse_input(&fs::read_to_string("inputs/11_input_example.txt").unwrap());
         let answer = brute_force(numbers, 25);
 
         println!("part 1 - example - answer: {:?}", answer);
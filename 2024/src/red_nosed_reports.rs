@@ -2,9 +2,8 @@ use std::fs;
 
 type Levels = Vec<i32>;
 
-fn read_input(file_name: &str) -> Vec<Levels> {
-    fs::read_to_string(file_name)
-        .unwrap()
+fn parse_input(input: &str) -> Vec<Levels> {
+    input
         .lines()
         .map(|line| {
             line.split(" ")
@@ -15,7 +14,11 @@ fn read_input(file_name: &str) -> Vec<Levels> {
 }
 
 fn red_nosed_reports_part_1(filename: &str) -> usize {
-    let levels = read_input(filename);
+    red_nosed_reports_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn red_nosed_reports_part_1_from_str(input: &str) -> usize {
+    let levels = parse_input(input);
 
     let mut counter = levels.len();
 
@@ -55,7 +58,11 @@ fn is_row_valid(levels: &Levels) -> bool {
 }
 
 fn red_nosed_reports_part_2(filename: &str) -> usize {
-    let levels = read_input(filename);
+    red_nosed_reports_part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn red_nosed_reports_part_2_from_str(input: &str) -> usize {
+    let levels = parse_input(input);
     let mut counter = 0;
 
     let levels_increasing = levels
@@ -94,6 +101,11 @@ fn red_nosed_reports_part_2(filename: &str) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::input_generator::reports;
+
     use super::*;
 
     #[test]
@@ -127,4 +139,22 @@ mod tests {
         println!("part 2 - original - answer: {:?}", answer);
         assert_eq!(answer, 381);
     }
+
+    #[test]
+    fn part_1_generated_input() {
+        let input = reports(&mut StdRng::seed_from_u64(2), 1000);
+        let answer = red_nosed_reports_part_1_from_str(&input);
+
+        println!("part 1 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 559);
+    }
+
+    #[test]
+    fn part_2_generated_input() {
+        let input = reports(&mut StdRng::seed_from_u64(2), 1000);
+        let answer = red_nosed_reports_part_2_from_str(&input);
+
+        println!("part 2 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 796);
+    }
 }
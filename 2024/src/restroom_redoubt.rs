@@ -1,9 +1,9 @@
 use itertools::{Itertools, MinMaxResult};
 use std::fs;
 
-struct Floor {
-    width: i32,
-    height: i32,
+pub(crate) struct Floor {
+    pub(crate) width: i32,
+    pub(crate) height: i32,
 }
 
 #[derive(Debug)]
@@ -19,9 +19,8 @@ struct Position {
     y: i32,
 }
 
-fn read_input(file_name: &str) -> Vec<RobotInfo> {
-    fs::read_to_string(file_name)
-        .unwrap()
+fn parse_input(input: &str) -> Vec<RobotInfo> {
+    input
         .lines()
         .map(|line| {
             let coords = line
@@ -58,7 +57,11 @@ fn calc_position(robot_info: &RobotInfo, round_num: i32, floor: &Floor) -> Posit
 }
 
 fn restroom_redoubt_part_1(filename: &str, floor: &Floor) -> i32 {
-    let inputs = read_input(filename);
+    restroom_redoubt_part_1_from_str(&fs::read_to_string(filename).unwrap(), floor)
+}
+
+pub(crate) fn restroom_redoubt_part_1_from_str(input: &str, floor: &Floor) -> i32 {
+    let inputs = parse_input(input);
 
     let total_rounds = 100;
     let half_width = (floor.width - 1) / 2;
@@ -90,7 +93,11 @@ fn restroom_redoubt_part_1(filename: &str, floor: &Floor) -> i32 {
 }
 
 fn restroom_redoubt_part_2(filename: &str, floor: &Floor) -> usize {
-    let inputs = read_input(filename);
+    restroom_redoubt_part_2_from_str(&fs::read_to_string(filename).unwrap(), floor)
+}
+
+pub(crate) fn restroom_redoubt_part_2_from_str(input: &str, floor: &Floor) -> usize {
+    let inputs = parse_input(input);
 
     let mut round_num = 1usize;
     loop {
@@ -56,9 +56,8 @@ enum Tile {
     End,
 }
 
-fn read_input(file_name: &str) -> Vec<Vec<Tile>> {
-    fs::read_to_string(file_name)
-        .unwrap()
+fn parse_input(input: &str) -> Vec<Vec<Tile>> {
+    input
         .lines()
         .map(|line| {
             line.chars()
@@ -279,10 +278,7 @@ fn dijkstra(adj_list: &AdjList, start_pos: Pos) {
 
         // queue.retain(|pos| *pos != *u);
 
-        for (neighbor_pos, dist, dir) in adj_list[u].iter() {
-
-        }
-
+        for (neighbor_pos, dist, dir) in adj_list[u].iter() {}
     }
 }
 
@@ -326,7 +322,11 @@ fn print_path_2(path: &HashSet<Pos>) {
 }
 
 fn reindeer_maze_part_1(filename: &str) -> usize {
-    let map = read_input(filename);
+    reindeer_maze_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn reindeer_maze_part_1_from_str(input: &str) -> usize {
+    let map = parse_input(input);
     // print_map(&map);
     let adj_list = convert_to_adj_list(&map);
     for (pos, info) in adj_list.iter() {
@@ -355,7 +355,11 @@ fn reindeer_maze_part_1(filename: &str) -> usize {
 }
 
 fn reindeer_maze_part_2(filename: &str) -> usize {
-    let _ = read_input(filename);
+    reindeer_maze_part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn reindeer_maze_part_2_from_str(input: &str) -> usize {
+    let _ = parse_input(input);
     todo!()
 }
 
@@ -57,9 +57,8 @@ impl Position {
     }
 }
 
-fn read_directions(file_name: &str) -> Vec<Direction> {
-    fs::read_to_string(file_name)
-        .unwrap()
+fn parse_directions(input: &str) -> Vec<Direction> {
+    input
         .lines()
         .skip_while(|line| !line.is_empty())
         .flat_map(|line| {
@@ -76,12 +75,11 @@ fn read_directions(file_name: &str) -> Vec<Direction> {
         .collect::<Vec<_>>()
 }
 
-fn read_input(file_name: &str) -> (HashMap<Position, ObstacleType>, Position, Vec<Direction>) {
+fn parse_input(input: &str) -> (HashMap<Position, ObstacleType>, Position, Vec<Direction>) {
     let mut obstacles = HashMap::new();
     let mut robot_pos = Position { row: 0, col: 0 };
 
-    fs::read_to_string(file_name)
-        .unwrap()
+    input
         .lines()
         .enumerate()
         .take_while(|(_, line)| !line.is_empty())
@@ -116,18 +114,17 @@ fn read_input(file_name: &str) -> (HashMap<Position, ObstacleType>, Position, Ve
             })
         });
 
-    let directions = read_directions(file_name);
+    let directions = parse_directions(input);
 
     (obstacles, robot_pos, directions)
 }
 
-fn read_input_2(file_name: &str) -> (HashMap<Position, usize>, Position, Vec<Direction>) {
+fn parse_input_2(input: &str) -> (HashMap<Position, usize>, Position, Vec<Direction>) {
     let mut obstacles = HashMap::new();
     let mut robot_pos = Position { row: 0, col: 0 };
     let mut obstacle_num = 1; // 0 is reserved for Walls
 
-    fs::read_to_string(file_name)
-        .unwrap()
+    input
         .lines()
         .enumerate()
         .take_while(|(_, line)| !line.is_empty())
@@ -177,13 +174,17 @@ fn read_input_2(file_name: &str) -> (HashMap<Position, usize>, Position, Vec<Dir
             })
         });
 
-    let directions = read_directions(file_name);
+    let directions = parse_directions(input);
 
     (obstacles, robot_pos, directions)
 }
 
 fn warehouse_woes_part_1(filename: &str) -> usize {
-    let (mut map, mut robot_pos, directions) = read_input(filename);
+    warehouse_woes_part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn warehouse_woes_part_1_from_str(input: &str) -> usize {
+    let (mut map, mut robot_pos, directions) = parse_input(input);
 
     for direction in directions {
         try_move(&mut map, &mut robot_pos, &direction, true);
@@ -290,7 +291,11 @@ fn try_move(
 }
 
 fn warehouse_woes_part_2(filename: &str) -> usize {
-    let (mut map, mut robot_pos, directions) = read_input_2(filename);
+    warehouse_woes_part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn warehouse_woes_part_2_from_str(input: &str) -> usize {
+    let (mut map, mut robot_pos, directions) = parse_input_2(input);
     print_map_2(&map, &robot_pos);
 
     for direction in directions {
@@ -404,6 +409,11 @@ fn get_box_positions(map: &HashMap<Position, usize>, current_pos: &Position) ->
 
 #[cfg(test)]
 mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::input_generator::warehouse;
+
     use super::*;
 
     #[test]
@@ -451,4 +461,22 @@ mod tests {
         println!("part 2 - original - answer: {:?}", answer);
         assert_eq!(answer, 1489116);
     }
+
+    #[test]
+    fn part_1_generated_input() {
+        let input = warehouse(&mut StdRng::seed_from_u64(15), 50, 50, 200, 500, 20_000);
+        let answer = warehouse_woes_part_1_from_str(&input);
+
+        println!("part 1 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 1239438);
+    }
+
+    #[test]
+    fn part_2_generated_input() {
+        let input = warehouse(&mut StdRng::seed_from_u64(15), 50, 50, 200, 500, 20_000);
+        let answer = warehouse_woes_part_2_from_str(&input);
+
+        println!("part 2 - generated - answer: {:?}", answer);
+        assert_eq!(answer, 1251221);
+    }
 }
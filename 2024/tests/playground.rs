@@ -0,0 +1,21 @@
+//! Runs in a headless wasm runner, see ../playground/build.sh
+
+#![cfg(target_arch = "wasm32")]
+
+use advent_of_code_2024::playground::{solve, solved_days};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn solve_input_example() {
+    let input = include_str!("../inputs/1_input_example.txt");
+
+    assert_eq!(solve(1, 1, input), Ok("11".to_string()));
+    assert_eq!(solve(1, 2, input), Ok("31".to_string()));
+}
+
+#[wasm_bindgen_test]
+fn solve_missing_day() {
+    assert!(solve(3, 1, "").is_err());
+    assert!(solve(16, 1, "").is_err());
+    assert_eq!(solved_days(), vec![1, 2, 10, 11, 12, 14, 15]);
+}
@@ -3,9 +3,8 @@ use std::fs;
 
 type BatteryBank = Vec<u64>;
 
-fn read_input(file_name: &str) -> Vec<BatteryBank> {
-    fs::read_to_string(file_name)
-        .unwrap()
+fn parse_input(input: &str) -> Vec<BatteryBank> {
+    input
         .lines()
         .filter(|line| !line.is_empty())
         .map(|line| {
@@ -18,13 +17,21 @@ fn read_input(file_name: &str) -> Vec<BatteryBank> {
 }
 
 fn part_1(filename: &str) -> u64 {
-    let battery_banks = read_input(filename);
+    part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn part_1_from_str(input: &str) -> u64 {
+    let battery_banks = parse_input(input);
 
     battery_banks.iter().map(analyze_bank).sum()
 }
 
 fn part_2(filename: &str) -> u64 {
-    let battery_banks = read_input(filename);
+    part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn part_2_from_str(input: &str) -> u64 {
+    let battery_banks = parse_input(input);
 
     battery_banks
         .iter()
@@ -1,152 +1,76 @@
 use std::fs;
 
+use cellular_automaton::{Automaton, DenseGrid, Grid};
+
 type Range = (usize, usize);
 type Id = usize;
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Default)]
 enum Tile {
+    #[default]
     Empty,
     Roll,
 }
 
-fn read_input(file_name: &str) -> Vec<Vec<Tile>> {
-    let file_content = fs::read_to_string(file_name).unwrap();
-
-    let mut columns = file_content
+fn parse_input(input: &str) -> DenseGrid<Tile> {
+    let rows = input
         .lines()
         .map(|line| {
-            let mut row = line
-                .chars()
+            line.chars()
                 .map(|c| match c {
                     '.' => Tile::Empty,
                     '@' => Tile::Roll,
                     _ => panic!("invalid tile type"),
                 })
-                .collect::<Vec<_>>();
-
-            row.insert(0, Tile::Empty);
-            row.push(Tile::Empty);
-            row
+                .collect::<Vec<_>>()
         })
         .collect::<Vec<_>>();
 
-    columns.insert(0, vec![Tile::Empty; columns[0].len()]);
-    columns.push(vec![Tile::Empty; columns[0].len()]);
-    columns
+    DenseGrid::from_rows(rows)
 }
 
 fn part_1(filename: &str) -> usize {
-    let map = read_input(filename);
-
-    let width = map[0].len();
-    let height = map.len();
-
-    let mut heat_map = create_heat_map(&map, width, height);
+    part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
 
-    print_heat_map(width, height, &mut heat_map);
+pub(crate) fn part_1_from_str(input: &str) -> usize {
+    let mut map = parse_input(input);
 
-    let mut sum = 0;
-    for (row_i, row) in map.iter().enumerate() {
-        for (col_i, tile) in row.iter().enumerate() {
-            if *tile == Tile::Roll && heat_map[row_i][col_i] < 4 {
-                sum += 1;
-            }
-        }
-    }
-
-    sum
+    removal().step(&mut map)
 }
 
 fn part_2(filename: &str) -> usize {
-    let mut map = read_input(filename);
-
-    let width = map[0].len();
-    let height = map.len();
-
-    let mut heat_map = create_heat_map(&map, width, height);
-
-    let mut sum = 0;
-    let mut removed_count = 0;
-    loop {
-        for row_i in 1..width - 1 {
-            for col_i in 1..height - 1 {
-                let tile = &mut map[row_i][col_i];
-                if *tile == Tile::Roll && heat_map[row_i][col_i] < 4 {
-                    removed_count += 1;
-
-                    *tile = Tile::Empty;
-                    heat_map[row_i][col_i] = 0;
-                    heat_map[row_i - 1][col_i] -= 1;
-                    heat_map[row_i + 1][col_i] -= 1;
-                    heat_map[row_i][col_i - 1] -= 1;
-                    heat_map[row_i][col_i + 1] -= 1;
-                    heat_map[row_i - 1][col_i - 1] -= 1;
-                    heat_map[row_i - 1][col_i + 1] -= 1;
-                    heat_map[row_i + 1][col_i - 1] -= 1;
-                    heat_map[row_i + 1][col_i + 1] -= 1;
-                }
-            }
-        }
-
-        if removed_count == 0 {
-            break;
-        }
-
-        sum += removed_count;
-        removed_count = 0;
-    }
-
-    sum
+    part_2_from_str(&fs::read_to_string(filename).unwrap())
 }
 
-fn create_heat_map(map: &Vec<Vec<Tile>>, width: usize, height: usize) -> Vec<Vec<i32>> {
-    let mut heat_map = vec![vec![0; width]; height];
-
-    for row_i in 1..width - 1 {
-        for col_i in 1..height - 1 {
-            if map[row_i][col_i] == Tile::Empty {
-                continue;
-            }
-
-            if map[row_i][col_i + 1] == Tile::Roll {
-                heat_map[row_i][col_i] += 1;
-                heat_map[row_i][col_i + 1] += 1;
-            }
+pub(crate) fn part_2_from_str(input: &str) -> usize {
+    let mut map = parse_input(input);
+    let rolls_count = map.count(&Tile::Roll);
 
-            if map[row_i + 1][col_i] == Tile::Roll {
-                heat_map[row_i][col_i] += 1;
-                heat_map[row_i + 1][col_i] += 1;
-            }
+    removal().run_until_stable(&mut map, usize::MAX);
 
-            if map[row_i + 1][col_i + 1] == Tile::Roll {
-                heat_map[row_i][col_i] += 1;
-                heat_map[row_i + 1][col_i + 1] += 1;
-            }
-
-            if map[row_i - 1][col_i + 1] == Tile::Roll {
-                heat_map[row_i][col_i] += 1;
-                heat_map[row_i - 1][col_i + 1] += 1;
-            }
-        }
-    }
-    heat_map
+    rolls_count - map.count(&Tile::Roll)
 }
 
-fn print_heat_map(width: usize, height: usize, heat_map: &mut Vec<Vec<i32>>) {
-    for row in heat_map.iter().skip(1).take(height - 2) {
-        for tile in row.iter().skip(1).take(width - 2) {
-            if *tile < 4 {
-                print!("x")
-            } else {
-                print!("{}", tile);
-            }
+/// A roll is accessible, and thus removed, when fewer than four of its eight neighbours are rolls.
+fn removal<'a>() -> Automaton<'a, DenseGrid<Tile>> {
+    Automaton::synchronous(|map: &DenseGrid<Tile>, point, _| match map.get(point) {
+        Tile::Roll
+            if point
+                .neighbours_8()
+                .iter()
+                .filter(|&&p| map.get(p) == Tile::Roll)
+                .count()
+                < 4 =>
+        {
+            Tile::Empty
         }
-        println!();
-    }
+        tile => tile,
+    })
 }
 
-fn print_map(map: &Vec<Vec<Tile>>) {
-    for row in map {
+fn print_map(map: &DenseGrid<Tile>) {
+    for row in map.rows() {
         for tile in row {
             match tile {
                 Tile::Empty => print!("."),
@@ -163,7 +87,7 @@ mod tests {
 
     #[test]
     fn read_example_input() {
-        _ = read_input("inputs/04_input_example_1.txt");
+        _ = parse_input(&fs::read_to_string("inputs/04_input_example_1.txt").unwrap());
     }
 
     #[test]
@@ -4,10 +4,8 @@ use std::fs;
 type Range = (usize, usize);
 type Id = usize;
 
-fn read_input(file_name: &str) -> (Vec<Range>, Vec<Id>) {
-    let file_content = fs::read_to_string(file_name).unwrap();
-
-    let ranges = file_content
+fn parse_input(input: &str) -> (Vec<Range>, Vec<Id>) {
+    let ranges = input
         .lines()
         .take_while(|s| !s.is_empty())
         .map(|line| {
@@ -19,7 +17,7 @@ fn read_input(file_name: &str) -> (Vec<Range>, Vec<Id>) {
         })
         .collect::<Vec<_>>();
 
-    let ids = file_content
+    let ids = input
         .lines()
         .skip_while(|s| !s.is_empty())
         .skip(1)
@@ -30,7 +28,11 @@ fn read_input(file_name: &str) -> (Vec<Range>, Vec<Id>) {
 }
 
 fn part_1(filename: &str) -> usize {
-    let (ranges, ids) = read_input(filename);
+    part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn part_1_from_str(input: &str) -> usize {
+    let (ranges, ids) = parse_input(input);
 
     let merged = merge_ranges_into_sorted_vec(ranges);
 
@@ -57,7 +59,11 @@ fn part_1(filename: &str) -> usize {
 }
 
 fn part_2(filename: &str) -> usize {
-    let (ranges, _ids) = read_input(filename);
+    part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn part_2_from_str(input: &str) -> usize {
+    let (ranges, _ids) = parse_input(input);
 
     let merged = merge_ranges_into_sorted_vec(ranges);
 
@@ -104,7 +110,8 @@ mod tests {
 
     #[test]
     fn read_example_input() {
-        let (ranges, ids) = read_input("inputs/05_input_example.txt");
+        let (ranges, ids) =
+            parse_input(&fs::read_to_string("inputs/05_input_example.txt").unwrap());
 
         for r in ranges {
             println!("{:?}", r);
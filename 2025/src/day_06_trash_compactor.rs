@@ -6,11 +6,9 @@ enum Operation {
     Multiply,
 }
 
-fn read_input(file_name: &str) -> (Vec<Vec<i64>>, Vec<Operation>) {
-    let file_content = fs::read_to_string(file_name).unwrap();
-
+fn parse_input(input: &str) -> (Vec<Vec<i64>>, Vec<Operation>) {
     let mut lines = Vec::new();
-    for line in file_content.lines() {
+    for line in input.lines() {
         let first_chat = line.chars().next().unwrap();
         if first_chat.is_digit(10) || first_chat.is_whitespace() {
             let numbers = line
@@ -28,11 +26,9 @@ fn read_input(file_name: &str) -> (Vec<Vec<i64>>, Vec<Operation>) {
     unreachable!();
 }
 
-fn read_input_2(file_name: &str) -> (Vec<Vec<u8>>, Vec<Operation>) {
-    let file_content = fs::read_to_string(file_name).unwrap();
-
+fn parse_input_2(input: &str) -> (Vec<Vec<u8>>, Vec<Operation>) {
     let mut lines = Vec::new();
-    for line in file_content.lines() {
+    for line in input.lines() {
         let first_char = line.chars().next().unwrap();
         if first_char.is_digit(10) || first_char.is_whitespace() {
             lines.push(line.as_bytes().to_vec());
@@ -56,7 +52,11 @@ fn parse_operations(line: &str) -> Vec<Operation> {
 }
 
 fn part_1(filename: &str) -> i64 {
-    let (numbers_lines, operations) = read_input(filename);
+    part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn part_1_from_str(input: &str) -> i64 {
+    let (numbers_lines, operations) = parse_input(input);
     let numbers_lines = transpose(numbers_lines);
 
     let mut total = 0i64;
@@ -71,7 +71,11 @@ fn part_1(filename: &str) -> i64 {
 }
 
 fn part_2(filename: &str) -> i64 {
-    let (mut rows, operations) = read_input_2(filename);
+    part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn part_2_from_str(input: &str) -> i64 {
+    let (mut rows, operations) = parse_input_2(input);
 
     let max_width = rows.iter().map(|a| a.len()).max().unwrap();
     for rows in rows.iter_mut() {
@@ -146,7 +150,8 @@ mod tests {
 
     #[test]
     fn read_example_input() {
-        let (numbers_lines, operations) = read_input("inputs/06_input_example_1.txt");
+        let (numbers_lines, operations) =
+            parse_input(&fs::read_to_string("inputs/06_input_example_1.txt").unwrap());
 
         println!("numbers_lines: {:?}", transpose(numbers_lines));
         println!("operations: {:?}", operations);
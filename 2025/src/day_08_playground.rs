@@ -59,9 +59,8 @@ impl Debug for Connection {
     }
 }
 
-fn read_input(file_name: &str) -> Vec<Coord> {
-    fs::read_to_string(file_name)
-        .unwrap()
+fn parse_input(input: &str) -> Vec<Coord> {
+    input
         .lines()
         .map(|line| {
             let parts = line
@@ -74,7 +73,11 @@ fn read_input(file_name: &str) -> Vec<Coord> {
 }
 
 fn part_1(filename: &str, laps: usize) -> usize {
-    let coords = read_input(filename);
+    part_1_from_str(&fs::read_to_string(filename).unwrap(), laps)
+}
+
+pub(crate) fn part_1_from_str(input: &str, laps: usize) -> usize {
+    let coords = parse_input(input);
     let mut heap = calc_distances(coords);
     let mut circuits = Vec::<HashSet<Coord>>::new();
 
@@ -96,7 +99,11 @@ fn part_1(filename: &str, laps: usize) -> usize {
 }
 
 fn part_2(filename: &str) -> i64 {
-    let coords = read_input(filename);
+    part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn part_2_from_str(input: &str) -> i64 {
+    let coords = parse_input(input);
     let coords_len = coords.len();
     let mut heap = calc_distances(coords.clone());
     let mut circuits = Vec::<HashSet<Coord>>::new();
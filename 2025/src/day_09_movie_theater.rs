@@ -4,9 +4,8 @@ use std::fs;
 
 type Coord = (i64, i64);
 
-fn read_input(file_name: &str) -> Vec<Coord> {
-    fs::read_to_string(file_name)
-        .unwrap()
+fn parse_input(input: &str) -> Vec<Coord> {
+    input
         .lines()
         .map(|line| {
             let parts = line
@@ -19,7 +18,11 @@ fn read_input(file_name: &str) -> Vec<Coord> {
 }
 
 fn part_1(filename: &str) -> i64 {
-    let coords = read_input(filename);
+    part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn part_1_from_str(input: &str) -> i64 {
+    let coords = parse_input(input);
     let mut current_best = 0;
 
     for i in 0..coords.len() {
@@ -37,7 +40,11 @@ fn part_1(filename: &str) -> i64 {
 }
 
 fn part_2(filename: &str) -> i64 {
-    let coords = read_input(filename);
+    part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn part_2_from_str(input: &str) -> i64 {
+    let coords = parse_input(input);
     println!("coords: {:?}", coords);
 
     let coords_all = coords
@@ -13,9 +13,8 @@ struct Instruction {
     joltage_requirements: Vec<i64>,
 }
 
-fn read_input(file_name: &str) -> Vec<Instruction> {
-    fs::read_to_string(file_name)
-        .unwrap()
+fn parse_input(input: &str) -> Vec<Instruction> {
+    input
         .lines()
         .map(|line| {
             let segments = line.trim().split_whitespace().collect::<Vec<_>>();
@@ -60,7 +59,11 @@ fn read_input(file_name: &str) -> Vec<Instruction> {
 }
 
 fn part_1(filename: &str) -> usize {
-    let input = read_input(filename);
+    part_1_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn part_1_from_str(input: &str) -> usize {
+    let input = parse_input(input);
 
     input
         .into_iter()
@@ -206,7 +209,11 @@ fn buttons_to_matrix(buttons: &[ButtonMask]) -> DMatrix<f64> {
 }
 
 fn part_2(filename: &str) -> i64 {
-    _ = read_input(filename);
+    part_2_from_str(&fs::read_to_string(filename).unwrap())
+}
+
+pub(crate) fn part_2_from_str(input: &str) -> i64 {
+    _ = parse_input(input);
 
     todo!()
 }
@@ -9,14 +9,12 @@ struct Input {
     index_to_name: HashMap<usize, String>,
 }
 
-fn read_input(file_name: &str) -> Input {
+fn parse_input(input: &str) -> Input {
     let mut rows_parsed = Vec::<(&str, Vec<&str>)>::new();
     let mut name_to_index = HashMap::new();
     let mut index_to_name = HashMap::new();
 
-    let content = fs::read_to_string(file_name).unwrap();
-
-    content.lines().for_each(|line| {
+    input.lines().for_each(|line| {
         let line_elements = line.split(": ").collect::<Vec<_>>();
         let name = line_elements[0];
         let neighbors = line_elements[1].split_whitespace().collect::<Vec<_>>();
@@ -46,7 +44,11 @@ fn read_input(file_name: &str) -> Input {
 }
 
 fn part_1(filename: &str, input_vertx_name: &str) -> usize {
-    let input = read_input(filename);
+    part_1_from_str(&fs::read_to_string(filename).unwrap(), input_vertx_name)
+}
+
+pub(crate) fn part_1_from_str(input: &str, input_vertx_name: &str) -> usize {
+    let input = parse_input(input);
 
     let end_vertex = input.name_to_index["out"];
     let start_vertex = input.name_to_index[input_vertx_name];
@@ -69,7 +71,11 @@ fn part_1(filename: &str, input_vertx_name: &str) -> usize {
 }
 
 fn part_2(filename: &str, input_vertx_name: &str) -> i64 {
-    let input = read_input(filename);
+    part_2_from_str(&fs::read_to_string(filename).unwrap(), input_vertx_name)
+}
+
+pub(crate) fn part_2_from_str(input: &str, input_vertx_name: &str) -> i64 {
+    let input = parse_input(input);
 
     // print_input(&input.neighbors_list, &input.index_to_name);
 
@@ -7,3 +7,4 @@ mod day_08_playground;
 mod day_09_movie_theater;
 mod day_10_factory;
 mod day_11_reactor;
+pub mod playground;
@@ -0,0 +1,97 @@
+use std::any::Any;
+use std::panic;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    day_03_lobby, day_04_printing_department, day_05_cafeteria, day_06_trash_compactor,
+    day_08_playground, day_09_movie_theater, day_11_reactor,
+};
+
+type Solver = fn(&str) -> String;
+
+/// Solution of given day and part, taking the puzzle input and using the puzzle's parameters.
+fn solver(day: u32, part: u32) -> Option<Solver> {
+    let solver: Solver = match (day, part) {
+        (3, 1) => |input| day_03_lobby::part_1_from_str(input).to_string(),
+        (4, 1) => |input| day_04_printing_department::part_1_from_str(input).to_string(),
+        (4, 2) => |input| day_04_printing_department::part_2_from_str(input).to_string(),
+        (5, 1) => |input| day_05_cafeteria::part_1_from_str(input).to_string(),
+        (5, 2) => |input| day_05_cafeteria::part_2_from_str(input).to_string(),
+        (6, 1) => |input| day_06_trash_compactor::part_1_from_str(input).to_string(),
+        (6, 2) => |input| day_06_trash_compactor::part_2_from_str(input).to_string(),
+        (8, 1) => |input| day_08_playground::part_1_from_str(input, 1000).to_string(),
+        (8, 2) => |input| day_08_playground::part_2_from_str(input).to_string(),
+        (9, 1) => |input| day_09_movie_theater::part_1_from_str(input).to_string(),
+        (9, 2) => |input| day_09_movie_theater::part_2_from_str(input).to_string(),
+        (11, 1) => |input| day_11_reactor::part_1_from_str(input, "you").to_string(),
+        _ => return None,
+    };
+
+    Some(solver)
+}
+
+/// Makes panics visible in the browser console, since they abort the wasm module.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(start)]
+fn start() {
+    console_error_panic_hook::set_once();
+}
+
+/// Solves the puzzle for the input given as a string.
+/// Fails if there is no solution of given day and part.
+/// A panic of the solution is caught as an error natively only: panics can't unwind in wasm,
+/// they abort the module and reach JS as a `RuntimeError`, see ../playground/index.html.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn solve(day: u32, part: u32, input: &str) -> Result<String, String> {
+    let solver = solver(day, part).ok_or(format!("no solution of day {day} part {part}"))?;
+
+    panic::catch_unwind(|| solver(input)).map_err(panic_message)
+}
+
+/// Days having a solution of at least one part.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn solved_days() -> Vec<u32> {
+    (1..=25)
+        .filter(|&day| solver(day, 1).is_some() || solver(day, 2).is_some())
+        .collect()
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "solution panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn solve_input_example() {
+        let input = fs::read_to_string("inputs/04_input_example_1.txt").unwrap();
+
+        assert_eq!(solve(4, 1, &input), Ok("13".to_string()));
+        assert_eq!(solve(4, 2, &input), Ok("43".to_string()));
+    }
+
+    #[test]
+    fn solve_missing_day() {
+        assert!(solve(7, 1, "").is_err());
+        assert!(solve(3, 2, "").is_err());
+        assert!(solve(10, 1, "").is_err());
+        assert_eq!(solved_days(), vec![3, 4, 5, 6, 8, 9, 11]);
+    }
+
+    #[test]
+    fn solve_invalid_input() {
+        assert_eq!(solve(4, 1, "@x@"), Err("invalid tile type".to_string()));
+    }
+}
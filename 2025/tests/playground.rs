@@ -0,0 +1,22 @@
+//! Runs in a headless wasm runner, see ../playground/build.sh
+
+#![cfg(target_arch = "wasm32")]
+
+use advent_of_code_2025::playground::{solve, solved_days};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn solve_input_example() {
+    let input = include_str!("../inputs/04_input_example_1.txt");
+
+    assert_eq!(solve(4, 1, input), Ok("13".to_string()));
+    assert_eq!(solve(4, 2, input), Ok("43".to_string()));
+}
+
+#[wasm_bindgen_test]
+fn solve_missing_day() {
+    assert!(solve(7, 1, "").is_err());
+    assert!(solve(3, 2, "").is_err());
+    assert!(solve(10, 1, "").is_err());
+    assert_eq!(solved_days(), vec![3, 4, 5, 6, 8, 9, 11]);
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use crate::grid::{Grid, Point};
+
+type SynchronousRule<'a, G> = Box<dyn Fn(&G, Point, usize) -> <G as Grid>::Cell + 'a>;
+type ProposalRule<'a, G> = Box<dyn Fn(&G, Point, usize) -> Option<Point> + 'a>;
+type Hook<'a, G> = Box<dyn FnMut(&Step, &G) + 'a>;
+
+/// How a single round is applied to the grid.
+pub enum Rule<'a, G: Grid> {
+    /// Every candidate cell is recomputed from the previous state of the grid,
+    /// then all new values are written at once.
+    Synchronous(SynchronousRule<'a, G>),
+    /// Every occupied cell may propose a point it wants to move to. A move happens only when
+    /// the target is an empty point of the grid and nobody else proposed the same target in this round.
+    Proposal(ProposalRule<'a, G>),
+}
+
+/// Summary of a finished round, passed to the hooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    /// 1-based number of the round
+    pub round: usize,
+    /// number of cells that changed (synchronous rule) or moved (proposal rule)
+    pub changed: usize,
+}
+
+pub struct Automaton<'a, G: Grid> {
+    rule: Rule<'a, G>,
+    hooks: Vec<Hook<'a, G>>,
+    round: usize,
+}
+
+impl<'a, G: Grid> Automaton<'a, G> {
+    /// Rule arguments are: the grid before the round, the evaluated point and the round number.
+    pub fn synchronous(rule: impl Fn(&G, Point, usize) -> G::Cell + 'a) -> Automaton<'a, G> {
+        Automaton::with_rule(Rule::Synchronous(Box::new(rule)))
+    }
+
+    /// Rule arguments are: the grid before the round, the occupied point and the round number.
+    pub fn proposal(rule: impl Fn(&G, Point, usize) -> Option<Point> + 'a) -> Automaton<'a, G> {
+        Automaton::with_rule(Rule::Proposal(Box::new(rule)))
+    }
+
+    pub fn with_rule(rule: Rule<'a, G>) -> Automaton<'a, G> {
+        Automaton {
+            rule,
+            hooks: Vec::new(),
+            round: 0,
+        }
+    }
+
+    /// Registers a callback invoked after every round with the updated grid.
+    pub fn on_step(mut self, hook: impl FnMut(&Step, &G) + 'a) -> Automaton<'a, G> {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Number of rounds performed so far.
+    pub fn rounds(&self) -> usize {
+        self.round
+    }
+
+    /// Performs a single round and returns number of changed cells.
+    pub fn step(&mut self, grid: &mut G) -> usize {
+        self.round += 1;
+
+        let changed = match &self.rule {
+            Rule::Synchronous(rule) => Self::synchronous_round(rule, grid, self.round),
+            Rule::Proposal(rule) => Self::proposal_round(rule, grid, self.round),
+        };
+
+        let step = Step {
+            round: self.round,
+            changed,
+        };
+        for hook in self.hooks.iter_mut() {
+            hook(&step, grid);
+        }
+
+        changed
+    }
+
+    /// Performs given number of rounds and returns total number of changed cells.
+    pub fn run(&mut self, grid: &mut G, rounds: usize) -> usize {
+        (0..rounds).map(|_| self.step(grid)).sum()
+    }
+
+    /// Runs until a round changes nothing and returns the number of that round,
+    /// or `None` if the grid is still changing after `max_rounds`.
+    pub fn run_until_stable(&mut self, grid: &mut G, max_rounds: usize) -> Option<usize> {
+        for _ in 0..max_rounds {
+            if self.step(grid) == 0 {
+                return Some(self.round);
+            }
+        }
+
+        None
+    }
+
+    /// Runs until the predicate holds for the grid after a round and returns the number of that round,
+    /// or `None` if it does not hold within `max_rounds`.
+    pub fn run_until(
+        &mut self,
+        grid: &mut G,
+        max_rounds: usize,
+        mut predicate: impl FnMut(&G) -> bool,
+    ) -> Option<usize> {
+        for _ in 0..max_rounds {
+            self.step(grid);
+            if predicate(grid) {
+                return Some(self.round);
+            }
+        }
+
+        None
+    }
+
+    fn synchronous_round(rule: &SynchronousRule<'a, G>, grid: &mut G, round: usize) -> usize {
+        let changes = grid
+            .candidates()
+            .into_iter()
+            .filter_map(|point| {
+                let cell = rule(grid, point, round);
+                (cell != grid.get(point)).then_some((point, cell))
+            })
+            .collect::<Vec<_>>();
+
+        let changed = changes.len();
+        for (point, cell) in changes {
+            grid.set(point, cell);
+        }
+
+        changed
+    }
+
+    fn proposal_round(rule: &ProposalRule<'a, G>, grid: &mut G, round: usize) -> usize {
+        let proposals = grid
+            .occupied()
+            .into_iter()
+            .filter_map(|point| rule(grid, point, round).map(|target| (point, target)))
+            .filter(|(_, target)| grid.contains(*target) && grid.is_empty_at(*target))
+            .collect::<Vec<_>>();
+
+        let mut proposals_count = HashMap::new();
+        for (_, target) in proposals.iter() {
+            *proposals_count.entry(*target).or_insert(0usize) += 1;
+        }
+
+        let moves = proposals
+            .into_iter()
+            .filter(|(_, target)| proposals_count[target] == 1)
+            .map(|(from, to)| (from, to, grid.get(from)))
+            .collect::<Vec<_>>();
+
+        for (from, _, _) in moves.iter() {
+            grid.set(*from, G::Cell::default());
+        }
+        for (_, to, cell) in moves.iter() {
+            grid.set(*to, cell.clone());
+        }
+
+        moves.len()
+    }
+}
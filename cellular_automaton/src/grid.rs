@@ -0,0 +1,392 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::ops::Add;
+
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Ord, PartialOrd, Default)]
+pub struct Point {
+    pub row: i32,
+    pub col: i32,
+}
+
+impl Point {
+    pub const fn new(row: i32, col: i32) -> Point {
+        Point { row, col }
+    }
+
+    /// Neighbours sharing an edge, in N, S, W, E order.
+    pub fn neighbours_4(&self) -> [Point; 4] {
+        [
+            Point::new(self.row - 1, self.col),
+            Point::new(self.row + 1, self.col),
+            Point::new(self.row, self.col - 1),
+            Point::new(self.row, self.col + 1),
+        ]
+    }
+
+    /// Neighbours sharing an edge or a corner, row by row starting from the top-left one.
+    pub fn neighbours_8(&self) -> [Point; 8] {
+        [
+            Point::new(self.row - 1, self.col - 1),
+            Point::new(self.row - 1, self.col),
+            Point::new(self.row - 1, self.col + 1),
+            Point::new(self.row, self.col - 1),
+            Point::new(self.row, self.col + 1),
+            Point::new(self.row + 1, self.col - 1),
+            Point::new(self.row + 1, self.col),
+            Point::new(self.row + 1, self.col + 1),
+        ]
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point::new(self.row + rhs.row, self.col + rhs.col)
+    }
+}
+
+impl Debug for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+/// A two-dimensional board of cells. `Cell::default()` is the empty cell, which is also
+/// what is returned for points outside the board.
+pub trait Grid {
+    type Cell: Clone + PartialEq + Default;
+
+    fn get(&self, point: Point) -> Self::Cell;
+
+    fn set(&mut self, point: Point, cell: Self::Cell);
+
+    /// Points that have to be re-evaluated by a synchronous rule.
+    fn candidates(&self) -> Vec<Point>;
+
+    /// Points holding a non-empty cell.
+    fn occupied(&self) -> Vec<Point>;
+
+    /// Whether a cell can be stored at given point.
+    fn contains(&self, point: Point) -> bool;
+
+    fn is_empty_at(&self, point: Point) -> bool {
+        self.get(point) == Self::Cell::default()
+    }
+}
+
+/// Unbounded grid storing only non-empty cells.
+#[derive(Clone, Debug, Default)]
+pub struct SparseGrid<C> {
+    cells: HashMap<Point, C>,
+}
+
+impl<C: Clone + PartialEq + Default> SparseGrid<C> {
+    pub fn new() -> SparseGrid<C> {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Point, &C)> {
+        self.cells.iter()
+    }
+
+    /// Returns top-left and bottom-right corners of the smallest rectangle containing all cells.
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+
+        Some(points.fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.row.min(p.row), min.col.min(p.col)),
+                Point::new(max.row.max(p.row), max.col.max(p.col)),
+            )
+        }))
+    }
+}
+
+impl<C: Clone + PartialEq + Default> FromIterator<(Point, C)> for SparseGrid<C> {
+    fn from_iter<T: IntoIterator<Item = (Point, C)>>(iter: T) -> Self {
+        let mut grid = SparseGrid::new();
+        for (point, cell) in iter {
+            grid.set(point, cell);
+        }
+        grid
+    }
+}
+
+impl<C: Clone + PartialEq + Default> Grid for SparseGrid<C> {
+    type Cell = C;
+
+    fn get(&self, point: Point) -> C {
+        self.cells.get(&point).cloned().unwrap_or_default()
+    }
+
+    fn set(&mut self, point: Point, cell: C) {
+        if cell == C::default() {
+            self.cells.remove(&point);
+        } else {
+            self.cells.insert(point, cell);
+        }
+    }
+
+    fn candidates(&self) -> Vec<Point> {
+        let mut points = self
+            .cells
+            .keys()
+            .flat_map(|p| p.neighbours_8().into_iter().chain([*p]))
+            .collect::<Vec<_>>();
+
+        points.sort_unstable();
+        points.dedup();
+        points
+    }
+
+    fn occupied(&self) -> Vec<Point> {
+        let mut points = self.cells.keys().copied().collect::<Vec<_>>();
+        points.sort_unstable();
+        points
+    }
+
+    fn contains(&self, _point: Point) -> bool {
+        true
+    }
+}
+
+/// Rectangular grid with rows `0..height` and columns `0..width`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DenseGrid<C> {
+    width: usize,
+    height: usize,
+    cells: Vec<C>,
+}
+
+impl<C: Clone + PartialEq + Default> DenseGrid<C> {
+    pub fn new(width: usize, height: usize) -> DenseGrid<C> {
+        DenseGrid {
+            width,
+            height,
+            cells: vec![C::default(); width * height],
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<C>>) -> DenseGrid<C> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+
+        if rows.iter().any(|row| row.len() != width) {
+            panic!("all rows have to be of the same length");
+        }
+
+        DenseGrid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[C]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn count(&self, cell: &C) -> usize {
+        self.cells.iter().filter(|&c| c == cell).count()
+    }
+
+    fn index(&self, point: Point) -> usize {
+        point.row as usize * self.width + point.col as usize
+    }
+}
+
+impl<C: Clone + PartialEq + Default> Grid for DenseGrid<C> {
+    type Cell = C;
+
+    fn get(&self, point: Point) -> C {
+        if self.contains(point) {
+            self.cells[self.index(point)].clone()
+        } else {
+            C::default()
+        }
+    }
+
+    fn set(&mut self, point: Point, cell: C) {
+        if !self.contains(point) {
+            panic!("point {point:?} is outside of the grid");
+        }
+
+        let index = self.index(point);
+        self.cells[index] = cell;
+    }
+
+    fn candidates(&self) -> Vec<Point> {
+        (0..self.height as i32)
+            .flat_map(|row| (0..self.width as i32).map(move |col| Point::new(row, col)))
+            .collect()
+    }
+
+    fn occupied(&self) -> Vec<Point> {
+        self.candidates()
+            .into_iter()
+            .filter(|p| !self.is_empty_at(*p))
+            .collect()
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        (0..self.height as i32).contains(&point.row) && (0..self.width as i32).contains(&point.col)
+    }
+}
+
+/// Unbounded grid storing cells densely in a rectangle, which is reallocated larger
+/// whenever a non-empty cell is set outside of it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GrowingGrid<C> {
+    /// point stored at row 0 and column 0 of the cells
+    origin: Point,
+    cells: DenseGrid<C>,
+    len: usize,
+}
+
+impl<C: Clone + PartialEq + Default> GrowingGrid<C> {
+    pub fn new() -> GrowingGrid<C> {
+        GrowingGrid {
+            origin: Point::default(),
+            cells: DenseGrid::new(0, 0),
+            len: 0,
+        }
+    }
+
+    /// Number of non-empty cells.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns top-left and bottom-right corners of the smallest rectangle containing all cells.
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        let points = self.occupied();
+        let (first, last) = (*points.first()?, *points.last()?);
+
+        // occupied points are sorted by rows
+        let (min_col, max_col) = points.iter().fold((first.col, first.col), |(min, max), p| {
+            (min.min(p.col), max.max(p.col))
+        });
+
+        Some((
+            Point::new(first.row, min_col),
+            Point::new(last.row, max_col),
+        ))
+    }
+
+    fn local(&self, point: Point) -> Point {
+        Point::new(point.row - self.origin.row, point.col - self.origin.col)
+    }
+
+    /// Reallocates the cells, so they hold the point with a margin around it and the stored rectangle.
+    fn grow_to(&mut self, point: Point) {
+        let (min, max) = match self.bounding_box() {
+            Some((min, max)) => (
+                Point::new(min.row.min(point.row), min.col.min(point.col)),
+                Point::new(max.row.max(point.row), max.col.max(point.col)),
+            ),
+            None => (point, point),
+        };
+        let margin = 8.max((max.row - min.row).max(max.col - min.col) / 4);
+
+        let origin = Point::new(min.row - margin, min.col - margin);
+        let mut cells = DenseGrid::new(
+            (max.col - min.col + 2 * margin + 1) as usize,
+            (max.row - min.row + 2 * margin + 1) as usize,
+        );
+        for p in self.occupied() {
+            cells.set(
+                Point::new(p.row - origin.row, p.col - origin.col),
+                self.get(p),
+            );
+        }
+
+        self.origin = origin;
+        self.cells = cells;
+    }
+}
+
+impl<C: Clone + PartialEq + Default> Default for GrowingGrid<C> {
+    fn default() -> Self {
+        GrowingGrid::new()
+    }
+}
+
+impl<C: Clone + PartialEq + Default> FromIterator<(Point, C)> for GrowingGrid<C> {
+    fn from_iter<T: IntoIterator<Item = (Point, C)>>(iter: T) -> Self {
+        let mut grid = GrowingGrid::new();
+        for (point, cell) in iter {
+            grid.set(point, cell);
+        }
+        grid
+    }
+}
+
+impl<C: Clone + PartialEq + Default> Grid for GrowingGrid<C> {
+    type Cell = C;
+
+    fn get(&self, point: Point) -> C {
+        self.cells.get(self.local(point))
+    }
+
+    fn set(&mut self, point: Point, cell: C) {
+        let empty = cell == C::default();
+        if !self.cells.contains(self.local(point)) {
+            if empty {
+                return;
+            }
+            self.grow_to(point);
+        }
+
+        let local = self.local(point);
+        match (self.cells.is_empty_at(local), empty) {
+            (true, false) => self.len += 1,
+            (false, true) => self.len -= 1,
+            _ => {}
+        }
+        self.cells.set(local, cell);
+    }
+
+    /// The stored rectangle with a ring of points around it, where empty cells next to the stored ones lie.
+    fn candidates(&self) -> Vec<Point> {
+        (-1..=self.cells.height() as i32)
+            .flat_map(|row| (-1..=self.cells.width() as i32).map(move |col| Point::new(row, col)))
+            .map(|p| Point::new(p.row + self.origin.row, p.col + self.origin.col))
+            .collect()
+    }
+
+    fn occupied(&self) -> Vec<Point> {
+        self.cells
+            .occupied()
+            .into_iter()
+            .map(|p| Point::new(p.row + self.origin.row, p.col + self.origin.col))
+            .collect()
+    }
+
+    fn contains(&self, _point: Point) -> bool {
+        true
+    }
+}
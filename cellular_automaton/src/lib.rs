@@ -0,0 +1,143 @@
+mod automaton;
+mod grid;
+
+pub use automaton::{Automaton, Rule, Step};
+pub use grid::{DenseGrid, Grid, GrowingGrid, Point, SparseGrid};
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    fn glider() -> SparseGrid<bool> {
+        [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+            .into_iter()
+            .map(|(row, col)| (Point::new(row, col), true))
+            .collect()
+    }
+
+    fn game_of_life<G: Grid<Cell = bool>>() -> Automaton<'static, G> {
+        Automaton::synchronous(|grid: &G, p, _| {
+            let alive = p
+                .neighbours_8()
+                .into_iter()
+                .filter(|n| grid.get(*n))
+                .count();
+            matches!((grid.get(p), alive), (true, 2) | (_, 3))
+        })
+    }
+
+    #[test]
+    fn glider_moves_diagonally() {
+        let mut grid = glider();
+        let mut automaton = game_of_life();
+
+        automaton.run(&mut grid, 4);
+
+        let moved = glider()
+            .iter()
+            .map(|(p, _)| (*p + Point::new(1, 1), true))
+            .collect::<SparseGrid<_>>();
+        assert_eq!(grid.occupied(), moved.occupied());
+        assert_eq!(automaton.rounds(), 4);
+    }
+
+    #[test]
+    fn glider_leaves_growing_grid() {
+        let mut grid = glider()
+            .iter()
+            .map(|(p, c)| (*p, *c))
+            .collect::<GrowingGrid<_>>();
+
+        game_of_life().run(&mut grid, 80);
+
+        assert_eq!(grid.len(), 5);
+        assert_eq!(
+            grid.bounding_box(),
+            Some((Point::new(20, 20), Point::new(22, 22)))
+        );
+    }
+
+    #[test]
+    fn block_is_a_fixed_point() {
+        let mut grid = DenseGrid::from_rows(vec![
+            vec![false, false, false, false],
+            vec![false, true, true, false],
+            vec![false, true, true, false],
+        ]);
+
+        let answer = game_of_life().run_until_stable(&mut grid, 10);
+
+        assert_eq!(answer, Some(1));
+        assert_eq!(grid.count(&true), 4);
+    }
+
+    #[test]
+    fn conflicting_proposals_are_cancelled() {
+        let mut grid = [
+            (Point::new(0, 0), 'a'),
+            (Point::new(0, 2), 'b'),
+            (Point::new(2, 0), 'c'),
+        ]
+        .into_iter()
+        .collect::<SparseGrid<_>>();
+
+        // 'a' and 'b' want the same tile, 'c' moves freely
+        let mut automaton = Automaton::proposal(|_: &SparseGrid<char>, p, _| match p.col {
+            0 if p.row == 0 => Some(Point::new(0, 1)),
+            2 => Some(Point::new(0, 1)),
+            _ => Some(Point::new(p.row, p.col + 1)),
+        });
+
+        assert_eq!(automaton.step(&mut grid), 1);
+        assert_eq!(grid.get(Point::new(0, 0)), 'a');
+        assert_eq!(grid.get(Point::new(0, 2)), 'b');
+        assert_eq!(grid.get(Point::new(2, 1)), 'c');
+    }
+
+    #[test]
+    fn proposals_outside_of_dense_grid_are_rejected() {
+        let mut grid = DenseGrid::from_rows(vec![vec![true, false], vec![false, true]]);
+
+        let mut automaton =
+            Automaton::proposal(|_: &DenseGrid<bool>, p, _| Some(Point::new(p.row, p.col + 1)));
+
+        assert_eq!(automaton.step(&mut grid), 1);
+        assert_eq!(grid.occupied(), vec![Point::new(0, 1), Point::new(1, 1)]);
+        assert_eq!(automaton.run_until_stable(&mut grid, 10), Some(2));
+    }
+
+    #[test]
+    fn proposals_outside_of_growing_grid_are_accepted() {
+        let mut grid = [(Point::new(0, 0), 'a'), (Point::new(0, 1), 'b')]
+            .into_iter()
+            .collect::<GrowingGrid<_>>();
+
+        let mut automaton =
+            Automaton::proposal(|_: &GrowingGrid<char>, p, _| Some(Point::new(p.row - 20, p.col)));
+
+        assert_eq!(automaton.run(&mut grid, 3), 6);
+        assert_eq!(grid.get(Point::new(-60, 0)), 'a');
+        assert_eq!(grid.get(Point::new(-60, 1)), 'b');
+        assert_eq!(
+            grid.occupied(),
+            vec![Point::new(-60, 0), Point::new(-60, 1)]
+        );
+        assert_eq!(grid.len(), 2);
+    }
+
+    #[test]
+    fn hooks_see_every_round() {
+        let rounds = RefCell::new(Vec::new());
+        let mut grid = glider();
+
+        game_of_life()
+            .on_step(|step, grid: &SparseGrid<bool>| {
+                rounds.borrow_mut().push((step.round, grid.len()))
+            })
+            .run(&mut grid, 3);
+
+        assert_eq!(rounds.into_inner(), vec![(1, 5), (2, 5), (3, 5)]);
+    }
+}
@@ -0,0 +1,26 @@
+#!/usr/bin/env bash
+# Builds the solutions of every year to WebAssembly for the browser playground.
+#
+# Requirements:
+#   rustup target add wasm32-unknown-unknown
+#   cargo install wasm-bindgen-cli --version 0.2.129
+#
+# Serve the page with:
+#   python3 -m http.server -d playground
+#
+# Tests of the wasm build run in each year directory with:
+#   cargo test --target wasm32-unknown-unknown --features wasm --test playground
+
+set -euo pipefail
+
+root="$(cd "$(dirname "$0")/.." && pwd)"
+out_dir="$root/playground/pkg"
+
+for year in 2022 2023 2024 2025; do
+    (
+        cd "$root/$year"
+        cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+        wasm-bindgen --target web --no-typescript --out-dir "$out_dir" --out-name "aoc_$year" \
+            "target/wasm32-unknown-unknown/release/advent_of_code_$year.wasm"
+    )
+done